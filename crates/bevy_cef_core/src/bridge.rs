@@ -0,0 +1,77 @@
+//! Which frames get the `window.cef` bridge and the custom extensions.
+//!
+//! The browser process resolves each webview's policy into a
+//! [`BridgePolicyPayload`] and ships it to the render process the same way as
+//! user scripts: in the browser's extra-info dictionary at creation (under
+//! [`BRIDGE_POLICY_KEY`]) and through a [`PROCESS_MESSAGE_BRIDGE_POLICY`]
+//! message whenever it changes. In `on_context_created` the render process
+//! removes `window.cef` from frames whose origin the policy does not admit and
+//! evaluates only the extensions it admits there.
+//!
+//! The render process cannot be trusted to enforce this on its own, so the
+//! browser side checks the origin attached to every incoming message again.
+
+use crate::util::origin_allowed;
+use bevy::platform::collections::HashMap;
+use serde::{Deserialize, Serialize};
+
+/// Extra-info dictionary key holding the JSON-encoded initial policy.
+pub const BRIDGE_POLICY_KEY: &str = "bridge_policy";
+
+/// Process message carrying a replacement policy to the render process.
+pub const PROCESS_MESSAGE_BRIDGE_POLICY: &str = "bridge-policy";
+
+/// A fully resolved bridge policy as seen by the render process.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BridgePolicyPayload {
+    /// Origins that get `window.cef`, written `scheme://host[:port]`. `*`
+    /// admits every origin.
+    pub origins: Vec<String>,
+    /// Origins per `CefExtensions` name. Extensions not listed follow
+    /// `origins`.
+    pub extensions: HashMap<String, Vec<String>>,
+}
+
+impl Default for BridgePolicyPayload {
+    /// Admits `cef://localhost` only.
+    fn default() -> Self {
+        Self {
+            origins: vec!["cef://localhost".to_string()],
+            extensions: HashMap::default(),
+        }
+    }
+}
+
+impl BridgePolicyPayload {
+    /// Whether frames at `origin` get `window.cef`.
+    pub fn exposes_api(&self, origin: &str) -> bool {
+        origin_allowed(&self.origins, origin)
+    }
+
+    /// Whether frames at `origin` get the extension registered as `name`.
+    pub fn exposes_extension(&self, name: &str, origin: &str) -> bool {
+        match self.extensions.get(name) {
+            Some(origins) => origin_allowed(origins, origin),
+            None => self.exposes_api(origin),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extensions_follow_the_api_origins_unless_listed() {
+        let mut policy = BridgePolicyPayload::default();
+        policy
+            .extensions
+            .insert("analytics".to_string(), vec!["*".to_string()]);
+
+        assert!(policy.exposes_api("cef://localhost"));
+        assert!(!policy.exposes_api("https://ads.example.com"));
+        assert!(policy.exposes_extension("myGame", "cef://localhost"));
+        assert!(!policy.exposes_extension("myGame", "https://ads.example.com"));
+        assert!(policy.exposes_extension("analytics", "https://ads.example.com"));
+    }
+}
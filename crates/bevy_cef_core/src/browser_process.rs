@@ -1,35 +1,55 @@
 #[cfg(target_os = "macos")]
 pub mod accelerated_paint;
+pub mod accessibility_handler;
 mod app;
+pub mod audio_handler;
+mod backend;
 mod browser_process_handler;
 mod browsers;
-#[cfg(target_os = "windows")]
+#[cfg(any(target_os = "windows", target_os = "linux"))]
 pub mod cef_command;
-#[cfg(target_os = "windows")]
+#[cfg(any(target_os = "windows", target_os = "linux"))]
 pub mod cef_thread;
 mod client_handler;
 mod command_line_config;
 mod context_menu_handler;
+pub mod dev_tools_message_observer;
 pub mod display_handler;
+pub mod drag_drop;
 pub mod drag_handler;
 mod extensions;
+pub mod focus_handler;
+pub mod keyboard_handler;
+pub mod life_span_handler;
 pub mod load_handler;
 mod localhost;
 mod message_pump;
 mod renderer_handler;
 mod request_context_handler;
+pub mod request_handler;
+mod webview_senders;
 
+pub use accessibility_handler::*;
 pub use app::*;
+pub use audio_handler::*;
+pub use backend::*;
 pub use browser_process_handler::*;
 pub use browsers::*;
 pub use client_handler::*;
 pub use command_line_config::*;
 pub use context_menu_handler::*;
+pub use dev_tools_message_observer::*;
 pub use display_handler::*;
+pub use drag_drop::*;
 pub use drag_handler::*;
 pub use extensions::*;
+pub use focus_handler::*;
+pub use keyboard_handler::*;
+pub use life_span_handler::*;
 pub use load_handler::*;
 pub use localhost::*;
 pub use message_pump::*;
 pub use renderer_handler::*;
 pub use request_context_handler::*;
+pub use request_handler::*;
+pub use webview_senders::*;
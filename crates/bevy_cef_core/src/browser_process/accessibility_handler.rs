@@ -0,0 +1,112 @@
+//! CEF AccessibilityHandler — forwards the page's accessibility tree.
+//!
+//! Only called once renderer accessibility is enabled on the browser, which
+//! happens for webviews created with an accessibility sender. Mirrors the
+//! `LoadHandlerBuilder` pattern (`load_handler.rs`).
+
+use crate::prelude::IntoString;
+use async_channel::Sender;
+use bevy::prelude::Entity;
+use cef::rc::{Rc, RcImpl};
+use cef::{ImplAccessibilityHandler, JsonWriterOptions, Value, WrapAccessibilityHandler, sys};
+use cef_dll_sys::cef_json_writer_options_t;
+
+/// Which callback produced an [`AccessibilityMessage`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessibilityChange {
+    /// `OnAccessibilityTreeChange`: tree updates and events.
+    Tree,
+    /// `OnAccessibilityLocationChange`: new bounds of existing nodes.
+    Location,
+}
+
+/// A change to a webview's accessibility tree, as the JSON form of the
+/// `CefValue` Chromium serialized it to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccessibilityMessage {
+    pub webview: Entity,
+    pub change: AccessibilityChange,
+    pub value: serde_json::Value,
+}
+
+pub type AccessibilitySenderInner = Sender<AccessibilityMessage>;
+
+/// ## Reference
+///
+/// - [`CefAccessibilityHandler Class Reference`](https://cef-builds.spotifycdn.com/docs/145/classCefAccessibilityHandler.html)
+pub struct AccessibilityHandlerBuilder {
+    object: *mut RcImpl<sys::_cef_accessibility_handler_t, Self>,
+    webview: Entity,
+    sender: AccessibilitySenderInner,
+}
+
+impl AccessibilityHandlerBuilder {
+    pub fn build(webview: Entity, sender: AccessibilitySenderInner) -> cef::AccessibilityHandler {
+        cef::AccessibilityHandler::new(Self {
+            object: core::ptr::null_mut(),
+            webview,
+            sender,
+        })
+    }
+
+    fn send(&self, change: AccessibilityChange, value: Option<&mut Value>) {
+        let json = cef::write_json(
+            value,
+            JsonWriterOptions::from(cef_json_writer_options_t::JSON_WRITER_DEFAULT),
+        )
+        .into_string();
+        let Ok(value) = serde_json::from_str(&json) else {
+            return;
+        };
+        let _ = self.sender.send_blocking(AccessibilityMessage {
+            webview: self.webview,
+            change,
+            value,
+        });
+    }
+}
+
+impl Rc for AccessibilityHandlerBuilder {
+    fn as_base(&self) -> &sys::cef_base_ref_counted_t {
+        unsafe {
+            let base = &*self.object;
+            core::mem::transmute(&base.cef_object)
+        }
+    }
+}
+
+impl Clone for AccessibilityHandlerBuilder {
+    fn clone(&self) -> Self {
+        let object = unsafe {
+            let rc_impl = &mut *self.object;
+            rc_impl.interface.add_ref();
+            rc_impl
+        };
+        Self {
+            object,
+            webview: self.webview,
+            sender: self.sender.clone(),
+        }
+    }
+}
+
+impl WrapAccessibilityHandler for AccessibilityHandlerBuilder {
+    fn wrap_rc(&mut self, object: *mut RcImpl<sys::_cef_accessibility_handler_t, Self>) {
+        self.object = object;
+    }
+}
+
+impl ImplAccessibilityHandler for AccessibilityHandlerBuilder {
+    fn on_accessibility_tree_change(&self, value: Option<&mut Value>) {
+        self.send(AccessibilityChange::Tree, value);
+    }
+
+    fn on_accessibility_location_change(&self, value: Option<&mut Value>) {
+        self.send(AccessibilityChange::Location, value);
+    }
+
+    #[inline]
+    fn get_raw(&self) -> *mut sys::_cef_accessibility_handler_t {
+        self.object.cast()
+    }
+}
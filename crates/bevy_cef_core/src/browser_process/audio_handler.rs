@@ -0,0 +1,186 @@
+//! CEF AudioHandler — captures a browser's PCM output instead of playing it on
+//! the OS device.
+//!
+//! Mirrors the `LoadHandlerBuilder` pattern (`load_handler.rs`). Only attached
+//! to browsers whose [`WebviewSenders::audio`](crate::prelude::WebviewSenders)
+//! is set.
+
+use async_channel::Sender;
+use bevy::prelude::Entity;
+use cef::rc::{Rc, RcImpl};
+use cef::{AudioParameters, Browser, CefString, ImplAudioHandler, WrapAudioHandler, sys};
+use std::os::raw::c_int;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// What happened to a webview's audio stream.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AudioStreamEvent {
+    /// The page started producing sound.
+    Started { sample_rate: u32, channels: u16 },
+    /// Interleaved `f32` samples, `channels` per frame.
+    Packet(Vec<f32>),
+    /// The page went silent or was navigated away.
+    Stopped,
+    /// CEF could not capture the stream.
+    Error(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioStreamMessage {
+    pub webview: Entity,
+    pub event: AudioStreamEvent,
+}
+
+pub type AudioStreamSenderInner = Sender<AudioStreamMessage>;
+
+/// ## Reference
+///
+/// - [`CefAudioHandler Class Reference`](https://cef-builds.spotifycdn.com/docs/145/classCefAudioHandler.html)
+pub struct AudioHandlerBuilder {
+    object: *mut RcImpl<sys::_cef_audio_handler_t, Self>,
+    webview: Entity,
+    sender: AudioStreamSenderInner,
+    /// Channel count of the running stream; packets do not carry it.
+    channels: Arc<AtomicUsize>,
+}
+
+impl AudioHandlerBuilder {
+    pub fn build(webview: Entity, sender: AudioStreamSenderInner) -> cef::AudioHandler {
+        cef::AudioHandler::new(Self {
+            object: core::ptr::null_mut(),
+            webview,
+            sender,
+            channels: Arc::new(AtomicUsize::new(0)),
+        })
+    }
+
+    fn send(&self, event: AudioStreamEvent) {
+        let _ = self.sender.send_blocking(AudioStreamMessage {
+            webview: self.webview,
+            event,
+        });
+    }
+}
+
+impl Rc for AudioHandlerBuilder {
+    fn as_base(&self) -> &sys::cef_base_ref_counted_t {
+        unsafe {
+            let base = &*self.object;
+            core::mem::transmute(&base.cef_object)
+        }
+    }
+}
+
+impl Clone for AudioHandlerBuilder {
+    fn clone(&self) -> Self {
+        let object = unsafe {
+            let rc_impl = &mut *self.object;
+            rc_impl.interface.add_ref();
+            rc_impl
+        };
+        Self {
+            object,
+            webview: self.webview,
+            sender: self.sender.clone(),
+            channels: self.channels.clone(),
+        }
+    }
+}
+
+impl WrapAudioHandler for AudioHandlerBuilder {
+    fn wrap_rc(&mut self, object: *mut RcImpl<sys::_cef_audio_handler_t, Self>) {
+        self.object = object;
+    }
+}
+
+impl ImplAudioHandler for AudioHandlerBuilder {
+    fn get_audio_parameters(
+        &self,
+        _browser: Option<&mut Browser>,
+        _params: Option<&mut AudioParameters>,
+    ) -> c_int {
+        // Keep CEF's default layout and rate; the Bevy side adapts to them.
+        1
+    }
+
+    fn on_audio_stream_started(
+        &self,
+        _browser: Option<&mut Browser>,
+        params: Option<&AudioParameters>,
+        channels: c_int,
+    ) {
+        let channels = channels.max(0) as usize;
+        self.channels.store(channels, Ordering::Relaxed);
+        self.send(AudioStreamEvent::Started {
+            sample_rate: params
+                .map(|p| p.sample_rate.max(0) as u32)
+                .unwrap_or(48_000),
+            channels: channels as u16,
+        });
+    }
+
+    fn on_audio_stream_packet(
+        &self,
+        _browser: Option<&mut Browser>,
+        data: *mut *const f32,
+        frames: c_int,
+        _pts: i64,
+    ) {
+        let channels = self.channels.load(Ordering::Relaxed);
+        if data.is_null() || frames <= 0 || channels == 0 {
+            return;
+        }
+        // SAFETY: CEF passes `channels` planes of `frames` samples each, valid
+        // for the duration of this call.
+        let planes: Vec<&[f32]> = unsafe {
+            std::slice::from_raw_parts(data, channels)
+                .iter()
+                .map(|plane| std::slice::from_raw_parts(*plane, frames as usize))
+                .collect()
+        };
+        self.send(AudioStreamEvent::Packet(interleave(&planes)));
+    }
+
+    fn on_audio_stream_stopped(&self, _browser: Option<&mut Browser>) {
+        self.channels.store(0, Ordering::Relaxed);
+        self.send(AudioStreamEvent::Stopped);
+    }
+
+    fn on_audio_stream_error(&self, _browser: Option<&mut Browser>, message: Option<&CefString>) {
+        self.channels.store(0, Ordering::Relaxed);
+        self.send(AudioStreamEvent::Error(
+            message.map(|m| m.to_string()).unwrap_or_default(),
+        ));
+    }
+
+    #[inline]
+    fn get_raw(&self) -> *mut sys::_cef_audio_handler_t {
+        self.object.cast()
+    }
+}
+
+/// Turns CEF's planar buffers (one slice per channel) into interleaved frames.
+fn interleave(planes: &[&[f32]]) -> Vec<f32> {
+    let frames = planes.iter().map(|p| p.len()).min().unwrap_or(0);
+    let mut samples = Vec::with_capacity(frames * planes.len());
+    for frame in 0..frames {
+        samples.extend(planes.iter().map(|plane| plane[frame]));
+    }
+    samples
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn planes_are_interleaved_frame_by_frame() {
+        let left = [0.1, 0.2, 0.3];
+        let right = [-0.1, -0.2, -0.3];
+        assert_eq!(
+            interleave(&[&left, &right]),
+            vec![0.1, -0.1, 0.2, -0.2, 0.3, -0.3]
+        );
+    }
+}
@@ -0,0 +1,806 @@
+//! The browser operations Bevy systems drive, behind a trait so the CEF-backed
+//! [`Browsers`] can be swapped for a test double.
+
+use crate::prelude::*;
+use crate::user_script::UserScriptPayload;
+use bevy::input::touch::TouchPhase;
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+#[allow(deprecated)]
+use raw_window_handle::RawWindowHandle;
+use std::path::PathBuf;
+
+/// Everything the plugin asks of the browsers behind its webviews.
+///
+/// [`Browsers`] implements it on top of CEF, and `ProxyBackend` on top of CEF's
+/// own UI thread on Windows and Linux. Each method mirrors the [`Browsers`] method of the
+/// same name; see there for details.
+pub trait WebviewBackend: 'static {
+    /// Creates the browser of a webview. Its handlers report through `senders`,
+    /// including `lifecycle` once the browser is up.
+    #[allow(clippy::too_many_arguments)]
+    fn create_browser(
+        &mut self,
+        webview: Entity,
+        uri: &str,
+        webview_size: Vec2,
+        initial_dpr: f32,
+        requester: Requester,
+        senders: WebviewSenders,
+        user_scripts: &[UserScriptPayload],
+        bridge: &BridgePolicyPayload,
+        window_handle: Option<RawWindowHandle>,
+    ) -> Result<(), BrowserCreationError>;
+
+    fn close(&mut self, webview: &Entity);
+
+    fn send_external_begin_frame_for(&self, webview: &Entity);
+
+    fn navigate(&self, webview: &Entity, url: &str);
+
+    fn reload_webview(&self, webview: &Entity);
+
+    /// Reloads every webview.
+    fn reload(&self);
+
+    fn go_back(&self, webview: &Entity);
+
+    fn go_forward(&self, webview: &Entity);
+
+    fn resize(&self, webview: &Entity, size: Vec2);
+
+    fn set_dpr(&self, webview: &Entity, dpr: f32);
+
+    fn notify_screen_info_changed(&self, webview: &Entity);
+
+    fn send_mouse_move(
+        &self,
+        webview: &Entity,
+        buttons: &[MouseButton],
+        position: Vec2,
+        mouse_leave: bool,
+    );
+
+    fn send_mouse_click(
+        &self,
+        webview: &Entity,
+        position: Vec2,
+        button: PointerButton,
+        mouse_up: bool,
+    );
+
+    fn send_mouse_wheel(&self, webview: &Entity, position: Vec2, delta: Vec2);
+
+    fn send_touch(
+        &self,
+        webview: &Entity,
+        id: i32,
+        position: Vec2,
+        phase: TouchPhase,
+        pressure: f32,
+    );
+
+    fn send_key(&self, webview: &Entity, event: cef::KeyEvent);
+
+    fn exec_edit_command(&self, webview: &Entity, cmd: EditCommand);
+
+    fn drag_target_enter(&self, webview: &Entity, files: &[PathBuf], position: Vec2);
+
+    fn drag_target_over(&self, webview: &Entity, position: Vec2);
+
+    fn drag_target_leave(&self, webview: &Entity);
+
+    fn drag_target_drop(&self, webview: &Entity, position: Vec2);
+
+    fn cancel_page_drag(&self, webview: &Entity);
+
+    fn set_focus(&self, webview: &Entity, focused: bool);
+
+    fn emit_event(&self, webview: &Entity, id: &str, event: &serde_json::Value);
+
+    fn execute_javascript(&self, webview: &Entity, code: &str);
+
+    fn set_user_scripts(&self, webview: &Entity, scripts: &[UserScriptPayload]);
+
+    fn set_bridge_policy(&self, webview: &Entity, policy: &BridgePolicyPayload);
+
+    fn show_devtool(&self, webview: &Entity);
+
+    /// Shows the DevTools of `webview` in the webview `surface`, replacing the
+    /// surface's own browser.
+    fn show_devtool_in(
+        &mut self,
+        webview: &Entity,
+        surface: Entity,
+        surface_size: Vec2,
+        dpr: f32,
+        senders: WebviewSenders,
+    ) -> Result<(), BrowserCreationError>;
+
+    fn inspect_element_at(&self, webview: &Entity, position: Vec2);
+
+    /// Starts reporting the webview's CDP results and events through `sender`.
+    /// Returns whether the webview has a browser to observe, or `None` when
+    /// that is reported later through `sender` as [`CdpMessage::Opened`].
+    fn open_cdp(&mut self, webview: &Entity, sender: CdpSenderInner) -> Option<bool>;
+
+    fn close_cdp(&mut self, webview: &Entity);
+
+    /// Sends a raw CDP command. Returns `false` when the browser refused it.
+    fn send_cdp(&self, webview: &Entity, message: &str) -> bool;
+
+    fn close_devtools(&self, webview: &Entity);
+
+    fn set_zoom_level(&self, webview: &Entity, zoom_level: f64);
+
+    fn set_audio_muted(&self, webview: &Entity, muted: bool);
+
+    fn set_hidden(&self, webview: &Entity, hidden: bool);
+
+    fn set_frame_rate(&self, webview: &Entity, fps: i32);
+
+    fn invalidate(&self, webview: &Entity);
+
+    fn ping(&self, webview: &Entity);
+
+    fn set_ime_composition(&self, text: &str, cursor_utf16: Option<u32>);
+
+    fn ime_cancel_composition(&self);
+
+    fn ime_finish_composition(&self, keep_selection: bool);
+
+    fn set_ime_commit_text(&self, text: &str);
+
+    /// Drains the frames painted since the last call.
+    #[cfg(not(target_os = "macos"))]
+    fn try_receive_textures(&self) -> Vec<RenderTextureMessage>;
+
+    /// Drains the latest IOSurface of each webview accepted by `keep`.
+    ///
+    /// Backends without GPU surfaces return nothing.
+    #[cfg(target_os = "macos")]
+    fn take_latest_webview_iosurfaces(
+        &self,
+        _keep: &dyn Fn(Entity) -> bool,
+    ) -> Vec<(Entity, RetainedIoSurface)> {
+        Vec::new()
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+impl WebviewBackend for Browsers {
+    fn create_browser(
+        &mut self,
+        webview: Entity,
+        uri: &str,
+        webview_size: Vec2,
+        initial_dpr: f32,
+        requester: Requester,
+        senders: WebviewSenders,
+        user_scripts: &[UserScriptPayload],
+        bridge: &BridgePolicyPayload,
+        window_handle: Option<RawWindowHandle>,
+    ) -> Result<(), BrowserCreationError> {
+        Browsers::create_browser(
+            self,
+            webview,
+            uri,
+            webview_size,
+            initial_dpr,
+            requester,
+            senders,
+            user_scripts,
+            bridge,
+            window_handle,
+        )
+    }
+
+    fn close(&mut self, webview: &Entity) {
+        Browsers::close(self, webview);
+    }
+
+    fn send_external_begin_frame_for(&self, webview: &Entity) {
+        Browsers::send_external_begin_frame_for(self, webview);
+    }
+
+    fn navigate(&self, webview: &Entity, url: &str) {
+        Browsers::navigate(self, webview, url);
+    }
+
+    fn reload_webview(&self, webview: &Entity) {
+        Browsers::reload_webview(self, webview);
+    }
+
+    fn reload(&self) {
+        Browsers::reload(self);
+    }
+
+    fn go_back(&self, webview: &Entity) {
+        Browsers::go_back(self, webview);
+    }
+
+    fn go_forward(&self, webview: &Entity) {
+        Browsers::go_forward(self, webview);
+    }
+
+    fn resize(&self, webview: &Entity, size: Vec2) {
+        Browsers::resize(self, webview, size);
+    }
+
+    fn set_dpr(&self, webview: &Entity, dpr: f32) {
+        Browsers::set_dpr(self, webview, dpr);
+    }
+
+    fn notify_screen_info_changed(&self, webview: &Entity) {
+        Browsers::notify_screen_info_changed(self, webview);
+    }
+
+    fn send_mouse_move(
+        &self,
+        webview: &Entity,
+        buttons: &[MouseButton],
+        position: Vec2,
+        mouse_leave: bool,
+    ) {
+        Browsers::send_mouse_move(self, webview, buttons, position, mouse_leave);
+    }
+
+    fn send_mouse_click(
+        &self,
+        webview: &Entity,
+        position: Vec2,
+        button: PointerButton,
+        mouse_up: bool,
+    ) {
+        Browsers::send_mouse_click(self, webview, position, button, mouse_up);
+    }
+
+    fn send_mouse_wheel(&self, webview: &Entity, position: Vec2, delta: Vec2) {
+        Browsers::send_mouse_wheel(self, webview, position, delta);
+    }
+
+    fn send_touch(
+        &self,
+        webview: &Entity,
+        id: i32,
+        position: Vec2,
+        phase: TouchPhase,
+        pressure: f32,
+    ) {
+        Browsers::send_touch(self, webview, id, position, phase, pressure);
+    }
+
+    fn send_key(&self, webview: &Entity, event: cef::KeyEvent) {
+        Browsers::send_key(self, webview, event);
+    }
+
+    fn exec_edit_command(&self, webview: &Entity, cmd: EditCommand) {
+        Browsers::exec_edit_command(self, webview, cmd);
+    }
+
+    fn drag_target_enter(&self, webview: &Entity, files: &[PathBuf], position: Vec2) {
+        Browsers::drag_target_enter(self, webview, files, position);
+    }
+
+    fn drag_target_over(&self, webview: &Entity, position: Vec2) {
+        Browsers::drag_target_over(self, webview, position);
+    }
+
+    fn drag_target_leave(&self, webview: &Entity) {
+        Browsers::drag_target_leave(self, webview);
+    }
+
+    fn drag_target_drop(&self, webview: &Entity, position: Vec2) {
+        Browsers::drag_target_drop(self, webview, position);
+    }
+
+    fn cancel_page_drag(&self, webview: &Entity) {
+        Browsers::cancel_page_drag(self, webview);
+    }
+
+    fn set_focus(&self, webview: &Entity, focused: bool) {
+        Browsers::set_focus(self, webview, focused);
+    }
+
+    fn emit_event(&self, webview: &Entity, id: &str, event: &serde_json::Value) {
+        Browsers::emit_event(self, webview, id, event);
+    }
+
+    fn execute_javascript(&self, webview: &Entity, code: &str) {
+        Browsers::execute_javascript(self, webview, code);
+    }
+
+    fn set_user_scripts(&self, webview: &Entity, scripts: &[UserScriptPayload]) {
+        Browsers::set_user_scripts(self, webview, scripts);
+    }
+
+    fn set_bridge_policy(&self, webview: &Entity, policy: &BridgePolicyPayload) {
+        Browsers::set_bridge_policy(self, webview, policy);
+    }
+
+    fn show_devtool(&self, webview: &Entity) {
+        Browsers::show_devtool(self, webview);
+    }
+
+    fn show_devtool_in(
+        &mut self,
+        webview: &Entity,
+        surface: Entity,
+        surface_size: Vec2,
+        dpr: f32,
+        senders: WebviewSenders,
+    ) -> Result<(), BrowserCreationError> {
+        Browsers::show_devtool_in(self, webview, surface, surface_size, dpr, senders)
+    }
+
+    fn inspect_element_at(&self, webview: &Entity, position: Vec2) {
+        Browsers::inspect_element_at(self, webview, position);
+    }
+
+    fn open_cdp(&mut self, webview: &Entity, sender: CdpSenderInner) -> Option<bool> {
+        Some(Browsers::open_cdp(self, webview, sender))
+    }
+
+    fn close_cdp(&mut self, webview: &Entity) {
+        Browsers::close_cdp(self, webview);
+    }
+
+    fn send_cdp(&self, webview: &Entity, message: &str) -> bool {
+        Browsers::send_cdp(self, webview, message)
+    }
+
+    fn close_devtools(&self, webview: &Entity) {
+        Browsers::close_devtools(self, webview);
+    }
+
+    fn set_zoom_level(&self, webview: &Entity, zoom_level: f64) {
+        Browsers::set_zoom_level(self, webview, zoom_level);
+    }
+
+    fn set_audio_muted(&self, webview: &Entity, muted: bool) {
+        Browsers::set_audio_muted(self, webview, muted);
+    }
+
+    fn set_hidden(&self, webview: &Entity, hidden: bool) {
+        Browsers::set_hidden(self, webview, hidden);
+    }
+
+    fn set_frame_rate(&self, webview: &Entity, fps: i32) {
+        Browsers::set_frame_rate(self, webview, fps);
+    }
+
+    fn invalidate(&self, webview: &Entity) {
+        Browsers::invalidate(self, webview);
+    }
+
+    fn ping(&self, webview: &Entity) {
+        Browsers::ping(self, webview);
+    }
+
+    fn set_ime_composition(&self, text: &str, cursor_utf16: Option<u32>) {
+        Browsers::set_ime_composition(self, text, cursor_utf16);
+    }
+
+    fn ime_cancel_composition(&self) {
+        Browsers::ime_cancel_composition(self);
+    }
+
+    fn ime_finish_composition(&self, keep_selection: bool) {
+        Browsers::ime_finish_composition(self, keep_selection);
+    }
+
+    fn set_ime_commit_text(&self, text: &str) {
+        Browsers::set_ime_commit_text(self, text);
+    }
+
+    #[cfg(target_os = "linux")]
+    fn try_receive_textures(&self) -> Vec<RenderTextureMessage> {
+        Browsers::try_receive_textures(self)
+    }
+
+    #[cfg(target_os = "macos")]
+    fn take_latest_webview_iosurfaces(
+        &self,
+        keep: &dyn Fn(Entity) -> bool,
+    ) -> Vec<(Entity, RetainedIoSurface)> {
+        Browsers::take_latest_webview_iosurfaces(self, keep)
+    }
+}
+
+/// The [`WebviewBackend`] of the multi-threaded message loop, always used on
+/// Windows and optional on Linux.
+///
+/// CEF's browsers live on its own UI thread there, so every call is enqueued as
+/// a [`CefCommand`] through [`BrowsersProxy`] and painted frames come back over
+/// a channel. Calls that report a result only fail here when the command cannot
+/// be queued; CEF's thread reports the outcome later, a browser's through the
+/// webview's lifecycle and a CDP session's as [`CdpMessage::Opened`].
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+pub struct ProxyBackend {
+    proxy: BrowsersProxy,
+    textures: async_channel::Receiver<RenderTextureMessage>,
+    /// Senders of the CDP sessions asked for, which refused commands report
+    /// through.
+    cdp: HashMap<Entity, CdpSenderInner>,
+}
+
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+impl ProxyBackend {
+    /// Wraps `proxy`, receiving the frames the CEF side sends to the paired
+    /// [`TextureSender`].
+    pub fn new(
+        proxy: BrowsersProxy,
+        textures: async_channel::Receiver<RenderTextureMessage>,
+    ) -> Self {
+        Self {
+            proxy,
+            textures,
+            cdp: HashMap::default(),
+        }
+    }
+}
+
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+impl WebviewBackend for ProxyBackend {
+    fn create_browser(
+        &mut self,
+        webview: Entity,
+        uri: &str,
+        webview_size: Vec2,
+        initial_dpr: f32,
+        requester: Requester,
+        senders: WebviewSenders,
+        user_scripts: &[UserScriptPayload],
+        bridge: &BridgePolicyPayload,
+        window_handle: Option<RawWindowHandle>,
+    ) -> Result<(), BrowserCreationError> {
+        self.proxy.create_browser(
+            webview,
+            uri,
+            webview_size,
+            initial_dpr,
+            requester,
+            senders,
+            user_scripts,
+            bridge,
+            window_handle,
+        )
+    }
+
+    fn close(&mut self, webview: &Entity) {
+        self.proxy.close(webview);
+    }
+
+    /// CEF paces these browsers itself at their `windowless_frame_rate`.
+    fn send_external_begin_frame_for(&self, _webview: &Entity) {}
+
+    fn navigate(&self, webview: &Entity, url: &str) {
+        self.proxy.navigate(webview, url);
+    }
+
+    fn reload_webview(&self, webview: &Entity) {
+        self.proxy.reload_webview(webview);
+    }
+
+    fn reload(&self) {
+        self.proxy.reload();
+    }
+
+    fn go_back(&self, webview: &Entity) {
+        self.proxy.go_back(webview);
+    }
+
+    fn go_forward(&self, webview: &Entity) {
+        self.proxy.go_forward(webview);
+    }
+
+    fn resize(&self, webview: &Entity, size: Vec2) {
+        self.proxy.resize(webview, size);
+    }
+
+    fn set_dpr(&self, webview: &Entity, dpr: f32) {
+        self.proxy.set_dpr(webview, dpr);
+    }
+
+    fn notify_screen_info_changed(&self, webview: &Entity) {
+        self.proxy.notify_screen_info_changed(webview);
+    }
+
+    fn send_mouse_move(
+        &self,
+        webview: &Entity,
+        buttons: &[MouseButton],
+        position: Vec2,
+        mouse_leave: bool,
+    ) {
+        self.proxy
+            .send_mouse_move(webview, buttons, position, mouse_leave);
+    }
+
+    fn send_mouse_click(
+        &self,
+        webview: &Entity,
+        position: Vec2,
+        button: PointerButton,
+        mouse_up: bool,
+    ) {
+        self.proxy
+            .send_mouse_click(webview, position, button, mouse_up);
+    }
+
+    fn send_mouse_wheel(&self, webview: &Entity, position: Vec2, delta: Vec2) {
+        self.proxy.send_mouse_wheel(webview, position, delta);
+    }
+
+    fn send_touch(
+        &self,
+        webview: &Entity,
+        id: i32,
+        position: Vec2,
+        phase: TouchPhase,
+        pressure: f32,
+    ) {
+        self.proxy
+            .send_touch(webview, id, position, phase, pressure);
+    }
+
+    fn send_key(&self, webview: &Entity, event: cef::KeyEvent) {
+        self.proxy.send_key(webview, event);
+    }
+
+    fn exec_edit_command(&self, webview: &Entity, cmd: EditCommand) {
+        self.proxy.exec_edit_command(webview, cmd);
+    }
+
+    fn drag_target_enter(&self, webview: &Entity, files: &[PathBuf], position: Vec2) {
+        self.proxy.drag_target_enter(webview, files, position);
+    }
+
+    fn drag_target_over(&self, webview: &Entity, position: Vec2) {
+        self.proxy.drag_target_over(webview, position);
+    }
+
+    fn drag_target_leave(&self, webview: &Entity) {
+        self.proxy.drag_target_leave(webview);
+    }
+
+    fn drag_target_drop(&self, webview: &Entity, position: Vec2) {
+        self.proxy.drag_target_drop(webview, position);
+    }
+
+    fn cancel_page_drag(&self, webview: &Entity) {
+        self.proxy.cancel_page_drag(webview);
+    }
+
+    fn set_focus(&self, webview: &Entity, focused: bool) {
+        self.proxy.set_focus(webview, focused);
+    }
+
+    fn emit_event(&self, webview: &Entity, id: &str, event: &serde_json::Value) {
+        self.proxy.emit_event(webview, id, event);
+    }
+
+    fn execute_javascript(&self, webview: &Entity, code: &str) {
+        self.proxy.execute_javascript(webview, code);
+    }
+
+    fn set_user_scripts(&self, webview: &Entity, scripts: &[UserScriptPayload]) {
+        self.proxy.set_user_scripts(webview, scripts);
+    }
+
+    fn set_bridge_policy(&self, webview: &Entity, policy: &BridgePolicyPayload) {
+        self.proxy.set_bridge_policy(webview, policy);
+    }
+
+    fn show_devtool(&self, webview: &Entity) {
+        self.proxy.show_devtool(webview);
+    }
+
+    fn show_devtool_in(
+        &mut self,
+        webview: &Entity,
+        surface: Entity,
+        surface_size: Vec2,
+        dpr: f32,
+        senders: WebviewSenders,
+    ) -> Result<(), BrowserCreationError> {
+        if *webview == surface {
+            return Err(BrowserCreationError::BrowserNotCreated);
+        }
+        self.proxy
+            .show_devtool_in(webview, surface, surface_size, dpr, senders)
+    }
+
+    fn inspect_element_at(&self, webview: &Entity, position: Vec2) {
+        self.proxy.inspect_element_at(webview, position);
+    }
+
+    fn open_cdp(&mut self, webview: &Entity, sender: CdpSenderInner) -> Option<bool> {
+        if !self.proxy.open_cdp(webview, sender.clone()) {
+            return Some(false);
+        }
+        self.cdp.insert(*webview, sender);
+        None
+    }
+
+    fn close_cdp(&mut self, webview: &Entity) {
+        self.cdp.remove(webview);
+        self.proxy.close_cdp(webview);
+    }
+
+    /// Refusals arrive later as [`CdpMessage::NotSent`].
+    fn send_cdp(&self, webview: &Entity, message: &str) -> bool {
+        let Some(sender) = self.cdp.get(webview) else {
+            return false;
+        };
+        self.proxy.send_cdp(webview, message, sender.clone())
+    }
+
+    fn close_devtools(&self, webview: &Entity) {
+        self.proxy.close_devtools(webview);
+    }
+
+    fn set_zoom_level(&self, webview: &Entity, zoom_level: f64) {
+        self.proxy.set_zoom_level(webview, zoom_level);
+    }
+
+    fn set_audio_muted(&self, webview: &Entity, muted: bool) {
+        self.proxy.set_audio_muted(webview, muted);
+    }
+
+    fn set_hidden(&self, webview: &Entity, hidden: bool) {
+        self.proxy.set_hidden(webview, hidden);
+    }
+
+    fn set_frame_rate(&self, webview: &Entity, fps: i32) {
+        self.proxy.set_frame_rate(webview, fps);
+    }
+
+    fn invalidate(&self, webview: &Entity) {
+        self.proxy.invalidate(webview);
+    }
+
+    fn ping(&self, webview: &Entity) {
+        self.proxy.ping(webview);
+    }
+
+    fn set_ime_composition(&self, text: &str, cursor_utf16: Option<u32>) {
+        self.proxy.set_ime_composition(text, cursor_utf16);
+    }
+
+    fn ime_cancel_composition(&self) {
+        self.proxy.ime_cancel_composition();
+    }
+
+    fn ime_finish_composition(&self, keep_selection: bool) {
+        self.proxy.ime_finish_composition(keep_selection);
+    }
+
+    fn set_ime_commit_text(&self, text: &str) {
+        self.proxy.set_ime_commit_text(text);
+    }
+
+    fn try_receive_textures(&self) -> Vec<RenderTextureMessage> {
+        latest_textures(&self.textures)
+    }
+}
+
+#[cfg(all(test, any(target_os = "windows", target_os = "linux")))]
+mod tests {
+    use super::*;
+
+    fn senders() -> WebviewSenders {
+        WebviewSenders {
+            ipc_event: async_channel::unbounded().0,
+            brp: async_channel::unbounded().0,
+            cursor_changed: async_channel::unbounded().0,
+            drag_regions: async_channel::unbounded().0,
+            load_handler: async_channel::unbounded().0,
+            address_changed: async_channel::unbounded().0,
+            title_changed: async_channel::unbounded().0,
+            console: async_channel::unbounded().0,
+            control: async_channel::unbounded().0,
+            key_unhandled: async_channel::unbounded().0,
+            take_focus: async_channel::unbounded().0,
+            drag_started: async_channel::unbounded().0,
+            render_process: async_channel::unbounded().0,
+            lifecycle: async_channel::unbounded().0,
+            audio: None,
+            accessibility: None,
+        }
+    }
+
+    fn create(backend: &mut ProxyBackend, webview: Entity) -> Result<(), BrowserCreationError> {
+        backend.create_browser(
+            webview,
+            "https://example.com",
+            Vec2::new(800., 600.),
+            1.,
+            Requester(async_channel::unbounded().0),
+            senders(),
+            &[],
+            &BridgePolicyPayload {
+                origins: Vec::new(),
+                extensions: Default::default(),
+            },
+            None,
+        )
+    }
+
+    fn backend() -> (ProxyBackend, async_channel::Receiver<CefCommand>) {
+        let (tx, rx) = async_channel::unbounded();
+        let backend = ProxyBackend::new(BrowsersProxy::new(tx), async_channel::unbounded().1);
+        (backend, rx)
+    }
+
+    #[test]
+    fn creation_is_queued_and_fails_once_cef_thread_is_gone() {
+        let (mut backend, rx) = backend();
+        let webview = Entity::from_raw_u32(1).unwrap();
+
+        assert!(create(&mut backend, webview).is_ok());
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(CefCommand::CreateBrowser { webview: queued, .. }) if queued == webview
+        ));
+
+        drop(rx);
+        assert!(create(&mut backend, webview).is_err());
+        assert!(
+            backend
+                .show_devtool_in(
+                    &webview,
+                    Entity::from_raw_u32(2).unwrap(),
+                    Vec2::ONE,
+                    1.,
+                    senders()
+                )
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn devtools_cannot_be_shown_in_their_own_webview() {
+        let (mut backend, rx) = backend();
+        let webview = Entity::from_raw_u32(1).unwrap();
+
+        assert!(
+            backend
+                .show_devtool_in(&webview, webview, Vec2::ONE, 1., senders())
+                .is_err()
+        );
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn cdp_opens_are_answered_later_and_sends_need_an_open() {
+        let (mut backend, rx) = backend();
+        let webview = Entity::from_raw_u32(1).unwrap();
+
+        assert!(!backend.send_cdp(&webview, r#"{"id":1}"#));
+        assert_eq!(
+            backend.open_cdp(&webview, async_channel::unbounded().0),
+            None
+        );
+        assert!(matches!(rx.try_recv(), Ok(CefCommand::OpenCdp { .. })));
+        assert!(backend.send_cdp(&webview, r#"{"id":1}"#));
+        assert!(matches!(rx.try_recv(), Ok(CefCommand::SendCdp { .. })));
+
+        backend.close_cdp(&webview);
+        assert!(!backend.send_cdp(&webview, r#"{"id":2}"#));
+    }
+
+    #[test]
+    fn cdp_opens_fail_right_away_once_cef_thread_is_gone() {
+        let (mut backend, rx) = backend();
+        let webview = Entity::from_raw_u32(1).unwrap();
+        drop(rx);
+
+        assert_eq!(
+            backend.open_cdp(&webview, async_channel::unbounded().0),
+            Some(false)
+        );
+        assert!(!backend.send_cdp(&webview, r#"{"id":1}"#));
+    }
+}
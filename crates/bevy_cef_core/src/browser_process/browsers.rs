@@ -1,49 +1,68 @@
 #[cfg(not(target_os = "windows"))]
-use crate::browser_process::BrpHandler;
+use crate::bridge::BRIDGE_POLICY_KEY;
+use crate::bridge::PROCESS_MESSAGE_BRIDGE_POLICY;
 #[cfg(not(target_os = "windows"))]
+use crate::browser_process::BrpHandler;
 use crate::browser_process::ClientHandlerBuilder;
 #[cfg(not(target_os = "windows"))]
-use crate::browser_process::client_handler::{IpcEventRaw, JsEmitEventHandler};
+use crate::browser_process::client_handler::{ControlHandler, JsEmitEventHandler, PongHandler};
 use crate::prelude::IntoString;
 use crate::prelude::*;
 #[cfg(not(target_os = "windows"))]
-use async_channel::Sender;
+use crate::user_script::USER_SCRIPTS_KEY;
+use crate::user_script::{PROCESS_MESSAGE_USER_SCRIPTS, UserScriptPayload};
+use bevy::input::touch::TouchPhase;
 use bevy::platform::collections::HashMap;
 use bevy::prelude::*;
-#[cfg(not(target_os = "windows"))]
-use bevy_remote::BrpMessage;
 use cef::{
-    Browser, BrowserHost, BrowserSettings, CompositionUnderline, ImplBrowser, ImplBrowserHost,
-    ImplFrame, ImplListValue, ImplProcessMessage, MouseButtonType, ProcessId, Range, WindowInfo,
-    process_message_create,
+    Browser, BrowserHost, BrowserSettings, CefStringList, Client, CompositionUnderline,
+    ImplBrowser, ImplBrowserHost, ImplFrame, ImplListValue, ImplProcessMessage, MouseButtonType,
+    PaintElementType, Point, PointerType, ProcessId, Range, Registration, State, TouchEventType,
+    WindowInfo, process_message_create,
 };
 #[cfg(not(target_os = "windows"))]
 use cef::{
-    CefString, Client, DictionaryValue, ImplDictionaryValue, ImplRequestContext, RequestContext,
+    CefString, DictionaryValue, ImplDictionaryValue, ImplRequestContext, RequestContext,
     RequestContextSettings, browser_host_create_browser_sync, dictionary_value_create,
 };
-use cef_dll_sys::{cef_event_flags_t, cef_mouse_button_type_t};
+use cef_dll_sys::{
+    cef_event_flags_t, cef_mouse_button_type_t, cef_paint_element_type_t, cef_pointer_type_t,
+    cef_state_t, cef_touch_event_type_t,
+};
 #[cfg(not(target_os = "windows"))]
 #[allow(deprecated)]
 use raw_window_handle::RawWindowHandle;
 #[cfg(not(target_os = "windows"))]
 use std::cell::Cell;
+use std::path::PathBuf;
 #[cfg(not(target_os = "windows"))]
 use std::rc::Rc;
+#[cfg(target_os = "linux")]
+use std::sync::{Arc, Mutex};
 
 pub(crate) mod devtool_render_handler;
 mod keyboard;
 
+#[cfg(not(target_os = "windows"))]
+use crate::browser_process::audio_handler::AudioHandlerBuilder;
 use crate::browser_process::browsers::devtool_render_handler::DevToolRenderHandlerBuilder;
 #[cfg(not(target_os = "windows"))]
-use crate::browser_process::display_handler::{
-    AddressChangedSenderInner, DisplayHandlerBuilder, SystemCursorIconSenderInner,
-    TitleChangedSenderInner,
+use crate::browser_process::display_handler::DisplayHandlerBuilder;
+use crate::browser_process::drag_drop::{
+    allowed_drag_operations, drag_mouse_event, drag_operation, file_drag_data,
 };
 #[cfg(not(target_os = "windows"))]
-use crate::browser_process::drag_handler::{DragHandlerBuilder, DraggableRegionSenderInner};
+use crate::browser_process::drag_handler::DragHandlerBuilder;
+#[cfg(not(target_os = "windows"))]
+use crate::browser_process::focus_handler::FocusHandlerBuilder;
+#[cfg(not(target_os = "windows"))]
+use crate::browser_process::keyboard_handler::KeyboardHandlerBuilder;
+#[cfg(not(target_os = "windows"))]
+use crate::browser_process::life_span_handler::LifeSpanHandlerBuilder;
 #[cfg(not(target_os = "windows"))]
-use crate::browser_process::load_handler::{LoadHandlerBuilder, LoadHandlerSenderInner};
+use crate::browser_process::load_handler::LoadHandlerBuilder;
+#[cfg(not(target_os = "windows"))]
+use crate::browser_process::request_handler::RequestHandlerBuilder;
 pub use keyboard::*;
 
 pub struct WebviewBrowser {
@@ -51,10 +70,12 @@ pub struct WebviewBrowser {
     pub host: BrowserHost,
     pub size: SharedViewSize,
     pub dpr: SharedDpr,
-    #[cfg(target_os = "linux")]
-    pub view_slot: SharedTexture,
-    #[cfg(target_os = "linux")]
-    pub popup_slot: SharedTexture,
+    /// Set while the page is dragging something; mouse input then drives the
+    /// drag instead of the page.
+    pub page_drag: SharedPageDrag,
+    /// The webview whose DevTools this browser shows, when it is a DevTools
+    /// surface.
+    pub inspected: Option<Entity>,
     /// [macOS GPU OSR] Latest IOSurface retained by `on_accelerated_paint`
     /// (Approach 2). Drained by the main-world collect system for extraction
     /// into the render world, where the `webview_blit` render-graph-schedule
@@ -79,12 +100,35 @@ pub enum EditCommand {
     Redo,
 }
 
-#[derive(Default)]
 pub struct Browsers {
     browsers: HashMap<Entity, WebviewBrowser>,
+    /// DevTools message observers of the webviews with an open CDP session.
+    cdp: HashMap<Entity, Registration>,
+    /// Handed to every browser's `on_paint` to deliver frames through.
+    #[cfg(target_os = "linux")]
+    texture_sender: TextureSender,
+    #[cfg(target_os = "linux")]
+    texture_receiver: async_channel::Receiver<RenderTextureMessage>,
+}
+
+impl Default for Browsers {
+    fn default() -> Self {
+        #[cfg(target_os = "linux")]
+        let (texture_sender, texture_receiver) = async_channel::unbounded();
+        Self {
+            browsers: HashMap::default(),
+            cdp: HashMap::default(),
+            #[cfg(target_os = "linux")]
+            texture_sender,
+            #[cfg(target_os = "linux")]
+            texture_receiver,
+        }
+    }
 }
 
 impl Browsers {
+    /// Creates the browser of a webview. Its handlers report through `senders`,
+    /// including `lifecycle` once the browser is up.
     #[cfg(not(target_os = "windows"))]
     #[allow(clippy::too_many_arguments)]
     pub fn create_browser(
@@ -94,23 +138,24 @@ impl Browsers {
         webview_size: Vec2,
         initial_dpr: f32,
         requester: Requester,
-        ipc_event_sender: Sender<IpcEventRaw>,
-        brp_sender: Sender<BrpMessage>,
-        system_cursor_icon_sender: SystemCursorIconSenderInner,
-        drag_regions_sender: DraggableRegionSenderInner,
-        load_handler_sender: LoadHandlerSenderInner,
-        address_changed_sender: AddressChangedSenderInner,
-        title_changed_sender: TitleChangedSenderInner,
-        initialize_scripts: &[String],
+        senders: WebviewSenders,
+        user_scripts: &[UserScriptPayload],
+        bridge: &BridgePolicyPayload,
         _window_handle: Option<RawWindowHandle>,
-    ) {
+    ) -> Result<(), BrowserCreationError> {
         let mut context = Self::request_context(requester);
-        let size: SharedViewSize = Rc::new(Cell::new(webview_size));
-        let dpr: SharedDpr = Rc::new(Cell::new(initial_dpr));
-        #[cfg(target_os = "linux")]
-        let view_slot: SharedTexture = Rc::new(Cell::new(None));
+        let accessibility = senders.accessibility.is_some();
+        #[cfg(target_os = "macos")]
+        let (size, dpr): (SharedViewSize, SharedDpr) = (
+            Rc::new(Cell::new(webview_size)),
+            Rc::new(Cell::new(initial_dpr)),
+        );
         #[cfg(target_os = "linux")]
-        let popup_slot: SharedTexture = Rc::new(Cell::new(None));
+        let (size, dpr): (SharedViewSize, SharedDpr) = (
+            Arc::new(Mutex::new(webview_size)),
+            Arc::new(Mutex::new(initial_dpr)),
+        );
+        let page_drag: SharedPageDrag = Rc::new(Cell::new(None));
         #[cfg(target_os = "macos")]
         let latest_iosurface: crate::browser_process::accelerated_paint::SharedRetainedIoSurface =
             Rc::new(std::cell::RefCell::new(None));
@@ -135,45 +180,41 @@ impl Browsers {
             Some(&mut self.client_handler(
                 webview,
                 size.clone(),
-                #[cfg(target_os = "linux")]
-                view_slot.clone(),
-                #[cfg(target_os = "linux")]
-                popup_slot.clone(),
                 dpr.clone(),
-                ipc_event_sender,
-                brp_sender,
-                system_cursor_icon_sender,
-                drag_regions_sender,
-                load_handler_sender,
-                address_changed_sender,
+                page_drag.clone(),
+                senders,
                 #[cfg(target_os = "macos")]
                 latest_iosurface.clone(),
-                title_changed_sender,
+                None,
             )),
             Some(&uri.into()),
             Some(&BrowserSettings {
                 windowless_frame_rate: 60,
                 ..Default::default()
             }),
-            Self::create_extra_info(initialize_scripts).as_mut(),
+            Self::create_extra_info(user_scripts, bridge).as_mut(),
             context.as_mut(),
         )
-        .expect("Failed to create browser");
-        let host = browser.host().expect("Failed to get browser host");
+        .ok_or(BrowserCreationError::BrowserNotCreated)?;
+        let host = browser
+            .host()
+            .ok_or(BrowserCreationError::HostUnavailable)?;
+        if accessibility {
+            enable_renderer_accessibility(&host);
+        }
         let webview_browser = WebviewBrowser {
             host,
             client: browser,
             size,
             dpr,
-            #[cfg(target_os = "linux")]
-            view_slot,
-            #[cfg(target_os = "linux")]
-            popup_slot,
+            page_drag,
+            inspected: None,
             #[cfg(target_os = "macos")]
             latest_iosurface,
         };
 
         self.browsers.insert(webview, webview_browser);
+        Ok(())
     }
 
     pub fn send_external_begin_frame(&mut self) {
@@ -182,6 +223,13 @@ impl Browsers {
         }
     }
 
+    /// Asks the specified webview alone to produce a frame.
+    pub fn send_external_begin_frame_for(&self, webview: &Entity) {
+        if let Some(browser) = self.browsers.get(webview) {
+            browser.host.send_external_begin_frame();
+        }
+    }
+
     /// [macOS GPU OSR] Drains the latest retained IOSurface for every webview
     /// that has received a new accelerated-paint frame since the last call
     /// (Approach 2).
@@ -235,6 +283,10 @@ impl Browsers {
         mouse_leave: bool,
     ) {
         if let Some(browser) = self.get_focused_browser(webview) {
+            if browser.page_drag.get().is_some() {
+                drag_page_over(browser, position);
+                return;
+            }
             let mouse_event = cef::MouseEvent {
                 x: position.x as i32,
                 y: position.y as i32,
@@ -254,6 +306,12 @@ impl Browsers {
         mouse_up: bool,
     ) {
         if let Some(browser) = self.get_focused_browser(webview) {
+            if browser.page_drag.get().is_some() {
+                if mouse_up && button == PointerButton::Primary {
+                    end_page_drag(browser, Some(position));
+                }
+                return;
+            }
             let mouse_event = cef::MouseEvent {
                 x: position.x as i32,
                 y: position.y as i32,
@@ -292,6 +350,82 @@ impl Browsers {
         }
     }
 
+    /// Starts dragging `files` over `webview`, as if they came from the OS file
+    /// manager.
+    ///
+    /// ## Reference
+    ///
+    /// - [`DragTargetDragEnter`](https://cef-builds.spotifycdn.com/docs/122.0/classCefBrowserHost.html)
+    pub fn drag_target_enter(&self, webview: &Entity, files: &[PathBuf], position: Vec2) {
+        if let Some(browser) = self.browsers.get(webview)
+            && let Some(mut data) = file_drag_data(files)
+        {
+            browser.host.drag_target_drag_enter(
+                Some(&mut data),
+                Some(&drag_mouse_event(position)),
+                allowed_drag_operations(),
+            );
+        }
+    }
+
+    /// Moves the drag started by [`Browsers::drag_target_enter`].
+    pub fn drag_target_over(&self, webview: &Entity, position: Vec2) {
+        if let Some(browser) = self.browsers.get(webview) {
+            browser.host.drag_target_drag_over(
+                Some(&drag_mouse_event(position)),
+                allowed_drag_operations(),
+            );
+        }
+    }
+
+    /// Cancels the drag started by [`Browsers::drag_target_enter`].
+    pub fn drag_target_leave(&self, webview: &Entity) {
+        if let Some(browser) = self.browsers.get(webview) {
+            browser.host.drag_target_drag_leave();
+        }
+    }
+
+    /// Drops the drag started by [`Browsers::drag_target_enter`] at `position`.
+    pub fn drag_target_drop(&self, webview: &Entity, position: Vec2) {
+        if let Some(browser) = self.browsers.get(webview) {
+            browser
+                .host
+                .drag_target_drop(Some(&drag_mouse_event(position)));
+        }
+    }
+
+    /// Cancels the page drag in flight on `webview`, if any; used when the
+    /// button is released outside the webview.
+    pub fn cancel_page_drag(&self, webview: &Entity) {
+        if let Some(browser) = self.browsers.get(webview) {
+            end_page_drag(browser, None);
+        }
+    }
+
+    /// Forwards a single touch point. `id` must stay stable for the lifetime of
+    /// the finger so Chromium's gesture recognizer can track pinches and flings.
+    ///
+    /// ## Reference
+    ///
+    /// - [`SendTouchEvent`](https://cef-builds.spotifycdn.com/docs/122.0/classCefBrowserHost.html)
+    pub fn send_touch(
+        &self,
+        webview: &Entity,
+        id: i32,
+        position: Vec2,
+        phase: TouchPhase,
+        pressure: f32,
+    ) {
+        if let Some(browser) = self.browsers.get(webview) {
+            if phase == TouchPhase::Started {
+                browser.host.set_focus(true as _);
+            }
+            browser
+                .host
+                .send_touch_event(Some(&touch_event(id, position, phase, pressure)));
+        }
+    }
+
     #[inline]
     pub fn send_key(&self, webview: &Entity, event: cef::KeyEvent) {
         if let Some(browser) = self.get_focused_browser(webview) {
@@ -346,11 +480,40 @@ impl Browsers {
         };
     }
 
+    /// Runs `code` in the webview's main frame.
+    ///
+    /// ## Reference
+    ///
+    /// - [`ExecuteJavaScript`](https://cef-builds.spotifycdn.com/docs/122.0/classCefFrame.html)
+    pub fn execute_javascript(&self, webview: &Entity, code: &str) {
+        if let Some(browser) = self.browsers.get(webview)
+            && let Some(frame) = browser.client.main_frame()
+        {
+            frame.execute_java_script(Some(&code.into()), Some(&(&frame.url()).into()), 0);
+        }
+    }
+
+    /// Replaces the webview's user scripts in every render process hosting
+    /// one of its frames. The new set applies from the next document load.
+    pub fn set_user_scripts(&self, webview: &Entity, scripts: &[UserScriptPayload]) {
+        if let Some(browser) = self.browsers.get(webview) {
+            send_user_scripts(&browser.client, scripts);
+        }
+    }
+
+    /// Replaces the webview's bridge policy in every render process hosting
+    /// one of its frames. The new policy applies from the next document load.
+    pub fn set_bridge_policy(&self, webview: &Entity, policy: &BridgePolicyPayload) {
+        if let Some(browser) = self.browsers.get(webview) {
+            send_bridge_policy(&browser.client, policy);
+        }
+    }
+
     pub fn resize(&self, webview: &Entity, size: Vec2) {
         if let Some(browser) = self.browsers.get(webview) {
-            #[cfg(not(target_os = "windows"))]
+            #[cfg(target_os = "macos")]
             browser.size.set(size);
-            #[cfg(target_os = "windows")]
+            #[cfg(not(target_os = "macos"))]
             {
                 *browser.size.lock().unwrap() = size;
             }
@@ -364,9 +527,9 @@ impl Browsers {
     /// CEF re-queries `GetScreenInfo` with the stale value.
     pub fn set_dpr(&self, webview: &Entity, dpr: f32) {
         if let Some(browser) = self.browsers.get(webview) {
-            #[cfg(not(target_os = "windows"))]
+            #[cfg(target_os = "macos")]
             browser.dpr.set(dpr);
-            #[cfg(target_os = "windows")]
+            #[cfg(not(target_os = "macos"))]
             {
                 *browser.dpr.lock().unwrap() = dpr;
             }
@@ -393,35 +556,159 @@ impl Browsers {
     ///
     /// The browser will be removed from the hash map after closing.
     pub fn close(&mut self, webview: &Entity) {
+        self.cdp.remove(webview);
         if let Some(browser) = self.browsers.remove(webview) {
             browser.host.close_browser(true as _);
             debug!("Closed browser with webview: {:?}", webview);
         }
     }
 
-    /// Drains the latest texture from each webview's view and popup slots.
+    /// Starts reporting the webview's Chrome DevTools Protocol results and
+    /// events through `sender`, until [`Browsers::close_cdp`] or the browser
+    /// closes. Returns whether the webview has a browser to observe.
+    ///
+    /// ## Reference
+    ///
+    /// - [`AddDevToolsMessageObserver`](https://cef-builds.spotifycdn.com/docs/145/classCefBrowserHost.html)
+    pub fn open_cdp(&mut self, webview: &Entity, sender: CdpSenderInner) -> bool {
+        let Some(browser) = self.browsers.get(webview) else {
+            return false;
+        };
+        if !self.cdp.contains_key(webview)
+            && let Some(registration) = browser.host.add_dev_tools_message_observer(Some(
+                &mut DevToolsMessageObserverBuilder::build(*webview, sender),
+            ))
+        {
+            self.cdp.insert(*webview, registration);
+        }
+        self.cdp.contains_key(webview)
+    }
+
+    /// Stops reporting the webview's CDP messages.
+    pub fn close_cdp(&mut self, webview: &Entity) {
+        self.cdp.remove(webview);
+    }
+
+    /// Sends a raw CDP command, `{"id": .., "method": .., "params": ..}`, to
+    /// the webview. Its result arrives through the sender given to
+    /// [`Browsers::open_cdp`]. Returns whether the browser accepted it.
+    ///
+    /// ## Reference
+    ///
+    /// - [`SendDevToolsMessage`](https://cef-builds.spotifycdn.com/docs/145/classCefBrowserHost.html)
+    pub fn send_cdp(&self, webview: &Entity, message: &str) -> bool {
+        self.browsers.get(webview).is_some_and(|browser| {
+            browser
+                .host
+                .send_dev_tools_message(Some(message.as_bytes()))
+                != 0
+        })
+    }
+
+    /// Drains the latest texture of each webview's view and popup painted since
+    /// the last call.
     ///
     /// Linux-only: the CPU `OnPaint` path. macOS uses the GPU IOSurface path.
     #[cfg(target_os = "linux")]
-    pub fn try_receive_textures(&self) -> impl Iterator<Item = RenderTextureMessage> + '_ {
-        self.browsers.values().flat_map(|b| {
-            [b.view_slot.take(), b.popup_slot.take()]
-                .into_iter()
-                .flatten()
-        })
+    pub fn try_receive_textures(&self) -> Vec<RenderTextureMessage> {
+        latest_textures(&self.texture_receiver)
     }
 
     /// Shows the DevTools for the specified webview.
     pub fn show_devtool(&self, webview: &Entity) {
+        if let Some(browser) = self.browsers.get(webview) {
+            show_native_devtools(&browser.host, None);
+        }
+    }
+
+    /// Shows the DevTools of `webview` in the webview `surface` instead of a
+    /// native window, replacing the surface's own browser. Frames, input and
+    /// resizes addressed to `surface` then drive the DevTools, and
+    /// [`Browsers::close`] on `surface` closes them.
+    ///
+    /// CEF creates the DevTools browser synchronously when asked on its UI
+    /// thread, which is where this runs.
+    #[cfg(not(target_os = "windows"))]
+    pub fn show_devtool_in(
+        &mut self,
+        webview: &Entity,
+        surface: Entity,
+        surface_size: Vec2,
+        dpr: f32,
+        senders: WebviewSenders,
+    ) -> Result<(), BrowserCreationError> {
+        if *webview == surface || !self.browsers.contains_key(webview) {
+            return Err(BrowserCreationError::BrowserNotCreated);
+        }
+        self.close(&surface);
+        #[cfg(target_os = "macos")]
+        let (size, dpr): (SharedViewSize, SharedDpr) =
+            (Rc::new(Cell::new(surface_size)), Rc::new(Cell::new(dpr)));
+        #[cfg(target_os = "linux")]
+        let (size, dpr): (SharedViewSize, SharedDpr) = (
+            Arc::new(Mutex::new(surface_size)),
+            Arc::new(Mutex::new(dpr)),
+        );
+        let page_drag: SharedPageDrag = Rc::new(Cell::new(None));
+        #[cfg(target_os = "macos")]
+        let latest_iosurface: crate::browser_process::accelerated_paint::SharedRetainedIoSurface =
+            Rc::new(std::cell::RefCell::new(None));
+        let created: SharedCreatedBrowser = Rc::new(std::cell::RefCell::new(None));
+        let mut client = self.client_handler(
+            surface,
+            size.clone(),
+            dpr.clone(),
+            page_drag.clone(),
+            senders,
+            #[cfg(target_os = "macos")]
+            latest_iosurface.clone(),
+            Some(created.clone()),
+        );
+        show_surface_devtools(
+            &self.browsers[webview].host,
+            &surface_window_info(),
+            &mut client,
+            None,
+        );
+        let browser = created
+            .take()
+            .ok_or(BrowserCreationError::BrowserNotCreated)?;
+        let host = browser
+            .host()
+            .ok_or(BrowserCreationError::HostUnavailable)?;
+        self.browsers.insert(
+            surface,
+            WebviewBrowser {
+                host,
+                client: browser,
+                size,
+                dpr,
+                page_drag,
+                inspected: Some(*webview),
+                #[cfg(target_os = "macos")]
+                latest_iosurface,
+            },
+        );
+        Ok(())
+    }
+
+    /// Inspects the element at `position` (in DIP) of the webview, in the
+    /// surface showing its DevTools if there is one. Otherwise opens the
+    /// DevTools in a native window if they are not shown yet.
+    #[cfg(not(target_os = "windows"))]
+    pub fn inspect_element_at(&self, webview: &Entity, position: Vec2) {
         let Some(browser) = self.browsers.get(webview) else {
             return;
         };
-        browser.host.show_dev_tools(
-            Some(&WindowInfo::default()),
-            Some(&mut ClientHandlerBuilder::new(DevToolRenderHandlerBuilder::build()).build()),
-            Some(&BrowserSettings::default()),
-            None,
-        );
+        match devtools_surface_client(&self.browsers, webview) {
+            Some(mut client) => show_surface_devtools(
+                &browser.host,
+                &surface_window_info(),
+                &mut client,
+                Some(position),
+            ),
+            None => show_native_devtools(&browser.host, Some(position)),
+        }
     }
 
     /// Closes the DevTools for the specified webview.
@@ -535,6 +822,61 @@ impl Browsers {
         }
     }
 
+    /// Tells the specified webview whether it is hidden. A hidden browser stops
+    /// painting and throttles its timers and animations.
+    ///
+    /// ## Reference
+    ///
+    /// - [`WasHidden`](https://cef-builds.spotifycdn.com/docs/122.0/classCefBrowserHost.html)
+    pub fn set_hidden(&self, webview: &Entity, hidden: bool) {
+        if let Some(browser) = self.browsers.get(webview) {
+            browser.host.was_hidden(hidden as _);
+        }
+    }
+
+    /// Marks the whole view of the specified webview as damaged, so its next
+    /// frame is painted even if nothing on the page changed.
+    ///
+    /// ## Reference
+    ///
+    /// - [`Invalidate`](https://cef-builds.spotifycdn.com/docs/122.0/classCefBrowserHost.html)
+    pub fn invalidate(&self, webview: &Entity) {
+        if let Some(browser) = self.browsers.get(webview) {
+            browser
+                .host
+                .invalidate(PaintElementType::from(cef_paint_element_type_t::PET_VIEW));
+        }
+    }
+
+    /// Sets how often CEF paints the specified webview on its own, in frames per
+    /// second (1 to 60). Only used where CEF drives compositing (Windows);
+    /// elsewhere frames follow `send_external_begin_frame_for`.
+    ///
+    /// ## Reference
+    ///
+    /// - [`SetWindowlessFrameRate`](https://cef-builds.spotifycdn.com/docs/122.0/classCefBrowserHost.html)
+    pub fn set_frame_rate(&self, webview: &Entity, fps: i32) {
+        if let Some(browser) = self.browsers.get(webview) {
+            browser.host.set_windowless_frame_rate(fps);
+        }
+    }
+
+    /// Asks the webview's render process to answer with a pong, reported
+    /// through the `render_process` sender. No answer means the page's main
+    /// thread is busy.
+    pub fn ping(&self, webview: &Entity) {
+        if let Some(mut process_message) =
+            process_message_create(Some(&PROCESS_MESSAGE_PING.into()))
+            && let Some(browser) = self.browsers.get(webview)
+            && let Some(frame) = browser.client.main_frame()
+        {
+            frame.send_process_message(
+                ProcessId::from(cef_dll_sys::cef_process_id_t::PID_RENDERER),
+                Some(&mut process_message),
+            );
+        }
+    }
+
     #[inline]
     pub fn reload(&self) {
         for browser in self.browsers.values() {
@@ -633,37 +975,66 @@ impl Browsers {
         &self,
         webview: Entity,
         size: SharedViewSize,
-        #[cfg(target_os = "linux")] view_slot: SharedTexture,
-        #[cfg(target_os = "linux")] popup_slot: SharedTexture,
         dpr: SharedDpr,
-        ipc_event_sender: Sender<IpcEventRaw>,
-        brp_sender: Sender<BrpMessage>,
-        system_cursor_icon_sender: SystemCursorIconSenderInner,
-        drag_regions_sender: DraggableRegionSenderInner,
-        load_handler_sender: LoadHandlerSenderInner,
-        address_changed_sender: AddressChangedSenderInner,
+        page_drag: SharedPageDrag,
+        senders: WebviewSenders,
         #[cfg(target_os = "macos")]
         latest_iosurface: crate::browser_process::accelerated_paint::SharedRetainedIoSurface,
-        title_changed_sender: TitleChangedSenderInner,
+        created: Option<SharedCreatedBrowser>,
     ) -> Client {
         #[cfg(target_os = "macos")]
-        let render_handler =
-            RenderHandlerBuilder::build(webview, size.clone(), dpr, latest_iosurface);
+        let render_handler = RenderHandlerBuilder::build(
+            webview,
+            size.clone(),
+            dpr,
+            latest_iosurface,
+            page_drag,
+            senders.drag_started,
+            senders.accessibility,
+        );
         #[cfg(target_os = "linux")]
-        let render_handler =
-            RenderHandlerBuilder::build(webview, view_slot, popup_slot, size.clone(), dpr);
-        ClientHandlerBuilder::new(render_handler)
+        let render_handler = RenderHandlerBuilder::build(
+            webview,
+            self.texture_sender.clone(),
+            size.clone(),
+            dpr,
+            page_drag,
+            senders.drag_started,
+            senders.accessibility,
+        );
+        let client = ClientHandlerBuilder::new(render_handler)
             .with_display_handler(DisplayHandlerBuilder::build(
                 webview,
-                system_cursor_icon_sender,
-                address_changed_sender,
-                title_changed_sender,
+                senders.cursor_changed,
+                senders.address_changed,
+                senders.title_changed,
+                senders.console,
+            ))
+            .with_drag_handler(DragHandlerBuilder::build(webview, senders.drag_regions))
+            .with_load_handler(LoadHandlerBuilder::build(webview, senders.load_handler))
+            .with_keyboard_handler(KeyboardHandlerBuilder::build(
+                webview,
+                senders.key_unhandled,
             ))
-            .with_drag_handler(DragHandlerBuilder::build(webview, drag_regions_sender))
-            .with_load_handler(LoadHandlerBuilder::build(webview, load_handler_sender))
-            .with_message_handler(JsEmitEventHandler::new(webview, ipc_event_sender))
-            .with_message_handler(BrpHandler::new(brp_sender))
-            .build()
+            .with_focus_handler(FocusHandlerBuilder::build(webview, senders.take_focus))
+            .with_life_span_handler(LifeSpanHandlerBuilder::build(
+                webview,
+                senders.lifecycle,
+                created,
+            ))
+            .with_request_handler(RequestHandlerBuilder::build(
+                webview,
+                senders.render_process.clone(),
+            ))
+            .with_message_handler(JsEmitEventHandler::new(webview, senders.ipc_event))
+            .with_message_handler(BrpHandler::new(webview, senders.brp))
+            .with_message_handler(ControlHandler::new(webview, senders.control))
+            .with_message_handler(PongHandler::new(webview, senders.render_process));
+        match senders.audio {
+            Some(audio) => client.with_audio_handler(AudioHandlerBuilder::build(webview, audio)),
+            None => client,
+        }
+        .build()
     }
 
     #[inline]
@@ -683,19 +1054,191 @@ impl Browsers {
     }
 
     #[cfg(not(target_os = "windows"))]
-    fn create_extra_info(scripts: &[String]) -> Option<DictionaryValue> {
-        if scripts.is_empty() {
-            return None;
-        }
+    fn create_extra_info(
+        scripts: &[UserScriptPayload],
+        bridge: &BridgePolicyPayload,
+    ) -> Option<DictionaryValue> {
         let extra = dictionary_value_create()?;
+        if !scripts.is_empty() {
+            extra.set_string(
+                Some(&CefString::from(USER_SCRIPTS_KEY)),
+                Some(&CefString::from(
+                    serde_json::to_string(scripts).ok()?.as_str(),
+                )),
+            );
+        }
         extra.set_string(
-            Some(&CefString::from(INIT_SCRIPT_KEY)),
-            Some(&CefString::from(scripts.join(";").as_str())),
+            Some(&CefString::from(BRIDGE_POLICY_KEY)),
+            Some(&CefString::from(
+                serde_json::to_string(bridge).ok()?.as_str(),
+            )),
         );
         Some(extra)
     }
 }
 
+/// Sends `scripts` to every frame of `browser`.
+pub(crate) fn send_user_scripts(browser: &Browser, scripts: &[UserScriptPayload]) {
+    if let Ok(json) = serde_json::to_string(scripts) {
+        send_to_all_frames(browser, PROCESS_MESSAGE_USER_SCRIPTS, &json);
+    }
+}
+
+/// Sends `policy` to every frame of `browser`.
+pub(crate) fn send_bridge_policy(browser: &Browser, policy: &BridgePolicyPayload) {
+    if let Ok(json) = serde_json::to_string(policy) {
+        send_to_all_frames(browser, PROCESS_MESSAGE_BRIDGE_POLICY, &json);
+    }
+}
+
+/// Sends a `name` message carrying `json` to every frame of `browser`.
+/// Out-of-process iframes live in their own render process, so messaging only
+/// the main frame would miss them.
+fn send_to_all_frames(browser: &Browser, name: &str, json: &str) {
+    let mut identifiers = CefStringList::new();
+    browser.frame_identifiers(Some(&mut identifiers));
+    for id in Vec::<String>::from(&identifiers) {
+        if let Some(frame) = browser.frame_by_identifier(Some(&id.as_str().into()))
+            && let Some(mut process_message) = process_message_create(Some(&name.into()))
+            && let Some(argument_list) = process_message.argument_list()
+        {
+            argument_list.set_string(0, Some(&json.into()));
+            frame.send_process_message(
+                ProcessId::from(cef_dll_sys::cef_process_id_t::PID_RENDERER),
+                Some(&mut process_message),
+            );
+        }
+    }
+}
+
+/// Shows the DevTools of `host` in a native window, or inspects the element at
+/// `inspect_at` in the DevTools already shown.
+pub(crate) fn show_native_devtools(host: &BrowserHost, inspect_at: Option<Vec2>) {
+    host.show_dev_tools(
+        Some(&WindowInfo::default()),
+        Some(&mut ClientHandlerBuilder::new(DevToolRenderHandlerBuilder::build()).build()),
+        Some(&BrowserSettings::default()),
+        inspect_at
+            .map(|p| Point {
+                x: p.x as i32,
+                y: p.y as i32,
+            })
+            .as_ref(),
+    );
+}
+
+/// Shows the DevTools of `host` off-screen through `client`, the client of a
+/// surface's browser, inspecting the element at `inspect_at` if given. CEF
+/// reuses DevTools already shown, so inspecting again selects the element in
+/// the same surface.
+pub(crate) fn show_surface_devtools(
+    host: &BrowserHost,
+    window_info: &WindowInfo,
+    client: &mut Client,
+    inspect_at: Option<Vec2>,
+) {
+    host.show_dev_tools(
+        Some(window_info),
+        Some(client),
+        Some(&BrowserSettings {
+            windowless_frame_rate: 60,
+            ..Default::default()
+        }),
+        inspect_at
+            .map(|p| Point {
+                x: p.x as i32,
+                y: p.y as i32,
+            })
+            .as_ref(),
+    );
+}
+
+/// The client of the surface showing the DevTools of `webview`, if any.
+pub(crate) fn devtools_surface_client(
+    browsers: &HashMap<Entity, WebviewBrowser>,
+    webview: &Entity,
+) -> Option<Client> {
+    browsers
+        .values()
+        .find(|browser| browser.inspected == Some(*webview))
+        .and_then(|surface| surface.host.client())
+}
+
+/// Off-screen windows of DevTools surfaces, paced by begin-frames like every
+/// other webview.
+#[cfg(not(target_os = "windows"))]
+fn surface_window_info() -> WindowInfo {
+    WindowInfo {
+        windowless_rendering_enabled: true as _,
+        external_begin_frame_enabled: true as _,
+        #[cfg(target_os = "macos")]
+        shared_texture_enabled: true as _,
+        ..Default::default()
+    }
+}
+
+/// Turns on renderer accessibility, so the page reports its accessibility
+/// tree to the render handler's accessibility handler.
+///
+/// ## Reference
+///
+/// - [`SetAccessibilityState`](https://cef-builds.spotifycdn.com/docs/122.0/classCefBrowserHost.html)
+pub(crate) fn enable_renderer_accessibility(host: &BrowserHost) {
+    host.set_accessibility_state(State::from(cef_state_t::STATE_ENABLED));
+}
+
+pub(crate) fn touch_event(
+    id: i32,
+    position: Vec2,
+    phase: TouchPhase,
+    pressure: f32,
+) -> cef::TouchEvent {
+    let type_ = match phase {
+        TouchPhase::Started => cef_touch_event_type_t::CEF_TET_PRESSED,
+        TouchPhase::Moved => cef_touch_event_type_t::CEF_TET_MOVED,
+        TouchPhase::Ended => cef_touch_event_type_t::CEF_TET_RELEASED,
+        TouchPhase::Canceled => cef_touch_event_type_t::CEF_TET_CANCELLED,
+    };
+    cef::TouchEvent {
+        id,
+        x: position.x,
+        y: position.y,
+        pressure,
+        type_: TouchEventType::from(type_),
+        pointer_type: PointerType::from(cef_pointer_type_t::CEF_POINTER_TYPE_TOUCH),
+        ..Default::default()
+    }
+}
+
+/// Moves the page drag in flight on `browser` to `position`.
+pub(crate) fn drag_page_over(browser: &WebviewBrowser, position: Vec2) {
+    browser
+        .host
+        .drag_target_drag_over(Some(&drag_mouse_event(position)), allowed_drag_operations());
+    browser.page_drag.set(Some(position));
+}
+
+/// Ends the page drag in flight on `browser`, dropping it at `drop_at` or
+/// cancelling it when `None`.
+pub(crate) fn end_page_drag(browser: &WebviewBrowser, drop_at: Option<Vec2>) {
+    let Some(last) = browser.page_drag.take() else {
+        return;
+    };
+    match drop_at {
+        Some(position) => browser
+            .host
+            .drag_target_drop(Some(&drag_mouse_event(position))),
+        None => browser.host.drag_target_drag_leave(),
+    }
+    let end = drop_at.unwrap_or(last);
+    browser.host.drag_source_ended_at(
+        end.x as i32,
+        end.y as i32,
+        drag_operation(drop_at.is_some()),
+    );
+    browser.host.drag_source_system_drag_ended();
+}
+
 #[allow(clippy::unnecessary_cast)]
 pub fn modifiers_from_mouse_buttons<'a>(buttons: impl IntoIterator<Item = &'a MouseButton>) -> u32 {
     let mut modifiers = cef_event_flags_t::EVENTFLAG_NONE.0 as u32;
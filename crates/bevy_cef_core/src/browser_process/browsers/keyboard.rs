@@ -147,6 +147,26 @@ pub fn create_cef_key_events(modifiers: u32, key_event: &KeyboardInput) -> Vec<c
     ]
 }
 
+/// Converts `text` into one `CHAR` event per UTF-16 code unit, which types it
+/// into the focused element without any key presses around it.
+pub fn create_cef_text_events(text: &str) -> Vec<cef::KeyEvent> {
+    text.encode_utf16()
+        .map(|character| {
+            cef::KeyEvent::from(cef_key_event_t {
+                size: core::mem::size_of::<cef_key_event_t>(),
+                type_: cef_key_event_type_t::KEYEVENT_CHAR,
+                modifiers: 0,
+                windows_key_code: character as i32,
+                native_key_code: 0,
+                character,
+                unmodified_character: character,
+                is_system_key: false as _,
+                focus_on_editable_field: true as _,
+            })
+        })
+        .collect()
+}
+
 fn is_not_character_key_code(keycode: &KeyCode) -> bool {
     match keycode {
         // Function keys are not character keys
@@ -246,7 +266,9 @@ fn macos_special_character(keycode: &KeyCode) -> Option<u16> {
     Some(character)
 }
 
-fn keycode_to_windows_vk(keycode: KeyCode) -> i32 {
+/// The Windows virtual-key code sent as `windows_key_code` for `keycode`; also
+/// what the page sees as `KeyboardEvent.keyCode`.
+pub fn keycode_to_windows_vk(keycode: KeyCode) -> i32 {
     match keycode {
         // Letters
         KeyCode::KeyA => 0x41,
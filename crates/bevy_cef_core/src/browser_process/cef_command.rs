@@ -1,33 +1,36 @@
-//! CefCommand enum and BrowsersProxy resource for the Windows multi-threaded
-//! message loop architecture.
+//! CefCommand enum and BrowsersProxy resource for the multi-threaded message
+//! loop architecture (always on Windows, opt-in on Linux).
 //!
-//! On Windows with `multi_threaded_message_loop`, CEF owns its own UI thread.
+//! With `multi_threaded_message_loop`, CEF owns its own UI thread.
 //! Bevy systems cannot call CEF APIs directly from arbitrary threads. Instead,
 //! they enqueue [`CefCommand`] variants through [`BrowsersProxy`], and a
 //! dedicated drain task on the CEF thread processes them.
 
 use async_channel::Sender;
+use bevy::input::touch::TouchPhase;
 use bevy::prelude::*;
-use bevy_remote::BrpMessage;
 use raw_window_handle::RawWindowHandle;
+use std::path::PathBuf;
 
-use crate::browser_process::client_handler::IpcEventRaw;
-use crate::browser_process::display_handler::{
-    AddressChangedSenderInner, SystemCursorIconSenderInner, TitleChangedSenderInner,
-};
-use crate::browser_process::drag_handler::DraggableRegionSenderInner;
-use crate::browser_process::load_handler::LoadHandlerSenderInner;
+use crate::bridge::BridgePolicyPayload;
+use crate::browser_process::browsers::EditCommand;
+use crate::browser_process::dev_tools_message_observer::CdpSenderInner;
+use crate::browser_process::life_span_handler::BrowserCreationError;
 use crate::browser_process::localhost::Requester;
+use crate::browser_process::webview_senders::WebviewSenders;
+use crate::user_script::UserScriptPayload;
 
 /// A `Send`-safe wrapper around [`RawWindowHandle`].
 ///
 /// `RawWindowHandle` is not `Send` because some variants contain raw pointers.
-/// On Windows the handle is an `HWND`, which is safe to send across threads.
+/// On Windows the handle is an `HWND`, which is safe to send across threads. On
+/// Linux the CEF side never dereferences it, as windowless browsers take no
+/// parent window.
 #[allow(deprecated)]
 pub struct SendRawWindowHandle(pub RawWindowHandle);
 
 // SAFETY: On Windows, `RawWindowHandle` contains a Win32 `HWND` which is safe
-// to send and share across threads.
+// to send and share across threads. On Linux it is only carried, never read.
 unsafe impl Send for SendRawWindowHandle {}
 unsafe impl Sync for SendRawWindowHandle {}
 
@@ -44,14 +47,9 @@ pub enum CefCommand {
         webview_size: Vec2,
         initial_dpr: f32,
         requester: Requester,
-        ipc_event_sender: Sender<IpcEventRaw>,
-        brp_sender: Sender<BrpMessage>,
-        system_cursor_icon_sender: SystemCursorIconSenderInner,
-        drag_regions_sender: DraggableRegionSenderInner,
-        load_handler_sender: LoadHandlerSenderInner,
-        address_changed_sender: AddressChangedSenderInner,
-        title_changed_sender: TitleChangedSenderInner,
-        initialize_scripts: Vec<String>,
+        senders: WebviewSenders,
+        user_scripts: Vec<UserScriptPayload>,
+        bridge: BridgePolicyPayload,
         window_handle: Option<SendRawWindowHandle>,
     },
 
@@ -102,15 +100,61 @@ pub enum CefCommand {
         delta: Vec2,
     },
 
+    /// Forward a touch point.
+    SendTouch {
+        webview: Entity,
+        id: i32,
+        position: Vec2,
+        phase: TouchPhase,
+        pressure: f32,
+    },
+
+    /// Start dragging files over a webview.
+    DragTargetEnter {
+        webview: Entity,
+        files: Vec<PathBuf>,
+        position: Vec2,
+    },
+
+    /// Move the file drag over a webview.
+    DragTargetOver { webview: Entity, position: Vec2 },
+
+    /// Cancel the file drag over a webview.
+    DragTargetLeave { webview: Entity },
+
+    /// Drop the file drag on a webview.
+    DragTargetDrop { webview: Entity, position: Vec2 },
+
+    /// Cancel a page-initiated drag.
+    CancelPageDrag { webview: Entity },
+
     /// Forward a keyboard event.
     SendKey {
         webview: Entity,
         event: cef::KeyEvent,
     },
 
+    /// Run an editor command in the webview's focused frame.
+    ExecEditCommand { webview: Entity, cmd: EditCommand },
+
     /// Set the CEF input focus state for a webview.
     SetFocus { webview: Entity, focused: bool },
 
+    /// Run JavaScript in the webview's main frame.
+    ExecuteJavaScript { webview: Entity, code: String },
+
+    /// Replace the webview's user scripts in its render processes.
+    SetUserScripts {
+        webview: Entity,
+        scripts: Vec<UserScriptPayload>,
+    },
+
+    /// Replace the webview's bridge policy in its render processes.
+    SetBridgePolicy {
+        webview: Entity,
+        policy: BridgePolicyPayload,
+    },
+
     /// Emit a host event to the webview's JS context.
     EmitEvent {
         webview: Entity,
@@ -121,6 +165,36 @@ pub enum CefCommand {
     /// Show DevTools for the given webview.
     ShowDevTool { webview: Entity },
 
+    /// Show DevTools for `webview` in the webview `surface`, replacing the
+    /// surface's browser. Failures are reported on the surface's lifecycle.
+    ShowDevToolIn {
+        webview: Entity,
+        surface: Entity,
+        size: Vec2,
+        dpr: f32,
+        senders: WebviewSenders,
+    },
+
+    /// Inspect the element at `position` (DIP) in the webview's DevTools.
+    InspectElement { webview: Entity, position: Vec2 },
+
+    /// Start reporting the webview's CDP results and events through `sender`.
+    OpenCdp {
+        webview: Entity,
+        sender: CdpSenderInner,
+    },
+
+    /// Stop reporting the webview's CDP messages.
+    CloseCdp { webview: Entity },
+
+    /// Send a raw CDP command to the webview, reporting a refusal through
+    /// `sender`.
+    SendCdp {
+        webview: Entity,
+        message: String,
+        sender: CdpSenderInner,
+    },
+
     /// Close DevTools for the given webview.
     CloseDevTools { webview: Entity },
 
@@ -130,6 +204,18 @@ pub enum CefCommand {
     /// Set audio muted state for a webview.
     SetAudioMuted { webview: Entity, muted: bool },
 
+    /// Mark a webview hidden or shown.
+    SetHidden { webview: Entity, hidden: bool },
+
+    /// Set how often CEF paints a webview.
+    SetFrameRate { webview: Entity, fps: i32 },
+
+    /// Repaint a webview's whole view on its next frame.
+    Invalidate { webview: Entity },
+
+    /// Ask a webview's render process for a pong.
+    Ping { webview: Entity },
+
     /// Reload all browsers.
     Reload,
 
@@ -175,6 +261,9 @@ impl BrowsersProxy {
         &self.tx
     }
 
+    /// Queues the creation of a browser. CEF's thread reports whether it was
+    /// created through `senders.lifecycle`; this only fails when the command
+    /// cannot be queued.
     #[allow(clippy::too_many_arguments, deprecated)]
     pub fn create_browser(
         &self,
@@ -183,32 +272,24 @@ impl BrowsersProxy {
         webview_size: Vec2,
         initial_dpr: f32,
         requester: Requester,
-        ipc_event_sender: Sender<IpcEventRaw>,
-        brp_sender: Sender<BrpMessage>,
-        system_cursor_icon_sender: SystemCursorIconSenderInner,
-        drag_regions_sender: DraggableRegionSenderInner,
-        load_handler_sender: LoadHandlerSenderInner,
-        address_changed_sender: AddressChangedSenderInner,
-        title_changed_sender: TitleChangedSenderInner,
-        initialize_scripts: &[String],
+        senders: WebviewSenders,
+        user_scripts: &[UserScriptPayload],
+        bridge: &BridgePolicyPayload,
         window_handle: Option<RawWindowHandle>,
-    ) {
-        let _ = self.tx.send_blocking(CefCommand::CreateBrowser {
-            webview,
-            uri: uri.to_owned(),
-            webview_size,
-            initial_dpr,
-            requester,
-            ipc_event_sender,
-            brp_sender,
-            system_cursor_icon_sender,
-            drag_regions_sender,
-            load_handler_sender,
-            address_changed_sender,
-            title_changed_sender,
-            initialize_scripts: initialize_scripts.to_vec(),
-            window_handle: window_handle.map(SendRawWindowHandle),
-        });
+    ) -> Result<(), BrowserCreationError> {
+        self.tx
+            .send_blocking(CefCommand::CreateBrowser {
+                webview,
+                uri: uri.to_owned(),
+                webview_size,
+                initial_dpr,
+                requester,
+                senders,
+                user_scripts: user_scripts.to_vec(),
+                bridge: bridge.clone(),
+                window_handle: window_handle.map(SendRawWindowHandle),
+            })
+            .map_err(|_| BrowserCreationError::BrowserNotCreated)
     }
 
     pub fn close(&self, entity: &Entity) {
@@ -298,6 +379,57 @@ impl BrowsersProxy {
         });
     }
 
+    pub fn send_touch(
+        &self,
+        webview: &Entity,
+        id: i32,
+        position: Vec2,
+        phase: TouchPhase,
+        pressure: f32,
+    ) {
+        let _ = self.tx.send_blocking(CefCommand::SendTouch {
+            webview: *webview,
+            id,
+            position,
+            phase,
+            pressure,
+        });
+    }
+
+    pub fn drag_target_enter(&self, webview: &Entity, files: &[PathBuf], position: Vec2) {
+        let _ = self.tx.send_blocking(CefCommand::DragTargetEnter {
+            webview: *webview,
+            files: files.to_vec(),
+            position,
+        });
+    }
+
+    pub fn drag_target_over(&self, webview: &Entity, position: Vec2) {
+        let _ = self.tx.send_blocking(CefCommand::DragTargetOver {
+            webview: *webview,
+            position,
+        });
+    }
+
+    pub fn drag_target_leave(&self, webview: &Entity) {
+        let _ = self
+            .tx
+            .send_blocking(CefCommand::DragTargetLeave { webview: *webview });
+    }
+
+    pub fn drag_target_drop(&self, webview: &Entity, position: Vec2) {
+        let _ = self.tx.send_blocking(CefCommand::DragTargetDrop {
+            webview: *webview,
+            position,
+        });
+    }
+
+    pub fn cancel_page_drag(&self, webview: &Entity) {
+        let _ = self
+            .tx
+            .send_blocking(CefCommand::CancelPageDrag { webview: *webview });
+    }
+
     pub fn send_key(&self, webview: &Entity, event: cef::KeyEvent) {
         let _ = self.tx.send_blocking(CefCommand::SendKey {
             webview: *webview,
@@ -305,6 +437,13 @@ impl BrowsersProxy {
         });
     }
 
+    pub fn exec_edit_command(&self, webview: &Entity, cmd: EditCommand) {
+        let _ = self.tx.send_blocking(CefCommand::ExecEditCommand {
+            webview: *webview,
+            cmd,
+        });
+    }
+
     /// Enqueue a focus-state change for a webview's browser.
     pub fn set_focus(&self, webview: &Entity, focused: bool) {
         let _ = self.tx.send_blocking(CefCommand::SetFocus {
@@ -321,12 +460,91 @@ impl BrowsersProxy {
         });
     }
 
+    pub fn execute_javascript(&self, webview: &Entity, code: &str) {
+        let _ = self.tx.send_blocking(CefCommand::ExecuteJavaScript {
+            webview: *webview,
+            code: code.to_owned(),
+        });
+    }
+
+    pub fn set_user_scripts(&self, webview: &Entity, scripts: &[UserScriptPayload]) {
+        let _ = self.tx.send_blocking(CefCommand::SetUserScripts {
+            webview: *webview,
+            scripts: scripts.to_vec(),
+        });
+    }
+
+    pub fn set_bridge_policy(&self, webview: &Entity, policy: &BridgePolicyPayload) {
+        let _ = self.tx.send_blocking(CefCommand::SetBridgePolicy {
+            webview: *webview,
+            policy: policy.clone(),
+        });
+    }
+
     pub fn show_devtool(&self, webview: &Entity) {
         let _ = self
             .tx
             .send_blocking(CefCommand::ShowDevTool { webview: *webview });
     }
 
+    /// Queues showing the DevTools of `webview` in `surface`, reporting like
+    /// [`BrowsersProxy::create_browser`].
+    pub fn show_devtool_in(
+        &self,
+        webview: &Entity,
+        surface: Entity,
+        size: Vec2,
+        dpr: f32,
+        senders: WebviewSenders,
+    ) -> Result<(), BrowserCreationError> {
+        self.tx
+            .send_blocking(CefCommand::ShowDevToolIn {
+                webview: *webview,
+                surface,
+                size,
+                dpr,
+                senders,
+            })
+            .map_err(|_| BrowserCreationError::BrowserNotCreated)
+    }
+
+    pub fn inspect_element_at(&self, webview: &Entity, position: Vec2) {
+        let _ = self.tx.send_blocking(CefCommand::InspectElement {
+            webview: *webview,
+            position,
+        });
+    }
+
+    /// Queues opening a CDP session, reported through `sender` as
+    /// [`CdpMessage::Opened`](crate::prelude::CdpMessage::Opened). Returns
+    /// whether the command was queued.
+    pub fn open_cdp(&self, webview: &Entity, sender: CdpSenderInner) -> bool {
+        self.tx
+            .send_blocking(CefCommand::OpenCdp {
+                webview: *webview,
+                sender,
+            })
+            .is_ok()
+    }
+
+    pub fn close_cdp(&self, webview: &Entity) {
+        let _ = self
+            .tx
+            .send_blocking(CefCommand::CloseCdp { webview: *webview });
+    }
+
+    /// Queues a CDP command; CEF's thread reports a refusal through `sender`.
+    /// Returns whether the command was queued.
+    pub fn send_cdp(&self, webview: &Entity, message: &str, sender: CdpSenderInner) -> bool {
+        self.tx
+            .send_blocking(CefCommand::SendCdp {
+                webview: *webview,
+                message: message.to_owned(),
+                sender,
+            })
+            .is_ok()
+    }
+
     pub fn close_devtools(&self, webview: &Entity) {
         let _ = self
             .tx
@@ -347,6 +565,32 @@ impl BrowsersProxy {
         });
     }
 
+    pub fn set_hidden(&self, webview: &Entity, hidden: bool) {
+        let _ = self.tx.send_blocking(CefCommand::SetHidden {
+            webview: *webview,
+            hidden,
+        });
+    }
+
+    pub fn set_frame_rate(&self, webview: &Entity, fps: i32) {
+        let _ = self.tx.send_blocking(CefCommand::SetFrameRate {
+            webview: *webview,
+            fps,
+        });
+    }
+
+    pub fn invalidate(&self, webview: &Entity) {
+        let _ = self
+            .tx
+            .send_blocking(CefCommand::Invalidate { webview: *webview });
+    }
+
+    pub fn ping(&self, webview: &Entity) {
+        let _ = self
+            .tx
+            .send_blocking(CefCommand::Ping { webview: *webview });
+    }
+
     pub fn reload(&self) {
         let _ = self.tx.send_blocking(CefCommand::Reload);
     }
@@ -1,48 +1,70 @@
-//! CEF-thread–resident browser state for Windows `multi_threaded_message_loop`.
+//! CEF-thread–resident browser state for `multi_threaded_message_loop`, which
+//! Windows always uses and Linux uses when `CefPlugin` opts in.
 //!
 //! [`BrowsersCefSide`] holds the actual `!Send` CEF browser objects and lives
 //! exclusively on the CEF UI thread.  Bevy systems communicate with it by
 //! sending [`CefCommand`]s through an `async_channel`, which are drained each
 //! tick via [`drain_commands`].
 
-// Module is already gated by #[cfg(target_os = "windows")] in browser_process.rs
+// Module is already gated by #[cfg(any(target_os = "windows", target_os = "linux"))] in browser_process.rs
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::path::PathBuf;
+use std::rc::Rc;
 use std::sync::{Arc, Mutex};
 
 use async_channel::{Receiver, Sender};
+use bevy::input::touch::TouchPhase;
 use bevy::platform::collections::HashMap;
 use bevy::prelude::*;
-use bevy_remote::BrpMessage;
 use cef::{
     BrowserSettings, CefString, Client, DictionaryValue, ImplBrowser, ImplBrowserHost,
     ImplDictionaryValue, ImplFrame, ImplListValue, ImplProcessMessage, ImplRequestContext,
-    MouseButtonType, ProcessId, Range, RequestContext, RequestContextSettings, WindowInfo,
-    browser_host_create_browser_sync, dictionary_value_create, process_message_create,
+    MouseButtonType, PaintElementType, ProcessId, Range, Registration, RequestContext,
+    RequestContextSettings, WindowInfo, browser_host_create_browser_sync, dictionary_value_create,
+    process_message_create,
 };
-use cef_dll_sys::{cef_event_flags_t, cef_mouse_button_type_t};
+use cef_dll_sys::{cef_event_flags_t, cef_mouse_button_type_t, cef_paint_element_type_t};
 #[allow(deprecated)]
 use raw_window_handle::RawWindowHandle;
 
+use crate::bridge::{BRIDGE_POLICY_KEY, BridgePolicyPayload};
 use crate::browser_process::ClientHandlerBuilder;
-use crate::browser_process::browsers::devtool_render_handler::DevToolRenderHandlerBuilder;
+use crate::browser_process::audio_handler::AudioHandlerBuilder;
 use crate::browser_process::browsers::{
-    WebviewBrowser, make_underlines_for, modifiers_from_mouse_buttons,
+    EditCommand, WebviewBrowser, devtools_surface_client, drag_page_over,
+    enable_renderer_accessibility, end_page_drag, make_underlines_for,
+    modifiers_from_mouse_buttons, send_bridge_policy, send_user_scripts, show_native_devtools,
+    show_surface_devtools, touch_event,
 };
 use crate::browser_process::cef_command::CefCommand;
-use crate::browser_process::client_handler::{BrpHandler, IpcEventRaw, JsEmitEventHandler};
-use crate::browser_process::display_handler::{
-    AddressChangedSenderInner, DisplayHandlerBuilder, SystemCursorIconSenderInner,
-    TitleChangedSenderInner,
+use crate::browser_process::client_handler::{
+    BrpHandler, ControlHandler, JsEmitEventHandler, PongHandler,
 };
-use crate::browser_process::drag_handler::{DragHandlerBuilder, DraggableRegionSenderInner};
-use crate::browser_process::load_handler::{LoadHandlerBuilder, LoadHandlerSenderInner};
+use crate::browser_process::dev_tools_message_observer::{
+    CdpMessage, CdpSenderInner, DevToolsMessageObserverBuilder,
+};
+use crate::browser_process::display_handler::DisplayHandlerBuilder;
+use crate::browser_process::drag_drop::{
+    SharedPageDrag, allowed_drag_operations, drag_mouse_event, file_drag_data,
+};
+use crate::browser_process::drag_handler::DragHandlerBuilder;
+use crate::browser_process::focus_handler::FocusHandlerBuilder;
+use crate::browser_process::keyboard_handler::KeyboardHandlerBuilder;
+use crate::browser_process::life_span_handler::{
+    BrowserCreationError, BrowserLifecycleEvent, BrowserLifecycleMessage, LifeSpanHandlerBuilder,
+    SharedCreatedBrowser,
+};
+use crate::browser_process::load_handler::LoadHandlerBuilder;
 use crate::browser_process::localhost::{LocalSchemaHandlerBuilder, Requester};
 use crate::browser_process::renderer_handler::{
     RenderHandlerBuilder, RenderTextureMessage, SharedDpr, SharedViewSize, TextureSender,
 };
 use crate::browser_process::request_context_handler::RequestContextHandlerBuilder;
-use crate::prelude::{INIT_SCRIPT_KEY, IntoString, PROCESS_MESSAGE_HOST_EMIT};
+use crate::browser_process::request_handler::RequestHandlerBuilder;
+use crate::browser_process::webview_senders::WebviewSenders;
+use crate::prelude::{IntoString, PROCESS_MESSAGE_HOST_EMIT, PROCESS_MESSAGE_PING};
+use crate::user_script::{USER_SCRIPTS_KEY, UserScriptPayload};
 use crate::util::{HOST_CEF, SCHEME_CEF};
 
 /// CEF-thread counterpart of `Browsers`.
@@ -51,6 +73,8 @@ use crate::util::{HOST_CEF, SCHEME_CEF};
 /// objects that must only be touched from the CEF UI thread.
 pub struct BrowsersCefSide {
     browsers: HashMap<Entity, WebviewBrowser>,
+    /// DevTools message observers of the webviews with an open CDP session.
+    cdp: HashMap<Entity, Registration>,
     texture_sender: TextureSender,
 }
 
@@ -59,6 +83,7 @@ impl BrowsersCefSide {
     pub fn new(texture_sender: TextureSender) -> Self {
         Self {
             browsers: HashMap::default(),
+            cdp: HashMap::default(),
             texture_sender,
         }
     }
@@ -82,34 +107,30 @@ impl BrowsersCefSide {
                 webview_size,
                 initial_dpr,
                 requester,
-                ipc_event_sender,
-                brp_sender,
-                system_cursor_icon_sender,
-                drag_regions_sender,
-                load_handler_sender,
-                address_changed_sender,
-                title_changed_sender,
-                initialize_scripts,
+                senders,
+                user_scripts,
+                bridge,
                 window_handle,
             } => {
                 #[allow(deprecated)]
                 let raw_handle = window_handle.map(|h| h.0);
-                self.create_browser(
+                let lifecycle = senders.lifecycle.clone();
+                if let Err(error) = self.create_browser(
                     webview,
                     &uri,
                     webview_size,
                     initial_dpr,
                     requester,
-                    ipc_event_sender,
-                    brp_sender,
-                    system_cursor_icon_sender,
-                    drag_regions_sender,
-                    load_handler_sender,
-                    address_changed_sender,
-                    title_changed_sender,
-                    &initialize_scripts,
+                    senders,
+                    &user_scripts,
+                    &bridge,
                     raw_handle,
-                );
+                ) {
+                    let _ = lifecycle.send_blocking(BrowserLifecycleMessage {
+                        webview,
+                        event: BrowserLifecycleEvent::CreationFailed(error),
+                    });
+                }
             }
             CefCommand::Close { entity } => self.close(&entity),
             CefCommand::Navigate { entity, url } => self.navigate(&entity, &url),
@@ -138,12 +159,69 @@ impl BrowsersCefSide {
                 position,
                 delta,
             } => self.send_mouse_wheel(&webview, position, delta),
+            CefCommand::SendTouch {
+                webview,
+                id,
+                position,
+                phase,
+                pressure,
+            } => self.send_touch(&webview, id, position, phase, pressure),
+            CefCommand::DragTargetEnter {
+                webview,
+                files,
+                position,
+            } => self.drag_target_enter(&webview, &files, position),
+            CefCommand::DragTargetOver { webview, position } => {
+                self.drag_target_over(&webview, position);
+            }
+            CefCommand::DragTargetLeave { webview } => self.drag_target_leave(&webview),
+            CefCommand::DragTargetDrop { webview, position } => {
+                self.drag_target_drop(&webview, position);
+            }
+            CefCommand::CancelPageDrag { webview } => self.cancel_page_drag(&webview),
             CefCommand::SendKey { webview, event } => self.send_key(&webview, event),
+            CefCommand::ExecEditCommand { webview, cmd } => self.exec_edit_command(&webview, cmd),
             CefCommand::SetFocus { webview, focused } => self.set_focus(&webview, focused),
+            CefCommand::ExecuteJavaScript { webview, code } => {
+                self.execute_javascript(&webview, &code);
+            }
+            CefCommand::SetUserScripts { webview, scripts } => {
+                self.set_user_scripts(&webview, &scripts);
+            }
+            CefCommand::SetBridgePolicy { webview, policy } => {
+                self.set_bridge_policy(&webview, &policy);
+            }
             CefCommand::EmitEvent { webview, id, event } => {
                 self.emit_event(&webview, id, &event);
             }
             CefCommand::ShowDevTool { webview } => self.show_devtool(&webview),
+            CefCommand::ShowDevToolIn {
+                webview,
+                surface,
+                size,
+                dpr,
+                senders,
+            } => {
+                let lifecycle = senders.lifecycle.clone();
+                if let Err(error) = self.show_devtool_in(&webview, surface, size, dpr, senders) {
+                    let _ = lifecycle.send_blocking(BrowserLifecycleMessage {
+                        webview: surface,
+                        event: BrowserLifecycleEvent::CreationFailed(error),
+                    });
+                }
+            }
+            CefCommand::InspectElement { webview, position } => {
+                self.inspect_element_at(&webview, position);
+            }
+            CefCommand::OpenCdp { webview, sender } => self.open_cdp(&webview, sender),
+            CefCommand::CloseCdp { webview } => {
+                self.cdp.remove(&webview);
+            }
+            CefCommand::SendCdp {
+                webview,
+                message,
+                sender,
+            } => self.send_cdp(&webview, &message, &sender),
             CefCommand::CloseDevTools { webview } => self.close_devtools(&webview),
             CefCommand::SetZoomLevel {
                 webview,
@@ -152,6 +230,10 @@ impl BrowsersCefSide {
             CefCommand::SetAudioMuted { webview, muted } => {
                 self.set_audio_muted(&webview, muted);
             }
+            CefCommand::SetHidden { webview, hidden } => self.set_hidden(&webview, hidden),
+            CefCommand::SetFrameRate { webview, fps } => self.set_frame_rate(&webview, fps),
+            CefCommand::Invalidate { webview } => self.invalidate(&webview),
+            CefCommand::Ping { webview } => self.ping(&webview),
             CefCommand::Reload => self.reload(),
             CefCommand::SetImeComposition { text, cursor_utf16 } => {
                 self.set_ime_composition(&text, cursor_utf16)
@@ -172,23 +254,21 @@ impl BrowsersCefSide {
         webview_size: Vec2,
         initial_dpr: f32,
         requester: Requester,
-        ipc_event_sender: Sender<IpcEventRaw>,
-        brp_sender: Sender<BrpMessage>,
-        system_cursor_icon_sender: SystemCursorIconSenderInner,
-        drag_regions_sender: DraggableRegionSenderInner,
-        load_handler_sender: LoadHandlerSenderInner,
-        address_changed_sender: AddressChangedSenderInner,
-        title_changed_sender: TitleChangedSenderInner,
-        initialize_scripts: &[String],
+        senders: WebviewSenders,
+        user_scripts: &[UserScriptPayload],
+        bridge: &BridgePolicyPayload,
         #[allow(deprecated)] _window_handle: Option<RawWindowHandle>,
-    ) {
+    ) -> Result<(), BrowserCreationError> {
         let mut context = Self::request_context(requester);
+        let accessibility = senders.accessibility.is_some();
         let size: SharedViewSize = Arc::new(Mutex::new(webview_size));
         let dpr: SharedDpr = Arc::new(Mutex::new(initial_dpr));
+        let page_drag: SharedPageDrag = Rc::new(Cell::new(None));
         let browser = browser_host_create_browser_sync(
             Some(&WindowInfo {
                 windowless_rendering_enabled: true as _,
                 external_begin_frame_enabled: false as _,
+                #[cfg(target_os = "windows")]
                 #[allow(deprecated)]
                 parent_window: match _window_handle {
                     Some(RawWindowHandle::Win32(handle)) => {
@@ -196,40 +276,48 @@ impl BrowsersCefSide {
                     }
                     _ => cef_dll_sys::HWND(std::ptr::null_mut()),
                 },
+                // Windowless rendering does not require a parent window handle on Linux.
+                #[cfg(target_os = "linux")]
+                parent_window: 0,
                 ..Default::default()
             }),
             Some(&mut self.client_handler(
                 webview,
                 size.clone(),
                 dpr.clone(),
-                ipc_event_sender,
-                brp_sender,
-                system_cursor_icon_sender,
-                drag_regions_sender,
-                load_handler_sender,
-                address_changed_sender,
-                title_changed_sender,
+                page_drag.clone(),
+                senders,
+                None,
             )),
             Some(&uri.into()),
             Some(&BrowserSettings {
                 windowless_frame_rate: 60,
                 ..Default::default()
             }),
-            Self::create_extra_info(initialize_scripts).as_mut(),
+            Self::create_extra_info(user_scripts, bridge).as_mut(),
             context.as_mut(),
         )
-        .expect("Failed to create browser");
-        let host = browser.host().expect("Failed to get browser host");
+        .ok_or(BrowserCreationError::BrowserNotCreated)?;
+        let host = browser
+            .host()
+            .ok_or(BrowserCreationError::HostUnavailable)?;
+        if accessibility {
+            enable_renderer_accessibility(&host);
+        }
         let webview_browser = WebviewBrowser {
             host,
             client: browser,
             size,
             dpr,
+            page_drag,
+            inspected: None,
         };
         self.browsers.insert(webview, webview_browser);
+        Ok(())
     }
 
     fn close(&mut self, entity: &Entity) {
+        self.cdp.remove(entity);
         if let Some(browser) = self.browsers.remove(entity) {
             browser.host.close_browser(true as _);
             debug!("Closed browser with webview: {:?}", entity);
@@ -310,6 +398,10 @@ impl BrowsersCefSide {
         mouse_leave: bool,
     ) {
         if let Some(browser) = self.get_focused_browser(webview) {
+            if browser.page_drag.get().is_some() {
+                drag_page_over(browser, position);
+                return;
+            }
             let mouse_event = cef::MouseEvent {
                 x: position.x as i32,
                 y: position.y as i32,
@@ -329,6 +421,12 @@ impl BrowsersCefSide {
         mouse_up: bool,
     ) {
         if let Some(browser) = self.get_focused_browser(webview) {
+            if browser.page_drag.get().is_some() {
+                if mouse_up && button == PointerButton::Primary {
+                    end_page_drag(browser, Some(position));
+                }
+                return;
+            }
             let mouse_event = cef::MouseEvent {
                 x: position.x as i32,
                 y: position.y as i32,
@@ -366,12 +464,86 @@ impl BrowsersCefSide {
         }
     }
 
+    fn send_touch(
+        &self,
+        webview: &Entity,
+        id: i32,
+        position: Vec2,
+        phase: TouchPhase,
+        pressure: f32,
+    ) {
+        if let Some(browser) = self.browsers.get(webview) {
+            if phase == TouchPhase::Started {
+                browser.host.set_focus(true as _);
+            }
+            browser
+                .host
+                .send_touch_event(Some(&touch_event(id, position, phase, pressure)));
+        }
+    }
+
+    fn drag_target_enter(&self, webview: &Entity, files: &[PathBuf], position: Vec2) {
+        if let Some(browser) = self.browsers.get(webview)
+            && let Some(mut data) = file_drag_data(files)
+        {
+            browser.host.drag_target_drag_enter(
+                Some(&mut data),
+                Some(&drag_mouse_event(position)),
+                allowed_drag_operations(),
+            );
+        }
+    }
+
+    fn drag_target_over(&self, webview: &Entity, position: Vec2) {
+        if let Some(browser) = self.browsers.get(webview) {
+            browser.host.drag_target_drag_over(
+                Some(&drag_mouse_event(position)),
+                allowed_drag_operations(),
+            );
+        }
+    }
+
+    fn drag_target_leave(&self, webview: &Entity) {
+        if let Some(browser) = self.browsers.get(webview) {
+            browser.host.drag_target_drag_leave();
+        }
+    }
+
+    fn drag_target_drop(&self, webview: &Entity, position: Vec2) {
+        if let Some(browser) = self.browsers.get(webview) {
+            browser
+                .host
+                .drag_target_drop(Some(&drag_mouse_event(position)));
+        }
+    }
+
+    fn cancel_page_drag(&self, webview: &Entity) {
+        if let Some(browser) = self.browsers.get(webview) {
+            end_page_drag(browser, None);
+        }
+    }
+
     fn send_key(&self, webview: &Entity, event: cef::KeyEvent) {
         if let Some(browser) = self.get_focused_browser(webview) {
             browser.host.send_key_event(Some(&event));
         }
     }
 
+    fn exec_edit_command(&self, webview: &Entity, cmd: EditCommand) {
+        if let Some(browser) = self.browsers.get(webview)
+            && let Some(frame) = browser.client.focused_frame()
+        {
+            match cmd {
+                EditCommand::Copy => frame.copy(),
+                EditCommand::Cut => frame.cut(),
+                EditCommand::Paste => frame.paste(),
+                EditCommand::SelectAll => frame.select_all(),
+                EditCommand::Undo => frame.undo(),
+                EditCommand::Redo => frame.redo(),
+            }
+        }
+    }
+
     /// Sets the CEF input focus state for a webview's browser.
     ///
     /// Uses a direct lookup (not `get_focused_browser`) because this is what
@@ -398,16 +570,132 @@ impl BrowsersCefSide {
         };
     }
 
+    fn execute_javascript(&self, webview: &Entity, code: &str) {
+        if let Some(browser) = self.browsers.get(webview)
+            && let Some(frame) = browser.client.main_frame()
+        {
+            frame.execute_java_script(Some(&code.into()), Some(&(&frame.url()).into()), 0);
+        }
+    }
+
+    fn set_user_scripts(&self, webview: &Entity, scripts: &[UserScriptPayload]) {
+        if let Some(browser) = self.browsers.get(webview) {
+            send_user_scripts(&browser.client, scripts);
+        }
+    }
+
+    fn set_bridge_policy(&self, webview: &Entity, policy: &BridgePolicyPayload) {
+        if let Some(browser) = self.browsers.get(webview) {
+            send_bridge_policy(&browser.client, policy);
+        }
+    }
+
     fn show_devtool(&self, webview: &Entity) {
+        if let Some(browser) = self.browsers.get(webview) {
+            show_native_devtools(&browser.host, None);
+        }
+    }
+
+    fn show_devtool_in(
+        &mut self,
+        webview: &Entity,
+        surface: Entity,
+        surface_size: Vec2,
+        dpr: f32,
+        senders: WebviewSenders,
+    ) -> Result<(), BrowserCreationError> {
+        if *webview == surface || !self.browsers.contains_key(webview) {
+            return Err(BrowserCreationError::BrowserNotCreated);
+        }
+        self.close(&surface);
+        let size: SharedViewSize = Arc::new(Mutex::new(surface_size));
+        let dpr: SharedDpr = Arc::new(Mutex::new(dpr));
+        let page_drag: SharedPageDrag = Rc::new(Cell::new(None));
+        let created: SharedCreatedBrowser = Rc::new(RefCell::new(None));
+        let mut client = self.client_handler(
+            surface,
+            size.clone(),
+            dpr.clone(),
+            page_drag.clone(),
+            senders,
+            Some(created.clone()),
+        );
+        show_surface_devtools(
+            &self.browsers[webview].host,
+            &surface_window_info(),
+            &mut client,
+            None,
+        );
+        let browser = created
+            .take()
+            .ok_or(BrowserCreationError::BrowserNotCreated)?;
+        let host = browser
+            .host()
+            .ok_or(BrowserCreationError::HostUnavailable)?;
+        self.browsers.insert(
+            surface,
+            WebviewBrowser {
+                host,
+                client: browser,
+                size,
+                dpr,
+                page_drag,
+                inspected: Some(*webview),
+            },
+        );
+        Ok(())
+    }
+
+    fn inspect_element_at(&self, webview: &Entity, position: Vec2) {
         let Some(browser) = self.browsers.get(webview) else {
             return;
         };
-        browser.host.show_dev_tools(
-            Some(&WindowInfo::default()),
-            Some(&mut ClientHandlerBuilder::new(DevToolRenderHandlerBuilder::build()).build()),
-            Some(&BrowserSettings::default()),
-            None,
-        );
+        match devtools_surface_client(&self.browsers, webview) {
+            Some(mut client) => show_surface_devtools(
+                &browser.host,
+                &surface_window_info(),
+                &mut client,
+                Some(position),
+            ),
+            None => show_native_devtools(&browser.host, Some(position)),
+        }
+    }
+
+    fn open_cdp(&mut self, webview: &Entity, sender: CdpSenderInner) {
+        if let Some(browser) = self.browsers.get(webview)
+            && !self.cdp.contains_key(webview)
+            && let Some(registration) = browser.host.add_dev_tools_message_observer(Some(
+                &mut DevToolsMessageObserverBuilder::build(*webview, sender.clone()),
+            ))
+        {
+            self.cdp.insert(*webview, registration);
+        }
+        let _ = sender.send_blocking(CdpMessage::Opened {
+            webview: *webview,
+            success: self.cdp.contains_key(webview),
+        });
+    }
+
+    /// Reports through `sender` unless the command reached an open session's
+    /// browser, so a refused command never leaves its call pending.
+    fn send_cdp(&self, webview: &Entity, message: &str, sender: &CdpSenderInner) {
+        let sent = self.cdp.contains_key(webview)
+            && self.browsers.get(webview).is_some_and(|browser| {
+                browser
+                    .host
+                    .send_dev_tools_message(Some(message.as_bytes()))
+                    != 0
+            });
+        if !sent {
+            let id = serde_json::from_str::<serde_json::Value>(message)
+                .ok()
+                .and_then(|message| message["id"].as_i64())
+                .unwrap_or_default();
+            let _ = sender.send_blocking(CdpMessage::NotSent {
+                webview: *webview,
+                id: id as i32,
+            });
+        }
     }
 
     fn close_devtools(&self, webview: &Entity) {
@@ -428,6 +716,39 @@ impl BrowsersCefSide {
         }
     }
 
+    fn set_hidden(&self, webview: &Entity, hidden: bool) {
+        if let Some(browser) = self.browsers.get(webview) {
+            browser.host.was_hidden(hidden as _);
+        }
+    }
+
+    fn set_frame_rate(&self, webview: &Entity, fps: i32) {
+        if let Some(browser) = self.browsers.get(webview) {
+            browser.host.set_windowless_frame_rate(fps);
+        }
+    }
+
+    fn invalidate(&self, webview: &Entity) {
+        if let Some(browser) = self.browsers.get(webview) {
+            browser
+                .host
+                .invalidate(PaintElementType::from(cef_paint_element_type_t::PET_VIEW));
+        }
+    }
+
+    fn ping(&self, webview: &Entity) {
+        if let Some(mut process_message) =
+            process_message_create(Some(&PROCESS_MESSAGE_PING.into()))
+            && let Some(browser) = self.browsers.get(webview)
+            && let Some(frame) = browser.client.main_frame()
+        {
+            frame.send_process_message(
+                ProcessId::from(cef_dll_sys::cef_process_id_t::PID_RENDERER),
+                Some(&mut process_message),
+            );
+        }
+    }
+
     fn reload(&self) {
         for browser in self.browsers.values() {
             if let Some(frame) = browser.client.main_frame() {
@@ -509,47 +830,76 @@ impl BrowsersCefSide {
         context
     }
 
-    #[allow(clippy::too_many_arguments)]
     fn client_handler(
         &self,
         webview: Entity,
         size: SharedViewSize,
         dpr: SharedDpr,
-        ipc_event_sender: Sender<IpcEventRaw>,
-        brp_sender: Sender<BrpMessage>,
-        system_cursor_icon_sender: SystemCursorIconSenderInner,
-        drag_regions_sender: DraggableRegionSenderInner,
-        load_handler_sender: LoadHandlerSenderInner,
-        address_changed_sender: AddressChangedSenderInner,
-        title_changed_sender: TitleChangedSenderInner,
+        page_drag: SharedPageDrag,
+        senders: WebviewSenders,
+        created: Option<SharedCreatedBrowser>,
     ) -> Client {
-        ClientHandlerBuilder::new(RenderHandlerBuilder::build(
+        let client = ClientHandlerBuilder::new(RenderHandlerBuilder::build(
             webview,
             self.texture_sender.clone(),
             size.clone(),
             dpr,
+            page_drag,
+            senders.drag_started,
+            senders.accessibility,
         ))
         .with_display_handler(DisplayHandlerBuilder::build(
             webview,
-            system_cursor_icon_sender,
-            address_changed_sender,
-            title_changed_sender,
+            senders.cursor_changed,
+            senders.address_changed,
+            senders.title_changed,
+            senders.console,
+        ))
+        .with_drag_handler(DragHandlerBuilder::build(webview, senders.drag_regions))
+        .with_load_handler(LoadHandlerBuilder::build(webview, senders.load_handler))
+        .with_keyboard_handler(KeyboardHandlerBuilder::build(
+            webview,
+            senders.key_unhandled,
+        ))
+        .with_focus_handler(FocusHandlerBuilder::build(webview, senders.take_focus))
+        .with_life_span_handler(LifeSpanHandlerBuilder::build(
+            webview,
+            senders.lifecycle,
+            created,
+        ))
+        .with_request_handler(RequestHandlerBuilder::build(
+            webview,
+            senders.render_process.clone(),
         ))
-        .with_drag_handler(DragHandlerBuilder::build(webview, drag_regions_sender))
-        .with_load_handler(LoadHandlerBuilder::build(webview, load_handler_sender))
-        .with_message_handler(JsEmitEventHandler::new(webview, ipc_event_sender))
-        .with_message_handler(BrpHandler::new(brp_sender))
+        .with_message_handler(JsEmitEventHandler::new(webview, senders.ipc_event))
+        .with_message_handler(BrpHandler::new(webview, senders.brp))
+        .with_message_handler(ControlHandler::new(webview, senders.control))
+        .with_message_handler(PongHandler::new(webview, senders.render_process));
+        match senders.audio {
+            Some(audio) => client.with_audio_handler(AudioHandlerBuilder::build(webview, audio)),
+            None => client,
+        }
         .build()
     }
 
-    fn create_extra_info(scripts: &[String]) -> Option<DictionaryValue> {
-        if scripts.is_empty() {
-            return None;
-        }
+    fn create_extra_info(
+        scripts: &[UserScriptPayload],
+        bridge: &BridgePolicyPayload,
+    ) -> Option<DictionaryValue> {
         let extra = dictionary_value_create()?;
+        if !scripts.is_empty() {
+            extra.set_string(
+                Some(&CefString::from(USER_SCRIPTS_KEY)),
+                Some(&CefString::from(
+                    serde_json::to_string(scripts).ok()?.as_str(),
+                )),
+            );
+        }
         extra.set_string(
-            Some(&CefString::from(INIT_SCRIPT_KEY)),
-            Some(&CefString::from(scripts.join(";").as_str())),
+            Some(&CefString::from(BRIDGE_POLICY_KEY)),
+            Some(&CefString::from(
+                serde_json::to_string(bridge).ok()?.as_str(),
+            )),
         );
         Some(extra)
     }
@@ -596,3 +946,13 @@ pub fn drain_commands(rx: &Receiver<CefCommand>) {
         }
     });
 }
+
+/// Off-screen windows of DevTools surfaces. CEF paints them on its own timer
+/// here, like every other webview.
+fn surface_window_info() -> WindowInfo {
+    WindowInfo {
+        windowless_rendering_enabled: true as _,
+        external_begin_frame_enabled: false as _,
+        ..Default::default()
+    }
+}
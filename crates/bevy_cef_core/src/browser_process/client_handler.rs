@@ -1,18 +1,22 @@
 mod brp_handler;
+mod control_handler;
 mod js_emit_event_handler;
+mod pong_handler;
 
 use crate::browser_process::ContextMenuHandlerBuilder;
 use crate::prelude::IntoString;
 use cef::rc::{Rc, RcImpl};
 use cef::{
-    Browser, Client, ContextMenuHandler, DisplayHandler, DragHandler, Frame, ImplClient,
-    ImplProcessMessage, ListValue, LoadHandler, ProcessId, ProcessMessage, RenderHandler,
-    WrapClient, sys,
+    AudioHandler, Browser, Client, ContextMenuHandler, DisplayHandler, DragHandler, FocusHandler,
+    Frame, ImplClient, ImplProcessMessage, KeyboardHandler, LifeSpanHandler, ListValue,
+    LoadHandler, ProcessId, ProcessMessage, RenderHandler, RequestHandler, WrapClient, sys,
 };
 use std::os::raw::c_int;
 
-pub use brp_handler::BrpHandler;
+pub use brp_handler::{BrpHandler, WebviewBrpRequest, WebviewBrpSenderInner};
+pub use control_handler::{ControlHandler, ControlMessage, ControlMessageSenderInner};
 pub use js_emit_event_handler::{IpcEventRaw, JsEmitEventHandler};
+pub use pong_handler::PongHandler;
 
 pub trait ProcessMessageHandler {
     fn process_name(&self) -> &'static str;
@@ -31,6 +35,11 @@ pub struct ClientHandlerBuilder {
     display_handler: Option<DisplayHandler>,
     drag_handler: Option<DragHandler>,
     load_handler: Option<LoadHandler>,
+    keyboard_handler: Option<KeyboardHandler>,
+    focus_handler: Option<FocusHandler>,
+    audio_handler: Option<AudioHandler>,
+    request_handler: Option<RequestHandler>,
+    life_span_handler: Option<LifeSpanHandler>,
 }
 
 impl ClientHandlerBuilder {
@@ -43,6 +52,11 @@ impl ClientHandlerBuilder {
             display_handler: None,
             drag_handler: None,
             load_handler: None,
+            keyboard_handler: None,
+            focus_handler: None,
+            audio_handler: None,
+            request_handler: None,
+            life_span_handler: None,
         }
     }
 
@@ -61,6 +75,31 @@ impl ClientHandlerBuilder {
         self
     }
 
+    pub fn with_keyboard_handler(mut self, keyboard_handler: KeyboardHandler) -> Self {
+        self.keyboard_handler = Some(keyboard_handler);
+        self
+    }
+
+    pub fn with_focus_handler(mut self, focus_handler: FocusHandler) -> Self {
+        self.focus_handler = Some(focus_handler);
+        self
+    }
+
+    pub fn with_audio_handler(mut self, audio_handler: AudioHandler) -> Self {
+        self.audio_handler = Some(audio_handler);
+        self
+    }
+
+    pub fn with_request_handler(mut self, request_handler: RequestHandler) -> Self {
+        self.request_handler = Some(request_handler);
+        self
+    }
+
+    pub fn with_life_span_handler(mut self, life_span_handler: LifeSpanHandler) -> Self {
+        self.life_span_handler = Some(life_span_handler);
+        self
+    }
+
     pub fn with_message_handler(mut self, handler: impl ProcessMessageHandler + 'static) -> Self {
         self.message_handlers.push(std::rc::Rc::new(handler));
         self
@@ -102,6 +141,11 @@ impl Clone for ClientHandlerBuilder {
             display_handler: self.display_handler.clone(),
             drag_handler: self.drag_handler.clone(),
             load_handler: self.load_handler.clone(),
+            keyboard_handler: self.keyboard_handler.clone(),
+            focus_handler: self.focus_handler.clone(),
+            audio_handler: self.audio_handler.clone(),
+            request_handler: self.request_handler.clone(),
+            life_span_handler: self.life_span_handler.clone(),
         }
     }
 }
@@ -123,6 +167,26 @@ impl ImplClient for ClientHandlerBuilder {
         self.load_handler.clone()
     }
 
+    fn keyboard_handler(&self) -> Option<KeyboardHandler> {
+        self.keyboard_handler.clone()
+    }
+
+    fn focus_handler(&self) -> Option<FocusHandler> {
+        self.focus_handler.clone()
+    }
+
+    fn audio_handler(&self) -> Option<AudioHandler> {
+        self.audio_handler.clone()
+    }
+
+    fn request_handler(&self) -> Option<RequestHandler> {
+        self.request_handler.clone()
+    }
+
+    fn life_span_handler(&self) -> Option<LifeSpanHandler> {
+        self.life_span_handler.clone()
+    }
+
     fn on_process_message_received(
         &self,
         browser: Option<&mut Browser>,
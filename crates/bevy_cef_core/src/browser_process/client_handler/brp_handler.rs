@@ -1,7 +1,8 @@
 use crate::browser_process::client_handler::ProcessMessageHandler;
 use crate::prelude::PROCESS_MESSAGE_BRP;
-use crate::util::IntoString;
+use crate::util::{IntoString, url_origin};
 use async_channel::Sender;
+use bevy::prelude::Entity;
 use bevy::tasks::IoTaskPool;
 use bevy_remote::{BrpMessage, BrpRequest};
 use cef::{
@@ -10,13 +11,25 @@ use cef::{
 };
 use cef_dll_sys::cef_process_id_t;
 
+/// A `cef.brp` call from a page, to be checked against the webview's policy
+/// before it reaches the Bevy Remote Protocol.
+pub struct WebviewBrpRequest {
+    pub webview: Entity,
+    /// Origin of the calling frame, e.g. `cef://localhost`.
+    pub origin: String,
+    pub message: BrpMessage,
+}
+
+pub type WebviewBrpSenderInner = Sender<WebviewBrpRequest>;
+
 pub struct BrpHandler {
-    sender: Sender<BrpMessage>,
+    webview: Entity,
+    sender: WebviewBrpSenderInner,
 }
 
 impl BrpHandler {
-    pub const fn new(sender: Sender<BrpMessage>) -> Self {
-        Self { sender }
+    pub const fn new(webview: Entity, sender: WebviewBrpSenderInner) -> Self {
+        Self { webview, sender }
     }
 }
 
@@ -30,16 +43,22 @@ impl ProcessMessageHandler for BrpHandler {
             && let Ok(request) = serde_json::from_str::<BrpRequest>(&args.string(1).into_string())
         {
             let id = args.string(0).into_string();
+            let origin = url_origin(&frame.url().into_string());
             let frame = frame.clone();
+            let webview = self.webview;
             let brp_sender = self.sender.clone();
             IoTaskPool::get()
                 .spawn(async move {
                     let (tx, rx) = async_channel::unbounded();
                     if brp_sender
-                        .send(BrpMessage {
-                            method: request.method,
-                            params: request.params,
-                            sender: tx,
+                        .send(WebviewBrpRequest {
+                            webview,
+                            origin,
+                            message: BrpMessage {
+                                method: request.method,
+                                params: request.params,
+                                sender: tx,
+                            },
                         })
                         .await
                         .is_err()
@@ -0,0 +1,52 @@
+use crate::browser_process::client_handler::ProcessMessageHandler;
+use crate::prelude::{IntoString, PROCESS_MESSAGE_CONTROL};
+use crate::util::url_origin;
+use async_channel::Sender;
+use bevy::prelude::Entity;
+use cef::{Browser, Frame, ImplFrame, ImplListValue, ListValue};
+
+/// A request from page JavaScript to bevy_cef itself, such as
+/// `window.cef.gamepad.release()`.
+///
+/// Unlike `cef.emit` payloads, these are consumed by bevy_cef's own plugins and
+/// never reach user `Receive<E>` observers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ControlMessage {
+    pub webview: Entity,
+    /// Origin of the frame that sent the command, e.g. `cef://localhost`.
+    pub origin: String,
+    /// Dotted command name, e.g. `gamepad.release`.
+    pub name: String,
+    /// JSON-encoded argument; `null` when the command takes none.
+    pub payload: String,
+}
+
+pub type ControlMessageSenderInner = Sender<ControlMessage>;
+
+pub struct ControlHandler {
+    webview: Entity,
+    sender: ControlMessageSenderInner,
+}
+
+impl ControlHandler {
+    pub const fn new(webview: Entity, sender: ControlMessageSenderInner) -> Self {
+        Self { webview, sender }
+    }
+}
+
+impl ProcessMessageHandler for ControlHandler {
+    fn process_name(&self) -> &'static str {
+        PROCESS_MESSAGE_CONTROL
+    }
+
+    fn handle_message(&self, _browser: &mut Browser, frame: &mut Frame, args: Option<ListValue>) {
+        if let Some(args) = args {
+            let _ = self.sender.send_blocking(ControlMessage {
+                webview: self.webview,
+                origin: url_origin(&frame.url().into_string()),
+                name: args.string(0).into_string(),
+                payload: args.string(1).into_string(),
+            });
+        }
+    }
+}
@@ -1,13 +1,16 @@
 use crate::browser_process::client_handler::ProcessMessageHandler;
 use crate::prelude::{IntoString, PROCESS_MESSAGE_JS_EMIT};
+use crate::util::url_origin;
 use async_channel::Sender;
 use bevy::prelude::Entity;
-use cef::{Browser, Frame, ImplListValue, ListValue};
+use cef::{Browser, Frame, ImplFrame, ImplListValue, ListValue};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct IpcEventRaw {
     pub webview: Entity,
+    /// Origin of the frame that emitted the event, e.g. `cef://localhost`.
+    pub origin: String,
     pub payload: String,
 }
 
@@ -27,10 +30,11 @@ impl ProcessMessageHandler for JsEmitEventHandler {
         PROCESS_MESSAGE_JS_EMIT
     }
 
-    fn handle_message(&self, _browser: &mut Browser, _frame: &mut Frame, args: Option<ListValue>) {
+    fn handle_message(&self, _browser: &mut Browser, frame: &mut Frame, args: Option<ListValue>) {
         if let Some(args) = args {
             let event = IpcEventRaw {
-                webview: self.webview, // Placeholder, should be set correctly
+                webview: self.webview,
+                origin: url_origin(&frame.url().into_string()),
                 payload: args.string(0).into_string(),
             };
             let _ = self.sender.send_blocking(event);
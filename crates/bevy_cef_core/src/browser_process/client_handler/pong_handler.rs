@@ -0,0 +1,33 @@
+use crate::browser_process::client_handler::ProcessMessageHandler;
+use crate::browser_process::request_handler::{
+    RenderProcessEvent, RenderProcessMessage, RenderProcessSenderInner,
+};
+use crate::prelude::PROCESS_MESSAGE_PONG;
+use bevy::prelude::Entity;
+use cef::{Browser, Frame, ListValue};
+
+/// Forwards the render process's answers to
+/// [`Browsers::ping`](crate::prelude::Browsers::ping).
+pub struct PongHandler {
+    webview: Entity,
+    sender: RenderProcessSenderInner,
+}
+
+impl PongHandler {
+    pub const fn new(webview: Entity, sender: RenderProcessSenderInner) -> Self {
+        Self { webview, sender }
+    }
+}
+
+impl ProcessMessageHandler for PongHandler {
+    fn process_name(&self) -> &'static str {
+        PROCESS_MESSAGE_PONG
+    }
+
+    fn handle_message(&self, _browser: &mut Browser, _frame: &mut Frame, _args: Option<ListValue>) {
+        let _ = self.sender.send_blocking(RenderProcessMessage {
+            webview: self.webview,
+            event: RenderProcessEvent::Pong,
+        });
+    }
+}
@@ -0,0 +1,127 @@
+//! CEF DevToolsMessageObserver — reports Chrome DevTools Protocol results and
+//! events of a webview.
+//!
+//! Mirrors the `FocusHandlerBuilder` pattern (`focus_handler.rs`).
+
+use async_channel::Sender;
+use bevy::prelude::Entity;
+use cef::rc::{Rc, RcImpl};
+use cef::{Browser, CefString, ImplDevToolsMessageObserver, WrapDevToolsMessageObserver, sys};
+use serde_json::Value;
+use std::os::raw::c_int;
+
+/// A Chrome DevTools Protocol message received from a webview's browser.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CdpMessage {
+    /// The answer to the command sent with `id`. `result` is the command's
+    /// result on success and the protocol error otherwise.
+    Result {
+        webview: Entity,
+        id: i32,
+        success: bool,
+        result: Value,
+    },
+    /// A domain event, e.g. `Network.requestWillBeSent`.
+    Event {
+        webview: Entity,
+        method: String,
+        params: Value,
+    },
+    /// Whether `BrowsersProxy::open_cdp` started observing the webview.
+    Opened { webview: Entity, success: bool },
+    /// The browser refused the command sent with `id`.
+    NotSent { webview: Entity, id: i32 },
+}
+
+pub type CdpSenderInner = Sender<CdpMessage>;
+
+/// ## Reference
+///
+/// - [`CefDevToolsMessageObserver Class Reference`](https://cef-builds.spotifycdn.com/docs/145/classCefDevToolsMessageObserver.html)
+pub struct DevToolsMessageObserverBuilder {
+    object: *mut RcImpl<sys::_cef_dev_tools_message_observer_t, Self>,
+    webview: Entity,
+    sender: CdpSenderInner,
+}
+
+impl DevToolsMessageObserverBuilder {
+    pub fn build(webview: Entity, sender: CdpSenderInner) -> cef::DevToolsMessageObserver {
+        cef::DevToolsMessageObserver::new(Self {
+            object: core::ptr::null_mut(),
+            webview,
+            sender,
+        })
+    }
+}
+
+impl Rc for DevToolsMessageObserverBuilder {
+    fn as_base(&self) -> &sys::cef_base_ref_counted_t {
+        unsafe {
+            let base = &*self.object;
+            core::mem::transmute(&base.cef_object)
+        }
+    }
+}
+
+impl Clone for DevToolsMessageObserverBuilder {
+    fn clone(&self) -> Self {
+        let object = unsafe {
+            let rc_impl = &mut *self.object;
+            rc_impl.interface.add_ref();
+            rc_impl
+        };
+        Self {
+            object,
+            webview: self.webview,
+            sender: self.sender.clone(),
+        }
+    }
+}
+
+impl WrapDevToolsMessageObserver for DevToolsMessageObserverBuilder {
+    fn wrap_rc(&mut self, object: *mut RcImpl<sys::_cef_dev_tools_message_observer_t, Self>) {
+        self.object = object;
+    }
+}
+
+impl ImplDevToolsMessageObserver for DevToolsMessageObserverBuilder {
+    fn on_dev_tools_method_result(
+        &self,
+        _browser: Option<&mut Browser>,
+        message_id: c_int,
+        success: c_int,
+        result: Option<&[u8]>,
+    ) {
+        let _ = self.sender.send_blocking(CdpMessage::Result {
+            webview: self.webview,
+            id: message_id,
+            success: success != 0,
+            result: parse_json(result),
+        });
+    }
+
+    fn on_dev_tools_event(
+        &self,
+        _browser: Option<&mut Browser>,
+        method: Option<&CefString>,
+        params: Option<&[u8]>,
+    ) {
+        let _ = self.sender.send_blocking(CdpMessage::Event {
+            webview: self.webview,
+            method: method.map(|m| m.to_string()).unwrap_or_default(),
+            params: parse_json(params),
+        });
+    }
+
+    #[inline]
+    fn get_raw(&self) -> *mut sys::_cef_dev_tools_message_observer_t {
+        self.object.cast()
+    }
+}
+
+/// CEF hands results and params over as UTF-8 JSON, not null-terminated.
+fn parse_json(bytes: Option<&[u8]>) -> Value {
+    bytes
+        .and_then(|bytes| serde_json::from_slice(bytes).ok())
+        .unwrap_or(Value::Null)
+}
@@ -1,6 +1,6 @@
 use async_channel::Sender;
 use bevy::log::{error, info, trace, warn};
-use bevy::prelude::Entity;
+use bevy::prelude::{Entity, Reflect};
 use bevy::window::SystemCursorIcon;
 use cef::rc::{ConvertParam, Rc, RcImpl};
 use cef::{
@@ -8,6 +8,7 @@ use cef::{
     LogSeverity, WrapDisplayHandler, sys,
 };
 use cef_dll_sys::{cef_cursor_type_t, cef_log_severity_t};
+use serde::{Deserialize, Serialize};
 use std::os::raw::c_int;
 
 /// Message sent from the CEF display handler when the browser's URL changes.
@@ -28,7 +29,51 @@ pub struct TitleChangedMessage {
 
 pub type TitleChangedSenderInner = Sender<TitleChangedMessage>;
 
-pub type SystemCursorIconSenderInner = Sender<SystemCursorIcon>;
+/// Severity of a page's console message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum WebviewConsoleLevel {
+    Verbose,
+    Info,
+    Warning,
+    Error,
+}
+
+/// Message sent from the CEF display handler when the page logs to its console.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConsoleMessage {
+    pub webview: Entity,
+    pub level: WebviewConsoleLevel,
+    pub message: String,
+    /// The script that logged the message.
+    pub source: String,
+    pub line: i32,
+}
+
+pub type ConsoleMessageSenderInner = Sender<ConsoleMessage>;
+
+/// A cursor requested by a page.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WebviewCursor {
+    System(SystemCursorIcon),
+    /// A CSS `cursor: url(..)` bitmap.
+    Custom {
+        /// Tightly packed RGBA8 rows.
+        rgba: Vec<u8>,
+        width: u32,
+        height: u32,
+        /// The click point, in pixels from the top-left of the bitmap.
+        hotspot: (u16, u16),
+    },
+}
+
+/// Message sent from the CEF display handler when the page changes the cursor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CursorChangedMessage {
+    pub webview: Entity,
+    pub cursor: WebviewCursor,
+}
+
+pub type CursorChangedSenderInner = Sender<CursorChangedMessage>;
 
 /// ## Reference
 ///
@@ -36,24 +81,27 @@ pub type SystemCursorIconSenderInner = Sender<SystemCursorIcon>;
 pub struct DisplayHandlerBuilder {
     object: *mut RcImpl<sys::cef_display_handler_t, Self>,
     webview: Entity,
-    cursor_icon: SystemCursorIconSenderInner,
+    cursor_changed: CursorChangedSenderInner,
     address_changed_sender: AddressChangedSenderInner,
     title_changed_sender: TitleChangedSenderInner,
+    console_sender: ConsoleMessageSenderInner,
 }
 
 impl DisplayHandlerBuilder {
     pub fn build(
         webview: Entity,
-        cursor_icon: SystemCursorIconSenderInner,
+        cursor_changed: CursorChangedSenderInner,
         address_changed_sender: AddressChangedSenderInner,
         title_changed_sender: TitleChangedSenderInner,
+        console_sender: ConsoleMessageSenderInner,
     ) -> cef::DisplayHandler {
         cef::DisplayHandler::new(Self {
             object: core::ptr::null_mut(),
             webview,
-            cursor_icon,
+            cursor_changed,
             address_changed_sender,
             title_changed_sender,
+            console_sender,
         })
     }
 }
@@ -77,9 +125,10 @@ impl Clone for DisplayHandlerBuilder {
         Self {
             object,
             webview: self.webview,
-            cursor_icon: self.cursor_icon.clone(),
+            cursor_changed: self.cursor_changed.clone(),
             address_changed_sender: self.address_changed_sender.clone(),
             title_changed_sender: self.title_changed_sender.clone(),
+            console_sender: self.console_sender.clone(),
         }
     }
 }
@@ -99,25 +148,34 @@ impl ImplDisplayHandler for DisplayHandlerBuilder {
         source: Option<&CefString>,
         line: c_int,
     ) -> c_int {
-        let message = format!(
-            "{}\nline:{line}\n{}",
-            source.map(|s| s.to_string()).unwrap_or_default(),
-            message.map(|m| m.to_string()).unwrap_or_default()
-        );
-        match level.into_raw() {
+        let source = source.map(|s| s.to_string()).unwrap_or_default();
+        let message = message.map(|m| m.to_string()).unwrap_or_default();
+        let log = format!("{source}\nline:{line}\n{message}");
+        let level = match level.into_raw() {
             cef_log_severity_t::LOGSEVERITY_ERROR => {
-                error!("{message}");
+                error!("{log}");
+                WebviewConsoleLevel::Error
             }
             cef_log_severity_t::LOGSEVERITY_WARNING => {
-                warn!("{message}");
+                warn!("{log}");
+                WebviewConsoleLevel::Warning
             }
             cef_log_severity_t::LOGSEVERITY_VERBOSE => {
-                trace!("{message}");
+                trace!("{log}");
+                WebviewConsoleLevel::Verbose
             }
             _ => {
-                info!("{message}");
+                info!("{log}");
+                WebviewConsoleLevel::Info
             }
-        }
+        };
+        let _ = self.console_sender.send_blocking(ConsoleMessage {
+            webview: self.webview,
+            level,
+            message,
+            source,
+            line,
+        });
         1
     }
 
@@ -155,11 +213,17 @@ impl ImplDisplayHandler for DisplayHandlerBuilder {
         #[cfg(target_os = "windows")] _cursor: *mut cef_dll_sys::HICON__,
         #[cfg(target_os = "linux")] _cursor: u64,
         type_: CursorType,
-        _: Option<&CursorInfo>,
+        custom_cursor_info: Option<&CursorInfo>,
     ) -> c_int {
-        let _ = self
-            .cursor_icon
-            .send_blocking(to_system_cursor_icon(type_.into_raw()));
+        let cursor = match (type_.into_raw(), custom_cursor_info) {
+            (cef_cursor_type_t::CT_CUSTOM, Some(info)) => custom_cursor(info),
+            _ => None,
+        }
+        .unwrap_or_else(|| WebviewCursor::System(to_system_cursor_icon(type_.into_raw())));
+        let _ = self.cursor_changed.send_blocking(CursorChangedMessage {
+            webview: self.webview,
+            cursor,
+        });
         1
     }
 
@@ -169,6 +233,36 @@ impl ImplDisplayHandler for DisplayHandlerBuilder {
     }
 }
 
+/// Copies a custom cursor bitmap out of CEF, which owns the BGRA buffer only
+/// for the duration of `on_cursor_change`.
+fn custom_cursor(info: &CursorInfo) -> Option<WebviewCursor> {
+    let width = u32::try_from(info.size.width).ok()?;
+    let height = u32::try_from(info.size.height).ok()?;
+    if info.buffer.is_null() || width == 0 || height == 0 {
+        return None;
+    }
+    // Safety: CEF guarantees a `width * height * 4` byte BGRA buffer while the
+    // callback runs; null and empty sizes are rejected above.
+    let bgra = unsafe {
+        std::slice::from_raw_parts(info.buffer as *const u8, (width * height * 4) as usize)
+    };
+    Some(WebviewCursor::Custom {
+        rgba: bgra_to_rgba(bgra),
+        width,
+        height,
+        hotspot: (
+            info.hotspot.x.clamp(0, u16::MAX as i32) as u16,
+            info.hotspot.y.clamp(0, u16::MAX as i32) as u16,
+        ),
+    })
+}
+
+fn bgra_to_rgba(bgra: &[u8]) -> Vec<u8> {
+    bgra.chunks_exact(4)
+        .flat_map(|px| [px[2], px[1], px[0], px[3]])
+        .collect()
+}
+
 pub fn to_system_cursor_icon(cursor_type: cef_dll_sys::cef_cursor_type_t) -> SystemCursorIcon {
     match cursor_type {
         cef_cursor_type_t::CT_POINTER => SystemCursorIcon::Default,
@@ -225,3 +319,16 @@ pub fn to_system_cursor_icon(cursor_type: cef_dll_sys::cef_cursor_type_t) -> Sys
         _ => SystemCursorIcon::Default,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custom_cursor_pixels_become_rgba() {
+        assert_eq!(
+            bgra_to_rgba(&[1, 2, 3, 4, 5, 6, 7, 8]),
+            vec![3, 2, 1, 4, 7, 6, 5, 8]
+        );
+    }
+}
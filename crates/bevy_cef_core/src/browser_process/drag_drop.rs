@@ -0,0 +1,97 @@
+//! Drag-and-drop plumbing for windowless browsers.
+//!
+//! An offscreen browser has no native window for the OS to drop onto, so both
+//! directions go through the embedder: file drops are replayed with the
+//! `DragTarget*` host calls, and page-initiated drags arrive through
+//! `CefRenderHandler::StartDragging` (see `renderer_handler.rs`).
+
+use crate::prelude::IntoString;
+use async_channel::Sender;
+use bevy::prelude::*;
+use cef::{CefStringList, DragData, ImplDragData, MouseEvent, drag_data_create};
+use cef_dll_sys::cef_drag_operations_mask_t;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// The contents of a drag started by a page.
+///
+/// Pages control these through `DataTransfer`: `setData("text/plain", ..)`
+/// ends up in [`text`](Self::text), `setData("text/html", ..)` in
+/// [`html`](Self::html). Custom MIME types are not exposed by CEF.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub struct WebviewDragData {
+    /// The URL of a dragged link or image.
+    pub link_url: Option<String>,
+    /// The title of a dragged link.
+    pub link_title: Option<String>,
+    /// Plain-text fragment.
+    pub text: Option<String>,
+    /// HTML fragment.
+    pub html: Option<String>,
+    /// Names of dragged files.
+    pub file_names: Vec<String>,
+}
+
+impl WebviewDragData {
+    pub(crate) fn from_cef(data: &DragData) -> Self {
+        let non_empty = |s: String| (!s.is_empty()).then_some(s);
+        let mut file_names = CefStringList::new();
+        data.file_names(Some(&mut file_names));
+        Self {
+            link_url: non_empty(data.link_url().into_string()),
+            link_title: non_empty(data.link_title().into_string()),
+            text: non_empty(data.fragment_text().into_string()),
+            html: non_empty(data.fragment_html().into_string()),
+            file_names: Vec::<String>::from(&file_names),
+        }
+    }
+}
+
+/// A page started dragging something out of `webview`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DragStartedMessage {
+    pub webview: Entity,
+    pub data: WebviewDragData,
+    /// Where the drag started, in DIP relative to the top-left of the webview.
+    pub position: Vec2,
+}
+
+pub type DragStartedSenderInner = Sender<DragStartedMessage>;
+
+/// Last pointer position of the page drag in flight, `None` when there is none.
+///
+/// Set by `start_dragging` and consumed by [`Browsers`](crate::prelude::Browsers)
+/// mouse forwarding; both run on the CEF UI thread on every platform.
+pub type SharedPageDrag = std::rc::Rc<std::cell::Cell<Option<Vec2>>>;
+
+/// Builds drag data carrying `files`, as if dragged from the OS file manager.
+pub(crate) fn file_drag_data(files: &[impl AsRef<Path>]) -> Option<DragData> {
+    let data = drag_data_create()?;
+    for file in files {
+        let path = file.as_ref().to_string_lossy();
+        data.add_file(Some(&path.as_ref().into()), None);
+    }
+    Some(data)
+}
+
+pub(crate) fn drag_mouse_event(position: Vec2) -> MouseEvent {
+    MouseEvent {
+        x: position.x as i32,
+        y: position.y as i32,
+        modifiers: 0,
+    }
+}
+
+/// The page decides the effect through `dropEffect`, so every operation is
+/// offered.
+pub(crate) fn allowed_drag_operations() -> cef::DragOperationsMask {
+    cef::DragOperationsMask::from(cef_drag_operations_mask_t::DRAG_OPERATION_EVERY)
+}
+
+pub(crate) fn drag_operation(dropped: bool) -> cef::DragOperationsMask {
+    cef::DragOperationsMask::from(if dropped {
+        cef_drag_operations_mask_t::DRAG_OPERATION_COPY
+    } else {
+        cef_drag_operations_mask_t::DRAG_OPERATION_NONE
+    })
+}
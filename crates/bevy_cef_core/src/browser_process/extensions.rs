@@ -1,9 +1,10 @@
 use bevy::platform::collections::HashMap;
 use serde::{Deserialize, Serialize};
 
-/// Custom JavaScript extensions to register via CEF's `register_extension`.
+/// Custom JavaScript extensions evaluated in webview frames.
 ///
-/// Extensions are global to all webviews and loaded before any page scripts run.
+/// Each extension is evaluated before any page scripts run, in the frames whose
+/// origin the webview's bridge policy admits (`cef://localhost` by default).
 /// Use existing `window.cef.emit()`, `window.cef.listen()`, and `window.cef.brp()`
 /// APIs within your extension code for Bevy communication.
 ///
@@ -33,7 +34,7 @@ impl CefExtensions {
     /// Adds a JavaScript extension.
     ///
     /// # Arguments
-    /// * `name` - Extension name (will be prefixed with `v8/` internally)
+    /// * `name` - Extension name, as referenced by bridge policies
     /// * `code` - JavaScript code defining the extension's API
     pub fn add(mut self, name: impl Into<String>, code: impl Into<String>) -> Self {
         self.0.insert(name.into(), code.into());
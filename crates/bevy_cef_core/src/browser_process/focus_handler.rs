@@ -0,0 +1,82 @@
+//! CEF FocusHandler — reports Tab traversal leaving the page.
+//!
+//! Mirrors the `LoadHandlerBuilder` pattern (`load_handler.rs`).
+
+use async_channel::Sender;
+use bevy::prelude::Entity;
+use cef::rc::{Rc, RcImpl};
+use cef::{Browser, ImplFocusHandler, WrapFocusHandler, sys};
+use std::os::raw::c_int;
+
+/// Focus is leaving the page: Tab past its last focusable element (`next`) or
+/// Shift-Tab past its first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TakeFocusMessage {
+    pub webview: Entity,
+    pub next: bool,
+}
+
+pub type TakeFocusSenderInner = Sender<TakeFocusMessage>;
+
+/// ## Reference
+///
+/// - [`CefFocusHandler Class Reference`](https://cef-builds.spotifycdn.com/docs/145/classCefFocusHandler.html)
+pub struct FocusHandlerBuilder {
+    object: *mut RcImpl<sys::_cef_focus_handler_t, Self>,
+    webview: Entity,
+    sender: TakeFocusSenderInner,
+}
+
+impl FocusHandlerBuilder {
+    pub fn build(webview: Entity, sender: TakeFocusSenderInner) -> cef::FocusHandler {
+        cef::FocusHandler::new(Self {
+            object: core::ptr::null_mut(),
+            webview,
+            sender,
+        })
+    }
+}
+
+impl Rc for FocusHandlerBuilder {
+    fn as_base(&self) -> &sys::cef_base_ref_counted_t {
+        unsafe {
+            let base = &*self.object;
+            core::mem::transmute(&base.cef_object)
+        }
+    }
+}
+
+impl Clone for FocusHandlerBuilder {
+    fn clone(&self) -> Self {
+        let object = unsafe {
+            let rc_impl = &mut *self.object;
+            rc_impl.interface.add_ref();
+            rc_impl
+        };
+        Self {
+            object,
+            webview: self.webview,
+            sender: self.sender.clone(),
+        }
+    }
+}
+
+impl WrapFocusHandler for FocusHandlerBuilder {
+    fn wrap_rc(&mut self, object: *mut RcImpl<sys::_cef_focus_handler_t, Self>) {
+        self.object = object;
+    }
+}
+
+impl ImplFocusHandler for FocusHandlerBuilder {
+    fn on_take_focus(&self, _browser: Option<&mut Browser>, next: c_int) {
+        let _ = self.sender.send_blocking(TakeFocusMessage {
+            webview: self.webview,
+            next: next != 0,
+        });
+    }
+
+    #[inline]
+    fn get_raw(&self) -> *mut sys::_cef_focus_handler_t {
+        self.object.cast()
+    }
+}
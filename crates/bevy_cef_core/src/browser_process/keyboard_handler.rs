@@ -0,0 +1,102 @@
+//! CEF KeyboardHandler — reports key presses the page did not consume.
+//!
+//! Mirrors the `LoadHandlerBuilder` pattern (`load_handler.rs`).
+
+use async_channel::Sender;
+use bevy::prelude::Entity;
+use cef::rc::{Rc, RcImpl};
+use cef::{Browser, ImplKeyboardHandler, KeyEvent, WrapKeyboardHandler, sys};
+use cef_dll_sys::cef_key_event_type_t;
+use std::os::raw::c_int;
+
+/// A key-down that the page let through: no handler called `preventDefault()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyUnhandledMessage {
+    pub webview: Entity,
+    /// The Windows virtual-key code, which CEF echoes back from the sent event.
+    pub windows_key_code: i32,
+}
+
+pub type KeyUnhandledSenderInner = Sender<KeyUnhandledMessage>;
+
+/// ## Reference
+///
+/// - [`CefKeyboardHandler Class Reference`](https://cef-builds.spotifycdn.com/docs/145/classCefKeyboardHandler.html)
+pub struct KeyboardHandlerBuilder {
+    object: *mut RcImpl<sys::_cef_keyboard_handler_t, Self>,
+    webview: Entity,
+    sender: KeyUnhandledSenderInner,
+}
+
+impl KeyboardHandlerBuilder {
+    pub fn build(webview: Entity, sender: KeyUnhandledSenderInner) -> cef::KeyboardHandler {
+        cef::KeyboardHandler::new(Self {
+            object: core::ptr::null_mut(),
+            webview,
+            sender,
+        })
+    }
+}
+
+impl Rc for KeyboardHandlerBuilder {
+    fn as_base(&self) -> &sys::cef_base_ref_counted_t {
+        unsafe {
+            let base = &*self.object;
+            core::mem::transmute(&base.cef_object)
+        }
+    }
+}
+
+impl Clone for KeyboardHandlerBuilder {
+    fn clone(&self) -> Self {
+        let object = unsafe {
+            let rc_impl = &mut *self.object;
+            rc_impl.interface.add_ref();
+            rc_impl
+        };
+        Self {
+            object,
+            webview: self.webview,
+            sender: self.sender.clone(),
+        }
+    }
+}
+
+impl WrapKeyboardHandler for KeyboardHandlerBuilder {
+    fn wrap_rc(&mut self, object: *mut RcImpl<sys::_cef_keyboard_handler_t, Self>) {
+        self.object = object;
+    }
+}
+
+impl ImplKeyboardHandler for KeyboardHandlerBuilder {
+    /// Called after the renderer has dispatched the event and nothing in the
+    /// page consumed it. `on_pre_key_event` keeps its default (deliver
+    /// everything), since Bevy already decides what reaches the page.
+    fn on_key_event(
+        &self,
+        _browser: Option<&mut Browser>,
+        event: Option<&KeyEvent>,
+        _os_event: sys::cef_event_handle_t,
+    ) -> c_int {
+        if let Some(event) = event {
+            let type_: cef_key_event_type_t = event.type_.into();
+            // Only the key-down is reported; the CHAR and KEYUP that follow it
+            // come back unhandled too and would otherwise report the key twice.
+            if matches!(
+                type_,
+                cef_key_event_type_t::KEYEVENT_RAWKEYDOWN | cef_key_event_type_t::KEYEVENT_KEYDOWN
+            ) {
+                let _ = self.sender.send_blocking(KeyUnhandledMessage {
+                    webview: self.webview,
+                    windows_key_code: event.windows_key_code,
+                });
+            }
+        }
+        0
+    }
+
+    #[inline]
+    fn get_raw(&self) -> *mut sys::_cef_keyboard_handler_t {
+        self.object.cast()
+    }
+}
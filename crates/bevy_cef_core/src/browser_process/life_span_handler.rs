@@ -0,0 +1,148 @@
+//! CEF LifeSpanHandler — reports when a webview's browser is created and
+//! when it is gone.
+//!
+//! Mirrors the `LoadHandlerBuilder` pattern (`load_handler.rs`).
+
+use async_channel::Sender;
+use bevy::prelude::{Entity, Reflect};
+use cef::rc::{Rc, RcImpl};
+use cef::{Browser, ImplBrowser, ImplLifeSpanHandler, WrapLifeSpanHandler, sys};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc as StdRc;
+
+/// Why a webview's browser could not be created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum BrowserCreationError {
+    /// The webview's host window does not exist.
+    MissingWindow,
+    /// CEF refused to create the browser.
+    BrowserNotCreated,
+    /// The browser was created without a host.
+    HostUnavailable,
+}
+
+impl fmt::Display for BrowserCreationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::MissingWindow => "the host window does not exist",
+            Self::BrowserNotCreated => "CEF failed to create the browser",
+            Self::HostUnavailable => "the browser has no host",
+        })
+    }
+}
+
+impl std::error::Error for BrowserCreationError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BrowserLifecycleEvent {
+    /// The browser exists and accepts commands.
+    Created,
+    /// Creation failed; no browser exists for the webview.
+    CreationFailed(BrowserCreationError),
+    /// The browser is about to be destroyed.
+    Closed,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BrowserLifecycleMessage {
+    pub webview: Entity,
+    pub event: BrowserLifecycleEvent,
+}
+
+pub type BrowserLifecycleSenderInner = Sender<BrowserLifecycleMessage>;
+
+/// Receives the browser once CEF has created it, for browsers CEF creates on
+/// its own such as DevTools.
+pub type SharedCreatedBrowser = StdRc<RefCell<Option<Browser>>>;
+
+/// ## Reference
+///
+/// - [`CefLifeSpanHandler Class Reference`](https://cef-builds.spotifycdn.com/docs/145/classCefLifeSpanHandler.html)
+pub struct LifeSpanHandlerBuilder {
+    object: *mut RcImpl<sys::_cef_life_span_handler_t, Self>,
+    webview: Entity,
+    sender: BrowserLifecycleSenderInner,
+    created: Option<SharedCreatedBrowser>,
+}
+
+impl LifeSpanHandlerBuilder {
+    pub fn build(
+        webview: Entity,
+        sender: BrowserLifecycleSenderInner,
+        created: Option<SharedCreatedBrowser>,
+    ) -> cef::LifeSpanHandler {
+        cef::LifeSpanHandler::new(Self {
+            object: core::ptr::null_mut(),
+            webview,
+            sender,
+            created,
+        })
+    }
+
+    fn send(&self, browser: Option<&mut Browser>, event: BrowserLifecycleEvent) {
+        // Popups share the client of the webview that opened them. Browsers CEF
+        // opens itself, such as DevTools, count as popups but have a client of
+        // their own.
+        if self.created.is_none() && browser.is_some_and(|browser| browser.is_popup() == 1) {
+            return;
+        }
+        let _ = self.sender.send_blocking(BrowserLifecycleMessage {
+            webview: self.webview,
+            event,
+        });
+    }
+}
+
+impl Rc for LifeSpanHandlerBuilder {
+    fn as_base(&self) -> &sys::cef_base_ref_counted_t {
+        unsafe {
+            let base = &*self.object;
+            core::mem::transmute(&base.cef_object)
+        }
+    }
+}
+
+impl Clone for LifeSpanHandlerBuilder {
+    fn clone(&self) -> Self {
+        let object = unsafe {
+            let rc_impl = &mut *self.object;
+            rc_impl.interface.add_ref();
+            rc_impl
+        };
+        Self {
+            object,
+            webview: self.webview,
+            sender: self.sender.clone(),
+            created: self.created.clone(),
+        }
+    }
+}
+
+impl WrapLifeSpanHandler for LifeSpanHandlerBuilder {
+    fn wrap_rc(&mut self, object: *mut RcImpl<sys::_cef_life_span_handler_t, Self>) {
+        self.object = object;
+    }
+}
+
+impl ImplLifeSpanHandler for LifeSpanHandlerBuilder {
+    fn on_after_created(&self, browser: Option<&mut Browser>) {
+        if let Some(created) = &self.created
+            && let Some(browser) = browser.as_deref()
+            && created.borrow().is_none()
+        {
+            created.replace(Some(browser.clone()));
+        }
+        self.send(browser, BrowserLifecycleEvent::Created);
+    }
+
+    fn on_before_close(&self, browser: Option<&mut Browser>) {
+        self.send(browser, BrowserLifecycleEvent::Closed);
+    }
+
+    #[inline]
+    fn get_raw(&self) -> *mut sys::_cef_life_span_handler_t {
+        self.object.cast()
+    }
+}
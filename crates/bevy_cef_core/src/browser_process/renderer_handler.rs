@@ -1,24 +1,25 @@
+use crate::browser_process::accessibility_handler::{
+    AccessibilityHandlerBuilder, AccessibilitySenderInner,
+};
+use crate::browser_process::drag_drop::{
+    DragStartedMessage, DragStartedSenderInner, SharedPageDrag, WebviewDragData,
+    allowed_drag_operations, drag_mouse_event,
+};
 use bevy::prelude::*;
 use cef::rc::{Rc, RcImpl};
 use cef::*;
 use cef_dll_sys::cef_paint_element_type_t;
-#[cfg(target_os = "linux")]
-use std::cell::Cell;
 use std::os::raw::c_int;
 
-/// A shared slot holding the latest texture for a single paint element type.
+/// Delivers `OnPaint` frames from the CEF UI thread to Bevy.
 ///
-/// Uses `Rc<Cell<Option<T>>>` instead of a channel because both producer (`on_paint`)
-/// and consumer (`send_render_textures`) run on the same thread (CEF UI thread =
-/// Bevy main thread under `external_message_pump` mode). This eliminates all
-/// synchronization overhead and naturally provides "latest frame wins" semantics.
+/// A channel rather than a shared slot, because with a multi-threaded message
+/// loop (always on Windows, optional on Linux) `on_paint` runs on CEF's own UI
+/// thread. Consumers keep only the latest frame per element with
+/// [`latest_textures`].
 ///
-/// Linux-only: the CPU `OnPaint` path. macOS uses the GPU IOSurface accelerated-paint
-/// path (no slots); Windows uses `TextureSender`.
-#[cfg(target_os = "linux")]
-pub type SharedTexture = std::rc::Rc<Cell<Option<RenderTextureMessage>>>;
-
-#[cfg(target_os = "windows")]
+/// macOS uses the GPU IOSurface accelerated-paint path instead.
+#[cfg(not(target_os = "macos"))]
 pub type TextureSender = async_channel::Sender<RenderTextureMessage>;
 
 /// The texture structure passed from [`CefRenderHandler::OnPaint`](https://cef-builds.spotifycdn.com/docs/106.1/classCefRenderHandler.html#a6547d5c9dd472e6b84706dc81d3f1741).
@@ -44,35 +45,54 @@ pub enum RenderPaintElementType {
     Popup,
 }
 
-#[cfg(not(target_os = "windows"))]
+#[cfg(target_os = "macos")]
 pub type SharedViewSize = std::rc::Rc<std::cell::Cell<Vec2>>;
-#[cfg(target_os = "windows")]
+#[cfg(not(target_os = "macos"))]
 pub type SharedViewSize = std::sync::Arc<std::sync::Mutex<Vec2>>;
 
 /// Thread-safe slot for a webview's current `device_scale_factor`.
 ///
-/// Mirrors `SharedViewSize`'s platform split: on non-Windows the CEF UI
-/// thread is the Bevy main thread, so no locking is needed; on Windows the
-/// CEF UI thread is separate, so an `Arc<Mutex<_>>` is required.
-#[cfg(not(target_os = "windows"))]
+/// Mirrors `SharedViewSize`'s platform split: on macOS the CEF UI thread is
+/// the Bevy main thread, so no locking is needed; on Windows and Linux the CEF
+/// UI thread may be separate, so an `Arc<Mutex<_>>` is required.
+#[cfg(target_os = "macos")]
 pub type SharedDpr = std::rc::Rc<std::cell::Cell<f32>>;
-#[cfg(target_os = "windows")]
+#[cfg(not(target_os = "macos"))]
 pub type SharedDpr = std::sync::Arc<std::sync::Mutex<f32>>;
 
+/// Drains `receiver`, keeping only the latest frame of each webview's view and
+/// popup. Frames a consumer never got to are superseded, not queued.
+#[cfg(not(target_os = "macos"))]
+pub fn latest_textures(
+    receiver: &async_channel::Receiver<RenderTextureMessage>,
+) -> Vec<RenderTextureMessage> {
+    let mut latest: Vec<RenderTextureMessage> = Vec::new();
+    while let Ok(texture) = receiver.try_recv() {
+        match latest
+            .iter_mut()
+            .find(|t| t.webview == texture.webview && t.ty == texture.ty)
+        {
+            Some(slot) => *slot = texture,
+            None => latest.push(texture),
+        }
+    }
+    latest
+}
+
 /// ## Reference
 ///
 /// - [`CefRenderHandler Class Reference`](https://cef-builds.spotifycdn.com/docs/106.1/classCefRenderHandler.html)
 pub struct RenderHandlerBuilder {
     object: *mut RcImpl<sys::cef_render_handler_t, Self>,
     webview: Entity,
-    #[cfg(target_os = "linux")]
-    view_slot: SharedTexture,
-    #[cfg(target_os = "linux")]
-    popup_slot: SharedTexture,
-    #[cfg(target_os = "windows")]
+    #[cfg(not(target_os = "macos"))]
     texture_sender: TextureSender,
     size: SharedViewSize,
     dpr: SharedDpr,
+    page_drag: SharedPageDrag,
+    drag_started: DragStartedSenderInner,
+    /// Attached only for webviews whose accessibility tree is bridged.
+    accessibility: Option<AccessibilityHandler>,
     /// Latest retained IOSurface for this webview's main view (Approach 2).
     ///
     /// `on_accelerated_paint` does no GPU work here — it only retains the latest
@@ -90,40 +110,32 @@ impl RenderHandlerBuilder {
         size: SharedViewSize,
         dpr: SharedDpr,
         latest_iosurface: crate::browser_process::accelerated_paint::SharedRetainedIoSurface,
+        page_drag: SharedPageDrag,
+        drag_started: DragStartedSenderInner,
+        accessibility: Option<AccessibilitySenderInner>,
     ) -> RenderHandler {
         RenderHandler::new(Self {
             object: std::ptr::null_mut(),
             webview,
             size,
             dpr,
+            page_drag,
+            drag_started,
+            accessibility: accessibility
+                .map(|sender| AccessibilityHandlerBuilder::build(webview, sender)),
             latest_iosurface,
         })
     }
 
-    #[cfg(target_os = "linux")]
-    pub fn build(
-        webview: Entity,
-        view_slot: SharedTexture,
-        popup_slot: SharedTexture,
-        size: SharedViewSize,
-        dpr: SharedDpr,
-    ) -> RenderHandler {
-        RenderHandler::new(Self {
-            object: std::ptr::null_mut(),
-            webview,
-            view_slot,
-            popup_slot,
-            size,
-            dpr,
-        })
-    }
-
-    #[cfg(target_os = "windows")]
+    #[cfg(not(target_os = "macos"))]
     pub fn build(
         webview: Entity,
         texture_sender: TextureSender,
         size: SharedViewSize,
         dpr: SharedDpr,
+        page_drag: SharedPageDrag,
+        drag_started: DragStartedSenderInner,
+        accessibility: Option<AccessibilitySenderInner>,
     ) -> RenderHandler {
         RenderHandler::new(Self {
             object: std::ptr::null_mut(),
@@ -131,6 +143,10 @@ impl RenderHandlerBuilder {
             texture_sender,
             size,
             dpr,
+            page_drag,
+            drag_started,
+            accessibility: accessibility
+                .map(|sender| AccessibilityHandlerBuilder::build(webview, sender)),
         })
     }
 }
@@ -160,14 +176,13 @@ impl Clone for RenderHandlerBuilder {
         Self {
             object,
             webview: self.webview,
-            #[cfg(target_os = "linux")]
-            view_slot: self.view_slot.clone(),
-            #[cfg(target_os = "linux")]
-            popup_slot: self.popup_slot.clone(),
-            #[cfg(target_os = "windows")]
+            #[cfg(not(target_os = "macos"))]
             texture_sender: self.texture_sender.clone(),
             size: self.size.clone(),
             dpr: self.dpr.clone(),
+            page_drag: self.page_drag.clone(),
+            drag_started: self.drag_started.clone(),
+            accessibility: self.accessibility.clone(),
             #[cfg(target_os = "macos")]
             latest_iosurface: self.latest_iosurface.clone(),
         }
@@ -177,9 +192,9 @@ impl Clone for RenderHandlerBuilder {
 impl ImplRenderHandler for RenderHandlerBuilder {
     fn view_rect(&self, _browser: Option<&mut Browser>, rect: Option<&mut cef::Rect>) {
         if let Some(rect) = rect {
-            #[cfg(not(target_os = "windows"))]
+            #[cfg(target_os = "macos")]
             let size = self.size.get();
-            #[cfg(target_os = "windows")]
+            #[cfg(not(target_os = "macos"))]
             let size = *self.size.lock().unwrap();
             rect.width = size.x as _;
             rect.height = size.y as _;
@@ -193,9 +208,9 @@ impl ImplRenderHandler for RenderHandlerBuilder {
     ) -> c_int {
         let Some(info) = screen_info else { return 0 };
 
-        #[cfg(not(target_os = "windows"))]
+        #[cfg(target_os = "macos")]
         let dpr = self.dpr.get();
-        #[cfg(target_os = "windows")]
+        #[cfg(not(target_os = "macos"))]
         let dpr = *self.dpr.lock().unwrap();
 
         info.device_scale_factor = dpr;
@@ -238,19 +253,7 @@ impl ImplRenderHandler for RenderHandlerBuilder {
             },
         };
 
-        #[cfg(not(target_os = "windows"))]
-        {
-            let slot = match ty {
-                RenderPaintElementType::Popup => &self.popup_slot,
-                RenderPaintElementType::View => &self.view_slot,
-            };
-            slot.set(Some(texture));
-        }
-
-        #[cfg(target_os = "windows")]
-        {
-            let _ = self.texture_sender.send_blocking(texture);
-        }
+        let _ = self.texture_sender.send_blocking(texture);
     }
 
     #[cfg(target_os = "macos")]
@@ -313,8 +316,76 @@ impl ImplRenderHandler for RenderHandlerBuilder {
         *self.latest_iosurface.borrow_mut() = Some(retained);
     }
 
+    fn accessibility_handler(&self) -> Option<AccessibilityHandler> {
+        self.accessibility.clone()
+    }
+
+    fn start_dragging(
+        &self,
+        browser: Option<&mut Browser>,
+        drag_data: Option<&mut DragData>,
+        _allowed_ops: DragOperationsMask,
+        x: c_int,
+        y: c_int,
+    ) -> c_int {
+        let (Some(browser), Some(drag_data)) = (browser, drag_data) else {
+            return 0;
+        };
+        let Some(host) = browser.host() else {
+            return 0;
+        };
+        let position = Vec2::new(x as f32, y as f32);
+        let _ = self.drag_started.send_blocking(DragStartedMessage {
+            webview: self.webview,
+            data: WebviewDragData::from_cef(drag_data),
+            position,
+        });
+        // The page is its own first drop target, so drag-and-drop within the
+        // page keeps working; `Browsers` routes mouse input to the drag until
+        // the button is released.
+        host.drag_target_drag_enter(
+            Some(drag_data),
+            Some(&drag_mouse_event(position)),
+            allowed_drag_operations(),
+        );
+        self.page_drag.set(Some(position));
+        1
+    }
+
     #[inline]
     fn get_raw(&self) -> *mut sys::_cef_render_handler_t {
         self.object.cast()
     }
 }
+
+#[cfg(all(test, not(target_os = "macos")))]
+mod tests {
+    use super::*;
+
+    fn frame(webview: u32, ty: RenderPaintElementType, width: u32) -> RenderTextureMessage {
+        RenderTextureMessage {
+            webview: Entity::from_raw_u32(webview).unwrap(),
+            ty,
+            width,
+            height: 1,
+            buffer: vec![0; width as usize * 4],
+        }
+    }
+
+    #[test]
+    fn latest_textures_keeps_the_last_frame_per_element() {
+        let (tx, rx) = async_channel::unbounded();
+        for texture in [
+            frame(1, RenderPaintElementType::View, 1),
+            frame(1, RenderPaintElementType::Popup, 2),
+            frame(2, RenderPaintElementType::View, 3),
+            frame(1, RenderPaintElementType::View, 4),
+        ] {
+            tx.send_blocking(texture).unwrap();
+        }
+
+        let widths: Vec<u32> = latest_textures(&rx).iter().map(|t| t.width).collect();
+        assert_eq!(widths, vec![4, 2, 3]);
+        assert!(latest_textures(&rx).is_empty());
+    }
+}
@@ -0,0 +1,136 @@
+//! CEF RequestHandler — reports render-process terminations, and the
+//! ping/pong replies used to watch for unresponsive pages.
+//!
+//! Mirrors the `LoadHandlerBuilder` pattern (`load_handler.rs`).
+
+use async_channel::Sender;
+use bevy::prelude::{Entity, Reflect};
+use cef::rc::{Rc, RcImpl};
+use cef::{Browser, CefString, ImplRequestHandler, TerminationStatus, WrapRequestHandler, sys};
+use cef_dll_sys::cef_termination_status_t;
+use serde::{Deserialize, Serialize};
+use std::os::raw::c_int;
+
+/// Why a webview's render process went away.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum WebviewTermination {
+    /// Non-zero exit status.
+    Abnormal,
+    /// Killed, e.g. by the OS or a task manager.
+    Killed,
+    /// Segmentation fault or similar.
+    Crashed,
+    /// Out of memory.
+    OutOfMemory,
+    /// The process never started.
+    LaunchFailed,
+    /// A code integrity check failed (Windows).
+    IntegrityFailure,
+}
+
+impl WebviewTermination {
+    fn from_cef(status: TerminationStatus) -> Self {
+        match status.into_raw() {
+            cef_termination_status_t::TS_PROCESS_WAS_KILLED => Self::Killed,
+            cef_termination_status_t::TS_PROCESS_CRASHED => Self::Crashed,
+            cef_termination_status_t::TS_PROCESS_OOM => Self::OutOfMemory,
+            cef_termination_status_t::TS_LAUNCH_FAILED => Self::LaunchFailed,
+            cef_termination_status_t::TS_INTEGRITY_FAILURE => Self::IntegrityFailure,
+            _ => Self::Abnormal,
+        }
+    }
+}
+
+/// News about the render process behind a webview.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RenderProcessEvent {
+    Terminated {
+        status: WebviewTermination,
+        error_code: i32,
+        error: String,
+    },
+    /// The page's main thread answered a ping from
+    /// [`Browsers::ping`](crate::prelude::Browsers::ping).
+    Pong,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderProcessMessage {
+    pub webview: Entity,
+    pub event: RenderProcessEvent,
+}
+
+pub type RenderProcessSenderInner = Sender<RenderProcessMessage>;
+
+/// ## Reference
+///
+/// - [`CefRequestHandler Class Reference`](https://cef-builds.spotifycdn.com/docs/145/classCefRequestHandler.html)
+pub struct RequestHandlerBuilder {
+    object: *mut RcImpl<sys::_cef_request_handler_t, Self>,
+    webview: Entity,
+    sender: RenderProcessSenderInner,
+}
+
+impl RequestHandlerBuilder {
+    pub fn build(webview: Entity, sender: RenderProcessSenderInner) -> cef::RequestHandler {
+        cef::RequestHandler::new(Self {
+            object: core::ptr::null_mut(),
+            webview,
+            sender,
+        })
+    }
+}
+
+impl Rc for RequestHandlerBuilder {
+    fn as_base(&self) -> &sys::cef_base_ref_counted_t {
+        unsafe {
+            let base = &*self.object;
+            core::mem::transmute(&base.cef_object)
+        }
+    }
+}
+
+impl Clone for RequestHandlerBuilder {
+    fn clone(&self) -> Self {
+        let object = unsafe {
+            let rc_impl = &mut *self.object;
+            rc_impl.interface.add_ref();
+            rc_impl
+        };
+        Self {
+            object,
+            webview: self.webview,
+            sender: self.sender.clone(),
+        }
+    }
+}
+
+impl WrapRequestHandler for RequestHandlerBuilder {
+    fn wrap_rc(&mut self, object: *mut RcImpl<sys::_cef_request_handler_t, Self>) {
+        self.object = object;
+    }
+}
+
+impl ImplRequestHandler for RequestHandlerBuilder {
+    fn on_render_process_terminated(
+        &self,
+        _browser: Option<&mut Browser>,
+        status: TerminationStatus,
+        error_code: c_int,
+        error_string: Option<&CefString>,
+    ) {
+        let _ = self.sender.send_blocking(RenderProcessMessage {
+            webview: self.webview,
+            event: RenderProcessEvent::Terminated {
+                status: WebviewTermination::from_cef(status),
+                error_code,
+                error: error_string.map(|e| e.to_string()).unwrap_or_default(),
+            },
+        });
+    }
+
+    #[inline]
+    fn get_raw(&self) -> *mut sys::_cef_request_handler_t {
+        self.object.cast()
+    }
+}
@@ -0,0 +1,48 @@
+//! The channels a browser's CEF handlers use to report back to Bevy.
+
+use crate::browser_process::accessibility_handler::AccessibilitySenderInner;
+use crate::browser_process::audio_handler::AudioStreamSenderInner;
+use crate::browser_process::client_handler::{
+    ControlMessageSenderInner, IpcEventRaw, WebviewBrpSenderInner,
+};
+use crate::browser_process::display_handler::{
+    AddressChangedSenderInner, ConsoleMessageSenderInner, CursorChangedSenderInner,
+    TitleChangedSenderInner,
+};
+use crate::browser_process::drag_drop::DragStartedSenderInner;
+use crate::browser_process::drag_handler::DraggableRegionSenderInner;
+use crate::browser_process::focus_handler::TakeFocusSenderInner;
+use crate::browser_process::keyboard_handler::KeyUnhandledSenderInner;
+use crate::browser_process::life_span_handler::BrowserLifecycleSenderInner;
+use crate::browser_process::load_handler::LoadHandlerSenderInner;
+use crate::browser_process::request_handler::RenderProcessSenderInner;
+use async_channel::Sender;
+
+/// Every sender handed to a new browser's handlers.
+///
+/// One set is shared by all webviews; each handler tags what it sends with its
+/// webview entity. Optional senders also decide whether the matching handler
+/// is attached at all.
+#[derive(Clone)]
+pub struct WebviewSenders {
+    pub ipc_event: Sender<IpcEventRaw>,
+    pub brp: WebviewBrpSenderInner,
+    pub cursor_changed: CursorChangedSenderInner,
+    pub drag_regions: DraggableRegionSenderInner,
+    pub load_handler: LoadHandlerSenderInner,
+    pub address_changed: AddressChangedSenderInner,
+    pub title_changed: TitleChangedSenderInner,
+    pub console: ConsoleMessageSenderInner,
+    pub control: ControlMessageSenderInner,
+    pub key_unhandled: KeyUnhandledSenderInner,
+    pub take_focus: TakeFocusSenderInner,
+    pub drag_started: DragStartedSenderInner,
+    pub render_process: RenderProcessSenderInner,
+    pub lifecycle: BrowserLifecycleSenderInner,
+    /// Set only for webviews whose audio is captured instead of played on the
+    /// OS device.
+    pub audio: Option<AudioStreamSenderInner>,
+    /// Set only while page accessibility is bridged; the browser then has
+    /// renderer accessibility enabled.
+    pub accessibility: Option<AccessibilitySenderInner>,
+}
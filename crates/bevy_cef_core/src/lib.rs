@@ -3,17 +3,20 @@ mod browser_process;
 #[cfg(target_os = "macos")]
 mod debug;
 
+pub mod bridge;
 pub mod custom_scheme;
 mod macros;
 mod render_process;
+pub mod user_script;
 mod util;
 
 pub mod prelude {
+    pub use crate::bridge::BridgePolicyPayload;
     #[cfg(all(feature = "browser", target_os = "macos"))]
     pub use crate::browser_process::accelerated_paint::{RetainedIoSurface, WebviewGpuSurface};
-    #[cfg(all(feature = "browser", target_os = "windows"))]
+    #[cfg(all(feature = "browser", any(target_os = "windows", target_os = "linux")))]
     pub use crate::browser_process::cef_command::{BrowsersProxy, CefCommand};
-    #[cfg(all(feature = "browser", target_os = "windows"))]
+    #[cfg(all(feature = "browser", any(target_os = "windows", target_os = "linux")))]
     pub use crate::browser_process::cef_thread::{drain_commands, init_cef_browsers};
     #[cfg(feature = "browser")]
     pub use crate::browser_process::display_handler::{
@@ -33,6 +36,7 @@ pub mod prelude {
     pub use crate::render_process::app::*;
     pub use crate::render_process::execute_render_process;
     pub use crate::render_process::render_process_handler::*;
+    pub use crate::user_script::{UserScriptPayload, UserScriptRunAt};
     pub use crate::util::*;
     pub use cef::DraggableRegion;
     pub use cef::Rect;
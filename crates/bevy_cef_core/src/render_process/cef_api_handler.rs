@@ -1,4 +1,7 @@
-use crate::prelude::{BRP_PROMISES, LISTEN_EVENTS, PROCESS_MESSAGE_BRP, PROCESS_MESSAGE_JS_EMIT};
+use crate::prelude::{
+    BRP_PROMISES, LISTEN_EVENTS, PROCESS_MESSAGE_BRP, PROCESS_MESSAGE_CONTROL,
+    PROCESS_MESSAGE_JS_EMIT,
+};
 use crate::util::{IntoString, v8_value_to_json};
 use cef::rc::{Rc, RcImpl};
 use cef::{
@@ -20,10 +23,11 @@ fn current_context_key() -> Option<(i32, String)> {
 /// Handles the `window.cef` JavaScript API functions.
 ///
 /// This handler is registered as a CEF extension during `on_web_kit_initialized`
-/// and provides three native functions:
+/// and provides four native functions:
 /// - `__cef_brp`: Async Bevy Remote Protocol requests
 /// - `__cef_emit`: Send events from JavaScript to Bevy
 /// - `__cef_listen`: Register callbacks for events from Bevy
+/// - `__cef_control`: Built-in commands for bevy_cef itself (e.g. `cef.gamepad.release()`)
 ///
 /// The Frame is obtained dynamically via `v8_context_get_current_context().frame()`
 /// since extensions are global and not bound to a specific context.
@@ -81,6 +85,7 @@ impl ImplV8Handler for CefApiHandler {
             "__cef_brp" => self.execute_brp(arguments, ret),
             "__cef_emit" => self.execute_emit(arguments),
             "__cef_listen" => self.execute_listen(arguments),
+            "__cef_control" => self.execute_control(arguments),
             _ => 0,
         }
     }
@@ -164,6 +169,34 @@ impl CefApiHandler {
         1
     }
 
+    fn execute_control(&self, arguments: Option<&[Option<V8Value>]>) -> c_int {
+        let Some(context) = v8_context_get_current_context() else {
+            return 0;
+        };
+        let Some(frame) = context.frame() else {
+            return 0;
+        };
+
+        if let Some(mut process) = process_message_create(Some(&PROCESS_MESSAGE_CONTROL.into()))
+            && let Some(arguments_list) = process.argument_list()
+            && let Some(arguments) = arguments
+            && let Some(Some(name)) = arguments.first()
+            && name.is_string().is_positive()
+        {
+            let payload = arguments
+                .get(1)
+                .and_then(|arg| v8_value_to_json(arg.as_ref()?))
+                .unwrap_or_default();
+            arguments_list.set_string(0, Some(&name.string_value().into_string().as_str().into()));
+            arguments_list.set_string(1, Some(&payload.to_string().as_str().into()));
+            frame.send_process_message(
+                ProcessId::from(cef_process_id_t::PID_BROWSER),
+                Some(&mut process),
+            );
+        }
+        1
+    }
+
     fn execute_listen(&self, arguments: Option<&[Option<V8Value>]>) -> c_int {
         let Some(key) = current_context_key() else {
             return 0;
@@ -1,17 +1,19 @@
+use crate::bridge::{BRIDGE_POLICY_KEY, BridgePolicyPayload, PROCESS_MESSAGE_BRIDGE_POLICY};
 use crate::macros::cef_error;
 use crate::prelude::{EXTENSIONS_SWITCH, IntoString};
 use crate::render_process::cef_api_handler::CefApiHandler;
+use crate::user_script::{PROCESS_MESSAGE_USER_SCRIPTS, USER_SCRIPTS_KEY, UserScriptPayload};
 use crate::util::v8_accessor::V8DefaultAccessorBuilder;
 use crate::util::v8_interceptor::V8DefaultInterceptorBuilder;
-use crate::util::{json_to_v8, read_switch_json};
+use crate::util::{json_to_v8, read_switch_json, url_origin};
 use bevy::platform::collections::HashMap;
 use bevy_remote::BrpResult;
 use cef::rc::{Rc, RcImpl};
 use cef::{
-    Browser, CefString, DictionaryValue, Frame, ImplBrowser, ImplDictionaryValue, ImplFrame,
-    ImplListValue, ImplProcessMessage, ImplRenderProcessHandler, ImplV8Context, ImplV8Exception,
-    ImplV8Value, ProcessId, ProcessMessage, V8Context, V8Handler, V8Value,
-    WrapRenderProcessHandler, register_extension, sys, v8_value_create_object,
+    Browser, DictionaryValue, Frame, ImplBrowser, ImplDictionaryValue, ImplFrame, ImplListValue,
+    ImplProcessMessage, ImplRenderProcessHandler, ImplV8Context, ImplV8Exception, ImplV8Value,
+    ProcessId, ProcessMessage, V8Context, V8Handler, V8Value, WrapRenderProcessHandler,
+    process_message_create, register_extension, sys, v8_value_create_object,
 };
 use std::collections::HashMap as StdHashMap;
 use std::os::raw::c_int;
@@ -19,15 +21,39 @@ use std::sync::Mutex;
 
 const CEF_API_EXTENSION_NAME: &str = "v8/bevy-cef-api";
 const CEF_API_EXTENSION_CODE: &str = r#"
-var cef;
-if (!cef) cef = {};
 (function() {
   native function __cef_brp();
   native function __cef_emit();
   native function __cef_listen();
+  native function __cef_control();
+  var cef = {};
+  // Configurable, so frames the bridge policy does not admit can lose it.
+  Object.defineProperty(window, 'cef', {
+    value: cef, writable: true, enumerable: true, configurable: true,
+  });
   cef.brp = __cef_brp;
   cef.emit = __cef_emit;
   cef.listen = __cef_listen;
+  cef.gamepad = {
+    release: function() { __cef_control('gamepad.release'); },
+  };
+  cef.keyboard = {
+    markHandled: function(e) {
+      __cef_control('keyboard.markHandled', typeof e === 'number' ? e : e.keyCode);
+    },
+  };
+  if (window.top === window) {
+    var scrollReport = 0;
+    window.addEventListener('scroll', function() {
+      // Pages the bridge policy does not admit lose `window.cef`, and their
+      // reports would be dropped anyway.
+      if (scrollReport || window.cef !== cef) return;
+      scrollReport = setTimeout(function() {
+        scrollReport = 0;
+        __cef_control('page.scroll', [window.scrollX, window.scrollY]);
+      }, 250);
+    }, { passive: true });
+  }
 })();
 "#;
 
@@ -39,12 +65,25 @@ pub(crate) static BRP_PROMISES: Mutex<HashMap<ContextKey, HashMap<String, V8Valu
 pub(crate) static LISTEN_EVENTS: Mutex<HashMap<ContextKey, HashMap<String, V8Value>>> =
     Mutex::new(HashMap::new());
 
-static INIT_SCRIPTS: Mutex<HashMap<c_int, String>> = Mutex::new(HashMap::new());
-pub const INIT_SCRIPT_KEY: &str = "init_script";
+/// Latest user-script set per browser id; replaced wholesale by
+/// [`PROCESS_MESSAGE_USER_SCRIPTS`].
+static USER_SCRIPTS: Mutex<HashMap<c_int, Vec<UserScriptPayload>>> = Mutex::new(HashMap::new());
+
+/// Latest bridge policy per browser id; replaced wholesale by
+/// [`PROCESS_MESSAGE_BRIDGE_POLICY`].
+static BRIDGE_POLICIES: Mutex<HashMap<c_int, BridgePolicyPayload>> = Mutex::new(HashMap::new());
+
+/// `CefExtensions` by name, evaluated per frame as its bridge policy allows.
+static EXTENSIONS: Mutex<Vec<(String, String)>> = Mutex::new(Vec::new());
 
 pub const PROCESS_MESSAGE_BRP: &str = "brp";
 pub const PROCESS_MESSAGE_HOST_EMIT: &str = "host-emit";
 pub const PROCESS_MESSAGE_JS_EMIT: &str = "js-emit";
+pub const PROCESS_MESSAGE_CONTROL: &str = "control";
+/// Sent by the host to check that the page's main thread is responsive.
+pub const PROCESS_MESSAGE_PING: &str = "ping";
+/// The render process's answer to [`PROCESS_MESSAGE_PING`].
+pub const PROCESS_MESSAGE_PONG: &str = "pong";
 
 pub struct RenderProcessHandlerBuilder {
     object: *mut RcImpl<sys::_cef_render_process_handler_t, Self>,
@@ -87,7 +126,7 @@ impl Clone for RenderProcessHandlerBuilder {
 impl ImplRenderProcessHandler for RenderProcessHandlerBuilder {
     fn on_web_kit_initialized(&self) {
         register_cef_api_extension();
-        register_extensions_from_command_line();
+        load_extensions_from_command_line();
     }
 
     fn on_browser_created(
@@ -96,12 +135,20 @@ impl ImplRenderProcessHandler for RenderProcessHandlerBuilder {
         extra: Option<&mut DictionaryValue>,
     ) {
         if let (Some(browser), Some(extra)) = (browser, extra) {
-            let script = extra.string(Some(&INIT_SCRIPT_KEY.into())).into_string();
-            if script.is_empty() {
-                return;
-            }
-            let id = browser.identifier();
-            INIT_SCRIPTS.lock().unwrap().insert(id, script);
+            let json = extra.string(Some(&USER_SCRIPTS_KEY.into())).into_string();
+            store_user_scripts(browser.identifier(), &json);
+            let json = extra.string(Some(&BRIDGE_POLICY_KEY.into())).into_string();
+            store_bridge_policy(browser.identifier(), &json);
+        }
+    }
+
+    fn on_browser_destroyed(&self, browser: Option<&mut Browser>) {
+        if let Some(browser) = browser {
+            USER_SCRIPTS.lock().unwrap().remove(&browser.identifier());
+            BRIDGE_POLICIES
+                .lock()
+                .unwrap()
+                .remove(&browser.identifier());
         }
     }
 
@@ -115,7 +162,8 @@ impl ImplRenderProcessHandler for RenderProcessHandlerBuilder {
             && let Some(frame) = frame
             && let Some(browser) = browser
         {
-            inject_initialize_scripts(browser, context, frame);
+            apply_bridge_policy(browser, context, frame);
+            inject_user_scripts(browser, context, frame);
         }
     }
 
@@ -139,12 +187,46 @@ impl ImplRenderProcessHandler for RenderProcessHandlerBuilder {
         _: ProcessId,
         message: Option<&mut ProcessMessage>,
     ) -> c_int {
-        if let Some(message) = message
-            && let Some(frame) = frame
+        let Some(message) = message else {
+            return 1;
+        };
+        let name = message.name().into_string();
+        if name == PROCESS_MESSAGE_USER_SCRIPTS {
+            // Needs no V8 context: the set is stored and applied from the next
+            // `on_context_created`, so it may arrive mid-navigation.
+            if let Some(browser) = browser
+                && let Some(args) = message.argument_list()
+            {
+                store_user_scripts(browser.identifier(), &args.string(0).into_string());
+            }
+            return 1;
+        }
+        if name == PROCESS_MESSAGE_BRIDGE_POLICY {
+            if let Some(browser) = browser
+                && let Some(args) = message.argument_list()
+            {
+                store_bridge_policy(browser.identifier(), &args.string(0).into_string());
+            }
+            return 1;
+        }
+        if name == PROCESS_MESSAGE_PING {
+            // Answered from the renderer main thread, so a page stuck in a
+            // script never replies.
+            if let Some(frame) = frame
+                && let Some(mut pong) = process_message_create(Some(&PROCESS_MESSAGE_PONG.into()))
+            {
+                frame.send_process_message(
+                    ProcessId::from(cef_dll_sys::cef_process_id_t::PID_BROWSER),
+                    Some(&mut pong),
+                );
+            }
+            return 1;
+        }
+        if let Some(frame) = frame
             && let Some(browser) = browser
             && let Some(ctx) = frame.v8_context()
         {
-            match message.name().into_string().as_str() {
+            match name.as_str() {
                 PROCESS_MESSAGE_BRP => {
                     handle_brp_message(message, browser, frame, ctx);
                 }
@@ -163,35 +245,126 @@ impl ImplRenderProcessHandler for RenderProcessHandlerBuilder {
     }
 }
 
-fn inject_initialize_scripts(browser: &mut Browser, context: &mut V8Context, frame: &mut Frame) {
-    let id = browser.identifier();
-    if let Some(script) = INIT_SCRIPTS.lock().ok().and_then(|scripts| {
-        let script = scripts.get(&id)?;
-        Some(CefString::from(script.as_str()))
-    }) {
-        context.enter();
-        let mut retval: Option<V8Value> = None;
-        let mut exception: Option<cef::V8Exception> = None;
-        let result = context.eval(
-            Some(&script),
-            Some(&(&frame.url()).into()),
-            0,
-            Some(&mut retval),
-            Some(&mut exception),
-        );
-        if result == 0 {
-            if let Some(ex) = exception {
-                cef_error!(
-                    "eval failed - message: {}, line: {}, column: {}",
-                    ex.message().into_string(),
-                    ex.line_number(),
-                    ex.start_column(),
-                );
-            } else {
-                cef_error!("eval failed with no exception details");
+fn store_user_scripts(browser_id: c_int, json: &str) {
+    let scripts = if json.is_empty() {
+        Vec::new()
+    } else {
+        match serde_json::from_str::<Vec<UserScriptPayload>>(json) {
+            Ok(scripts) => scripts,
+            Err(e) => {
+                cef_error!("failed to parse user scripts: {e}");
+                return;
             }
         }
-        context.exit();
+    };
+    let mut map = USER_SCRIPTS.lock().unwrap();
+    if scripts.is_empty() {
+        map.remove(&browser_id);
+    } else {
+        map.insert(browser_id, scripts);
+    }
+}
+
+/// Stores the policy of a browser. A browser without one (or with an
+/// unreadable one) gets [`BridgePolicyPayload::default`].
+fn store_bridge_policy(browser_id: c_int, json: &str) {
+    let policy = if json.is_empty() {
+        BridgePolicyPayload::default()
+    } else {
+        match serde_json::from_str::<BridgePolicyPayload>(json) {
+            Ok(policy) => policy,
+            Err(e) => {
+                cef_error!("failed to parse bridge policy: {e}");
+                BridgePolicyPayload::default()
+            }
+        }
+    };
+    BRIDGE_POLICIES.lock().unwrap().insert(browser_id, policy);
+}
+
+/// Removes `window.cef` from the frame if its origin may not use it, then
+/// evaluates the extensions the origin may use.
+fn apply_bridge_policy(browser: &mut Browser, context: &mut V8Context, frame: &mut Frame) {
+    let url = frame.url().into_string();
+    let origin = url_origin(&url);
+    let policy = BRIDGE_POLICIES
+        .lock()
+        .ok()
+        .and_then(|policies| policies.get(&browser.identifier()).cloned())
+        .unwrap_or_default();
+    let mut scripts = Vec::new();
+    if !policy.exposes_api(&origin) {
+        scripts.push("delete window.cef;".to_string());
+    }
+    if let Ok(extensions) = EXTENSIONS.lock() {
+        scripts.extend(
+            extensions
+                .iter()
+                .filter(|(name, _)| policy.exposes_extension(name, &origin))
+                .map(|(_, code)| code.clone()),
+        );
+    }
+    if scripts.is_empty() {
+        return;
+    }
+
+    context.enter();
+    for script in scripts {
+        eval_script(context, &script, &url);
+    }
+    context.exit();
+}
+
+fn inject_user_scripts(browser: &mut Browser, context: &mut V8Context, frame: &mut Frame) {
+    let url = frame.url().into_string();
+    let is_main = frame.is_main() != 0;
+    let scripts: Vec<String> = USER_SCRIPTS
+        .lock()
+        .ok()
+        .and_then(|scripts| {
+            let scripts = scripts.get(&browser.identifier())?;
+            Some(
+                scripts
+                    .iter()
+                    .filter(|s| s.applies_to(&url, is_main))
+                    .map(UserScriptPayload::wrapped_code)
+                    .collect(),
+            )
+        })
+        .unwrap_or_default();
+    if scripts.is_empty() {
+        return;
+    }
+
+    context.enter();
+    for script in scripts {
+        eval_script(context, &script, &url);
+    }
+    context.exit();
+}
+
+/// Evaluates `script` in the entered `context`, logging any exception.
+fn eval_script(context: &mut V8Context, script: &str, url: &str) {
+    let mut retval: Option<V8Value> = None;
+    let mut exception: Option<cef::V8Exception> = None;
+    let result = context.eval(
+        Some(&script.into()),
+        Some(&url.into()),
+        0,
+        Some(&mut retval),
+        Some(&mut exception),
+    );
+    if result == 0 {
+        if let Some(ex) = exception {
+            cef_error!(
+                "eval failed - message: {}, line: {}, column: {}",
+                ex.message().into_string(),
+                ex.line_number(),
+                ex.start_column(),
+            );
+        } else {
+            cef_error!("eval failed with no exception details");
+        }
     }
 }
 
@@ -280,16 +453,14 @@ fn handle_listen_message(
     }
 }
 
-fn register_extensions_from_command_line() {
+/// Reads the `CefExtensions` passed by the browser process. Unlike the
+/// `window.cef` API they are not registered as V8 extensions, which would load
+/// them into every frame, but evaluated by [`apply_bridge_policy`].
+fn load_extensions_from_command_line() {
     let Some(extensions) = read_switch_json::<StdHashMap<String, String>>(EXTENSIONS_SWITCH) else {
         return;
     };
-    for (name, code) in extensions {
-        let full_name = format!("v8/{}", name);
-        register_extension(
-            Some(&full_name.as_str().into()),
-            Some(&code.as_str().into()),
-            None,
-        );
-    }
+    let mut extensions: Vec<_> = extensions.into_iter().collect();
+    extensions.sort_by(|a, b| a.0.cmp(&b.0));
+    *EXTENSIONS.lock().unwrap() = extensions;
 }
@@ -0,0 +1,167 @@
+//! User scripts shared between the browser and render processes.
+//!
+//! The browser process resolves each webview's scripts into
+//! [`UserScriptPayload`]s and ships them to the render process twice: once in
+//! the browser's extra-info dictionary at creation (under [`USER_SCRIPTS_KEY`])
+//! and again through a [`PROCESS_MESSAGE_USER_SCRIPTS`] message whenever the
+//! set changes. The render process keeps the latest set per browser id and
+//! evaluates the matching scripts in `on_context_created`, so an update applies
+//! from the next document load onwards without recreating the browser.
+
+use serde::{Deserialize, Serialize};
+
+/// Extra-info dictionary key holding the JSON-encoded initial script set.
+pub const USER_SCRIPTS_KEY: &str = "user_scripts";
+
+/// Process message carrying a replacement script set to the render process.
+pub const PROCESS_MESSAGE_USER_SCRIPTS: &str = "user-scripts";
+
+/// When a user script is evaluated relative to the document's own scripts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "browser", derive(bevy::prelude::Reflect))]
+pub enum UserScriptRunAt {
+    /// Evaluated as soon as the frame's JavaScript context is created, before
+    /// any `<script>` in the page runs.
+    #[default]
+    DocumentStart,
+    /// Evaluated once the DOM has been parsed (`DOMContentLoaded`), or
+    /// immediately if the document is already past that point.
+    DocumentIdle,
+}
+
+/// A fully resolved user script as seen by the render process.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserScriptPayload {
+    /// The JavaScript source.
+    pub code: String,
+    /// URL match patterns; see [`url_matches_pattern`]. Empty matches every URL.
+    pub matches: Vec<String>,
+    /// Only inject into the main frame, skipping iframes.
+    pub main_frame_only: bool,
+    pub run_at: UserScriptRunAt,
+}
+
+impl UserScriptPayload {
+    /// Whether this script should be injected into a frame at `url`.
+    pub fn applies_to(&self, url: &str, is_main_frame: bool) -> bool {
+        if self.main_frame_only && !is_main_frame {
+            return false;
+        }
+        self.matches.is_empty() || self.matches.iter().any(|p| url_matches_pattern(p, url))
+    }
+
+    /// Returns the source to evaluate, deferring it to `DOMContentLoaded` for
+    /// [`UserScriptRunAt::DocumentIdle`].
+    pub fn wrapped_code(&self) -> String {
+        match self.run_at {
+            UserScriptRunAt::DocumentStart => self.code.clone(),
+            UserScriptRunAt::DocumentIdle => format!(
+                "(function(){{var run=function(){{\n{}\n}};\
+                 if(document.readyState==='loading'){{\
+                 document.addEventListener('DOMContentLoaded',run,{{once:true}});\
+                 }}else{{run();}}}})();",
+                self.code
+            ),
+        }
+    }
+}
+
+/// Matches `url` against a user-script match pattern.
+///
+/// `<all_urls>` matches everything. Any other pattern is a glob over the full
+/// URL in which `*` matches any run of characters (including none), e.g.
+/// `https://*.example.com/*` or `cef://localhost/menu/*`.
+pub fn url_matches_pattern(pattern: &str, url: &str) -> bool {
+    if pattern == "<all_urls>" {
+        return true;
+    }
+    glob_match(pattern.as_bytes(), url.as_bytes())
+}
+
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && pattern[p] == b'*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == text[t] {
+            p += 1;
+            t += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn script(matches: &[&str], main_frame_only: bool) -> UserScriptPayload {
+        UserScriptPayload {
+            code: String::new(),
+            matches: matches.iter().map(|m| m.to_string()).collect(),
+            main_frame_only,
+            run_at: UserScriptRunAt::DocumentStart,
+        }
+    }
+
+    #[test]
+    fn all_urls_matches_everything() {
+        assert!(url_matches_pattern("<all_urls>", "https://example.com/"));
+        assert!(url_matches_pattern(
+            "<all_urls>",
+            "cef://localhost/index.html"
+        ));
+    }
+
+    #[test]
+    fn glob_matches_subdomains_and_paths() {
+        let p = "https://*.example.com/*";
+        assert!(url_matches_pattern(p, "https://www.example.com/"));
+        assert!(url_matches_pattern(p, "https://a.b.example.com/x/y?q=1"));
+        assert!(!url_matches_pattern(p, "http://www.example.com/"));
+        assert!(!url_matches_pattern(p, "https://example.org/"));
+    }
+
+    #[test]
+    fn glob_without_wildcard_is_exact() {
+        assert!(url_matches_pattern(
+            "cef://localhost/menu.html",
+            "cef://localhost/menu.html"
+        ));
+        assert!(!url_matches_pattern(
+            "cef://localhost/menu.html",
+            "cef://localhost/menu.html#top"
+        ));
+    }
+
+    #[test]
+    fn empty_matches_applies_everywhere() {
+        assert!(script(&[], false).applies_to("https://example.com/", false));
+    }
+
+    #[test]
+    fn main_frame_only_skips_iframes() {
+        let s = script(&["<all_urls>"], true);
+        assert!(s.applies_to("https://example.com/", true));
+        assert!(!s.applies_to("https://example.com/", false));
+    }
+
+    #[test]
+    fn document_idle_defers_to_dom_content_loaded() {
+        let mut s = script(&[], false);
+        s.code = "init()".into();
+        assert_eq!(s.wrapped_code(), "init()");
+        s.run_at = UserScriptRunAt::DocumentIdle;
+        let wrapped = s.wrapped_code();
+        assert!(wrapped.contains("DOMContentLoaded"));
+        assert!(wrapped.contains("init()"));
+    }
+}
@@ -184,3 +184,47 @@ pub fn json_to_v8(v: serde_json::Value) -> Option<cef::V8Value> {
         }
     }
 }
+
+/// Returns the `scheme://host[:port]` origin of `url`, lowercased, or `"null"`
+/// for URLs without a host such as `about:blank`, `data:` and `file://`.
+pub fn url_origin(url: &str) -> String {
+    let Some((scheme, rest)) = url.split_once("://") else {
+        return "null".to_string();
+    };
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    if scheme.is_empty() || host.is_empty() {
+        return "null".to_string();
+    }
+    format!(
+        "{}://{}",
+        scheme.to_ascii_lowercase(),
+        host.to_ascii_lowercase()
+    )
+}
+
+/// Whether `origin` is one of `allowed`, where `*` admits every origin.
+pub fn origin_allowed(allowed: &[String], origin: &str) -> bool {
+    allowed.iter().any(|a| a == "*" || a == origin)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn url_origin_keeps_scheme_host_and_port() {
+        assert_eq!(
+            url_origin("cef://localhost/ui/index.html"),
+            "cef://localhost"
+        );
+        assert_eq!(
+            url_origin("HTTPS://user@Example.com:8443/a?b#c"),
+            "https://example.com:8443"
+        );
+        assert_eq!(url_origin("about:blank"), "null");
+        assert_eq!(url_origin("file:///tmp/page.html"), "null");
+    }
+}
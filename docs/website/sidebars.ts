@@ -46,6 +46,9 @@ const sidebars: SidebarsConfig = {
         'guides/preload-scripts',
         'guides/extensions',
         'guides/zoom-and-audio',
+        'guides/headless',
+        'guides/diagnostics',
+        'guides/accessibility',
       ],
     },
     'concepts',
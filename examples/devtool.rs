@@ -2,6 +2,7 @@
 //!
 //! ## Key Bindings
 //! - `Q`: Show DevTool
+//! - `W`: Show DevTool in the panel on the right
 //! - `E`: Close DevTool
 
 use bevy::input::common_conditions::input_just_pressed;
@@ -19,6 +20,7 @@ fn main() {
             Update,
             (
                 show_devtool.run_if(input_just_pressed(KeyCode::KeyQ)),
+                show_devtool_in_panel.run_if(input_just_pressed(KeyCode::KeyW)),
                 close_devtool.run_if(input_just_pressed(KeyCode::KeyE)),
             ),
         )
@@ -28,10 +30,13 @@ fn main() {
 #[derive(Component)]
 struct DebugWebview;
 
+#[derive(Component)]
+struct DevToolPanel;
+
 fn spawn_camera(mut commands: Commands) {
     commands.spawn((
         Camera3d::default(),
-        Transform::from_translation(Vec3::new(0., 0., 3.)).looking_at(Vec3::ZERO, Vec3::Y),
+        Transform::from_translation(Vec3::new(0., 0., 4.)).looking_at(Vec3::ZERO, Vec3::Y),
     ));
 }
 
@@ -47,10 +52,7 @@ fn spawn_webview(
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<WebviewExtendStandardMaterial>>,
 ) {
-    commands.spawn((
-        DebugWebview,
-        WebviewSource::new("https://github.com/not-elm/bevy_cef"),
-        Mesh3d(meshes.add(Plane3d::new(Vec3::Z, Vec2::ONE))),
+    let mut material = || {
         MeshMaterial3d(materials.add(WebviewExtendStandardMaterial {
             base: StandardMaterial {
                 unlit: true,
@@ -58,7 +60,21 @@ fn spawn_webview(
                 ..default()
             },
             ..default()
-        })),
+        }))
+    };
+    commands.spawn((
+        DebugWebview,
+        WebviewSource::new("https://github.com/not-elm/bevy_cef"),
+        Mesh3d(meshes.add(Plane3d::new(Vec3::Z, Vec2::ONE))),
+        material(),
+        Transform::from_xyz(-1.1, 0., 0.),
+    ));
+    commands.spawn((
+        DevToolPanel,
+        WebviewSource::inline("<body style=\"background: #222\"></body>"),
+        Mesh3d(meshes.add(Plane3d::new(Vec3::Z, Vec2::ONE))),
+        material(),
+        Transform::from_xyz(1.1, 0., 0.),
     ));
 }
 
@@ -68,6 +84,17 @@ fn show_devtool(mut commands: Commands, webviews: Query<Entity, With<DebugWebvie
     });
 }
 
+fn show_devtool_in_panel(
+    mut commands: Commands,
+    webviews: Query<Entity, With<DebugWebview>>,
+    panel: Query<Entity, With<DevToolPanel>>,
+) {
+    commands.trigger(RequestShowDevToolIn {
+        webview: webviews.single().unwrap(),
+        surface: panel.single().unwrap(),
+    });
+}
+
 fn close_devtool(mut commands: Commands, webviews: Query<Entity, With<DebugWebview>>) {
     commands.trigger(RequestCloseDevtool {
         webview: webviews.single().unwrap(),
@@ -1,7 +1,7 @@
-//! Example demonstrating custom JavaScript extensions via CEF's register_extension.
+//! Example demonstrating custom JavaScript extensions.
 //!
 //! This example shows how to create global JavaScript APIs that are available
-//! in all webviews before any page scripts run.
+//! in `cef://localhost` pages before any page scripts run.
 
 use bevy::prelude::*;
 use bevy_cef::prelude::*;
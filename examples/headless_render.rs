@@ -0,0 +1,103 @@
+//! Renders HTML into an image without any window, e.g. to bake thumbnails.
+//!
+//! There is no `WinitPlugin`, no `PrimaryWindow` and no GPU: the
+//! `ScheduleRunnerPlugin` drives the app, the webview's size and DPR come from
+//! its components, and the page is drawn into a `WebviewTextureTarget`. Once
+//! loaded, the next painted frame is saved as `thumbnail.png` and the app exits.
+//!
+//! Linux/Windows: CPU frames land in the image asset. On macOS the page texture
+//! only exists on the GPU, so there is nothing to save.
+//! Run with: `cargo run --example headless_render --features debug`
+
+use bevy::app::ScheduleRunnerPlugin;
+use bevy::prelude::*;
+use bevy::render::RenderPlugin;
+use bevy::render::settings::WgpuSettings;
+use bevy::window::ExitCondition;
+use bevy::winit::WinitPlugin;
+use bevy_cef::prelude::*;
+use std::time::Duration;
+
+const HTML: &str = r#"<!DOCTYPE html>
+<html>
+<body style="margin: 0; display: grid; place-items: center; height: 100vh;
+             background: linear-gradient(135deg, #1e3c72, #2a5298);">
+    <h1 style="color: white; font-family: sans-serif;">Baked by bevy_cef</h1>
+</body>
+</html>"#;
+
+#[derive(Resource)]
+struct Thumbnail {
+    image: Handle<Image>,
+    loaded: bool,
+}
+
+fn main() {
+    App::new()
+        .add_plugins((
+            DefaultPlugins
+                .set(WindowPlugin {
+                    primary_window: None,
+                    exit_condition: ExitCondition::DontExit,
+                    close_when_requested: false,
+                    ..default()
+                })
+                .set(RenderPlugin {
+                    render_creation: WgpuSettings {
+                        backends: None,
+                        ..default()
+                    }
+                    .into(),
+                    ..default()
+                })
+                .disable::<WinitPlugin>(),
+            ScheduleRunnerPlugin::run_loop(Duration::from_secs_f64(1.0 / 60.0)),
+            CefPlugin::default(),
+        ))
+        .add_systems(Startup, spawn_webview)
+        .add_systems(Update, save_thumbnail)
+        .run();
+}
+
+fn spawn_webview(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    let image = images.add(Image::default());
+    commands.insert_resource(Thumbnail {
+        image: image.clone(),
+        loaded: false,
+    });
+    commands
+        .spawn((
+            WebviewSource::inline(HTML),
+            WebviewSize(Vec2::new(640.0, 360.0)),
+            WebviewDpr(2.0),
+            WebviewTextureTarget(image),
+        ))
+        .observe(|_: On<LoadFinished>, mut thumbnail: ResMut<Thumbnail>| {
+            thumbnail.loaded = true;
+        });
+}
+
+fn save_thumbnail(
+    mut events: MessageReader<AssetEvent<Image>>,
+    mut exit: MessageWriter<AppExit>,
+    thumbnail: Res<Thumbnail>,
+    images: Res<Assets<Image>>,
+) {
+    let painted = events
+        .read()
+        .any(|event| event.is_modified(&thumbnail.image));
+    if !thumbnail.loaded || !painted {
+        return;
+    }
+    let Some(image) = images.get(&thumbnail.image) else {
+        return;
+    };
+    match image.clone().try_into_dynamic() {
+        Ok(frame) => match frame.to_rgba8().save("thumbnail.png") {
+            Ok(()) => info!("Saved thumbnail.png ({}x{})", image.width(), image.height()),
+            Err(e) => error!("Failed to save thumbnail.png: {e}"),
+        },
+        Err(e) => error!("Failed to convert the frame: {e}"),
+    }
+    exit.write(AppExit::Success);
+}
@@ -2,9 +2,9 @@
 //! sample its texture from a third-party `UiMaterial` — the pattern a terminal
 //! emulator uses to composite an inline webview in its own shader.
 //!
-//! macOS only: the headless texture path rides the GPU IOSurface pipeline.
-//! On Linux/Windows this compiles and runs but the node stays black — the
-//! headless path is not wired there yet.
+//! macOS injects the page texture on the GPU (IOSurface pipeline); Linux and
+//! Windows copy each CPU frame into the image. For an app with no window at
+//! all, see the `headless_render` example.
 //! Run with: `cargo run --example headless_texture --features debug`
 
 use bevy::asset::AssetId;
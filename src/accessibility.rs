@@ -0,0 +1,746 @@
+//! Page accessibility trees bridged into Bevy's AccessKit integration.
+//!
+//! [`WebviewAccessibilityPlugin`] is opt-in. While it is added, every new
+//! browser gets renderer accessibility enabled and an accessibility handler
+//! that forwards Chromium's tree and location changes. Each webview keeps the
+//! page's tree in `WebviewAxTree` and mirrors its nodes as child entities with
+//! an [`AccessibilityNode`], rooted under the webview entity's own node.
+//!
+//! Page nodes keep their bounds in page pixels; the webview's node carries the
+//! transform from page pixels to the window, so moving the webview or the
+//! camera only updates that one node. AccessKit actions on page nodes (focus,
+//! click, set value) are replayed on the element at the node's center.
+
+use crate::common::{HostWindow, WebviewDpr, WebviewSize, WebviewSource};
+use crate::focus::RequestFocus;
+use crate::navigation::LoadStarted;
+use crate::system_param::projection::WebviewProjection;
+use crate::webview::WebviewBrowsers;
+use async_channel::Receiver;
+use bevy::a11y::accesskit::{Action, ActionData, Affine, Node, Rect as AxBounds, Role};
+use bevy::a11y::{AccessibilityNode, AccessibilitySystems, ActionRequest};
+use bevy::platform::collections::{HashMap, HashSet};
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_cef_core::prelude::{AccessibilityChange, AccessibilityMessage, AccessibilitySenderInner};
+use serde::Deserialize;
+
+/// Focuses, clicks, or sets the value of a node's element, given its tag, its
+/// `id` if any, and its center as fractions of the viewport. An element with
+/// the `id` is used as is; otherwise the center is scrolled into view and the
+/// element hit there, or its nearest ancestor, must have the tag, so a covering
+/// or moved element is not acted on instead. Setting goes through the
+/// prototype's `value` setter so frameworks that track it see the change.
+const ACTION_JS: &str = r#"(function(fx,fy,tag,id,action,value){var e=id&&document.getElementById(id);if(e&&e.localName!==tag)return;if(!e){var x=fx*innerWidth,y=fy*innerHeight;if(fx<0||fx>1||fy<0||fy>1){var sx=scrollX,sy=scrollY;scrollBy(x-innerWidth/2,y-innerHeight/2);x-=scrollX-sx;y-=scrollY-sy;}e=document.elementFromPoint(x,y);while(e&&e.localName!==tag)e=e.parentElement;}if(!e)return;if(action==='click'){e.click();return;}e.focus();if(action!=='value')return;var d=Object.getOwnPropertyDescriptor(Object.getPrototypeOf(e),'value');if(d&&d.set)d.set.call(e,value);else if(e.isContentEditable)e.textContent=value;else return;e.dispatchEvent(new Event('input',{bubbles:true}));e.dispatchEvent(new Event('change',{bubbles:true}));})"#;
+
+/// Exposes page content to screen readers through Bevy's AccessKit adapter.
+///
+/// Must be added before webviews are spawned: renderer accessibility is
+/// enabled when a browser is created.
+///
+/// ```rust,no_run
+/// use bevy::prelude::*;
+/// use bevy_cef::prelude::*;
+///
+/// App::new().add_plugins((
+///     DefaultPlugins,
+///     CefPlugin::default(),
+///     WebviewAccessibilityPlugin,
+/// ));
+/// ```
+pub struct WebviewAccessibilityPlugin;
+
+impl Plugin for WebviewAccessibilityPlugin {
+    fn build(&self, app: &mut App) {
+        let (tx, rx) = async_channel::unbounded();
+        app.insert_resource(AccessibilitySender(tx))
+            .insert_resource(AccessibilityReceiver(rx))
+            .add_observer(track_accessibility_tree)
+            .add_observer(expect_new_tree_on_load)
+            .add_systems(
+                PreUpdate,
+                (receive_accessibility_changes, sync_page_nodes).chain(),
+            )
+            .add_systems(
+                PostUpdate,
+                update_webview_nodes
+                    .after(TransformSystems::Propagate)
+                    .after(bevy::ui::UiSystems::Layout)
+                    .before(AccessibilitySystems::Update),
+            );
+
+        app.add_systems(Update, route_accessibility_actions);
+    }
+}
+
+/// Handed to new browsers' accessibility handlers; its presence is what turns
+/// renderer accessibility on.
+#[derive(Resource)]
+pub(crate) struct AccessibilitySender(pub(crate) AccessibilitySenderInner);
+
+#[derive(Resource)]
+struct AccessibilityReceiver(Receiver<AccessibilityMessage>);
+
+/// The page's accessibility tree and the entities mirroring its nodes.
+#[derive(Component, Default)]
+pub(crate) struct WebviewAxTree {
+    tree: AxTree,
+    entities: HashMap<i32, Entity>,
+    root: Option<Entity>,
+    structure_changed: bool,
+    bounds_changed: bool,
+    /// The page-to-window transform last written to the webview's node.
+    transform: Option<[f64; 6]>,
+}
+
+/// A page node mirrored as an entity.
+#[derive(Component, Debug, Clone, Copy)]
+pub(crate) struct WebviewAxNode {
+    webview: Entity,
+    id: i32,
+}
+
+/// `OnAccessibilityTreeChange`: the updates of one tree, which is the main
+/// frame's or an iframe's.
+#[derive(Deserialize)]
+struct AxUpdatesAndEvents {
+    #[serde(default)]
+    ax_tree_id: String,
+    #[serde(default)]
+    updates: Vec<AxTreeUpdate>,
+}
+
+#[derive(Deserialize)]
+struct AxTreeUpdate {
+    #[serde(default)]
+    tree_data: Option<AxTreeData>,
+    #[serde(default)]
+    node_id_to_clear: i32,
+    #[serde(default)]
+    root_id: i32,
+    #[serde(default)]
+    nodes: Vec<AxNodeData>,
+}
+
+#[derive(Deserialize)]
+struct AxTreeData {
+    #[serde(default)]
+    parent_tree_id: Option<String>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+struct AxNodeData {
+    id: i32,
+    #[serde(default)]
+    role: String,
+    #[serde(default)]
+    state: Vec<String>,
+    #[serde(default)]
+    offset_container_id: Option<i32>,
+    #[serde(default)]
+    location: AxRect,
+    #[serde(default)]
+    child_ids: Vec<i32>,
+    #[serde(default)]
+    attributes: serde_json::Map<String, serde_json::Value>,
+}
+
+impl AxNodeData {
+    fn string(&self, key: &str) -> Option<&str> {
+        self.attributes
+            .get(key)
+            .and_then(serde_json::Value::as_str)
+            .filter(|s| !s.is_empty())
+    }
+
+    fn number(&self, key: &str) -> f32 {
+        self.attributes
+            .get(key)
+            .and_then(serde_json::Value::as_f64)
+            .unwrap_or_default() as f32
+    }
+
+    fn has_state(&self, state: &str) -> bool {
+        self.state.iter().any(|s| s == state)
+    }
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(default)]
+struct AxRect {
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+}
+
+/// `OnAccessibilityLocationChange`: a list of moved nodes, or the tree they
+/// belong to with the list under `updates`, depending on the CEF version.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AxLocationChanges {
+    List(Vec<AxLocationChange>),
+    Tree {
+        #[serde(default)]
+        ax_tree_id: String,
+        #[serde(default)]
+        updates: Vec<AxLocationChange>,
+    },
+}
+
+#[derive(Deserialize)]
+struct AxLocationChange {
+    #[serde(default)]
+    ax_tree_id: Option<String>,
+    id: i32,
+    new_location: AxRelativeBounds,
+}
+
+#[derive(Deserialize)]
+struct AxRelativeBounds {
+    #[serde(default)]
+    offset_container_id: Option<i32>,
+    #[serde(default)]
+    bounds: AxRect,
+}
+
+/// The main frame's accessibility tree. Iframes report separate trees, which
+/// are not bridged.
+#[derive(Default)]
+struct AxTree {
+    id: Option<String>,
+    root: Option<i32>,
+    nodes: HashMap<i32, AxNodeData>,
+    /// Set when a page load starts, after which the next new tree without a
+    /// parent is the new document's.
+    navigating: bool,
+}
+
+impl AxTree {
+    /// Applies a tree change, returning whether it was for this tree. A tree
+    /// without a parent is taken as the main frame's only while there is none
+    /// yet or a page load started since, so an iframe whose parent is not
+    /// reported does not replace the page.
+    fn apply(&mut self, change: AxUpdatesAndEvents) -> bool {
+        let is_main_frame = change.updates.iter().any(|update| {
+            update.tree_data.as_ref().is_some_and(|data| {
+                data.parent_tree_id
+                    .as_deref()
+                    .is_none_or(|parent| parent.is_empty())
+            })
+        });
+        if is_main_frame
+            && !change.ax_tree_id.is_empty()
+            && (self.id.is_none() || self.navigating)
+            && self.id.as_deref() != Some(change.ax_tree_id.as_str())
+        {
+            *self = Self {
+                id: Some(change.ax_tree_id.clone()),
+                ..default()
+            };
+        }
+        if self.id.as_deref() != Some(change.ax_tree_id.as_str()) {
+            return false;
+        }
+        for update in change.updates {
+            if update.node_id_to_clear != 0
+                && let Some(node) = self.nodes.get_mut(&update.node_id_to_clear)
+            {
+                node.child_ids.clear();
+            }
+            if update.root_id != 0 {
+                self.root = Some(update.root_id);
+            }
+            for node in update.nodes {
+                self.nodes.insert(node.id, node);
+            }
+        }
+        self.prune();
+        true
+    }
+
+    /// Moves nodes, returning whether any belonged to this tree.
+    fn relocate(&mut self, changes: AxLocationChanges) -> bool {
+        let (tree_id, changes) = match changes {
+            AxLocationChanges::List(changes) => (None, changes),
+            AxLocationChanges::Tree {
+                ax_tree_id,
+                updates,
+            } => (Some(ax_tree_id), updates),
+        };
+        let mut moved = false;
+        for change in changes {
+            let tree_id = change.ax_tree_id.as_ref().or(tree_id.as_ref());
+            if tree_id.is_some_and(|id| self.id.as_ref() != Some(id)) {
+                continue;
+            }
+            if let Some(node) = self.nodes.get_mut(&change.id) {
+                node.offset_container_id = change.new_location.offset_container_id;
+                node.location = change.new_location.bounds;
+                moved = true;
+            }
+        }
+        moved
+    }
+
+    /// Drops nodes no longer reachable from the root.
+    fn prune(&mut self) {
+        let mut reachable = HashSet::new();
+        let mut stack: Vec<i32> = self.root.into_iter().collect();
+        while let Some(id) = stack.pop() {
+            if let Some(node) = self.nodes.get(&id)
+                && reachable.insert(id)
+            {
+                stack.extend(&node.child_ids);
+            }
+        }
+        self.nodes.retain(|id, _| reachable.contains(id));
+    }
+
+    /// The bounds of `id` in page pixels: its location is relative to its
+    /// offset container (the root by default), scrolled by the container.
+    fn bounds(&self, id: i32) -> Option<Rect> {
+        let mut node = self.nodes.get(&id)?;
+        let min = Vec2::new(node.location.x, node.location.y);
+        let mut rect = Rect::from_corners(
+            min,
+            min + Vec2::new(node.location.width, node.location.height),
+        );
+        // The tree has no cycles once pruned, but offset containers are not
+        // tree edges; bound the walk anyway.
+        for _ in 0..self.nodes.len() {
+            let container_id = node
+                .offset_container_id
+                .filter(|id| *id > 0)
+                .or(self.root)
+                .filter(|container| *container != node.id);
+            let Some(container) = container_id.and_then(|id| self.nodes.get(&id)) else {
+                break;
+            };
+            let offset = Vec2::new(container.location.x, container.location.y)
+                - Vec2::new(container.number("scrollX"), container.number("scrollY"));
+            rect.min += offset;
+            rect.max += offset;
+            node = container;
+        }
+        Some(rect)
+    }
+
+    /// The AccessKit node of `id`, without children: Bevy adds those from the
+    /// entity hierarchy.
+    fn accesskit_node(&self, id: i32) -> Option<Node> {
+        let data = self.nodes.get(&id)?;
+        let role = accesskit_role(&data.role, data.has_state("multiline"));
+        let mut node = Node::new(role);
+        if let Some(name) = data.string("name") {
+            node.set_label(name);
+        }
+        if let Some(value) = data.string("value") {
+            node.set_value(value);
+        }
+        if let Some(description) = data.string("description") {
+            node.set_description(description);
+        }
+        if let Some(placeholder) = data.string("placeholder") {
+            node.set_placeholder(placeholder);
+        }
+        if let Some(bounds) = self.bounds(id) {
+            node.set_bounds(AxBounds::new(
+                bounds.min.x as f64,
+                bounds.min.y as f64,
+                bounds.max.x as f64,
+                bounds.max.y as f64,
+            ));
+        }
+        if data.has_state("focusable") {
+            node.add_action(Action::Focus);
+        }
+        if matches!(
+            role,
+            Role::Button
+                | Role::Link
+                | Role::CheckBox
+                | Role::RadioButton
+                | Role::MenuItem
+                | Role::Tab
+                | Role::ListBoxOption
+        ) {
+            node.add_action(Action::Click);
+        }
+        if data.has_state("editable") {
+            node.add_action(Action::SetValue);
+        }
+        Some(node)
+    }
+}
+
+/// Maps Chromium's role names to AccessKit roles; unknown roles become
+/// generic containers so their children are still reachable.
+fn accesskit_role(role: &str, multiline: bool) -> Role {
+    match role {
+        "button" | "toggleButton" | "popUpButton" => Role::Button,
+        "link" => Role::Link,
+        "checkBox" | "switch" => Role::CheckBox,
+        "radioButton" => Role::RadioButton,
+        "textField" | "searchBox" if multiline => Role::MultilineTextInput,
+        "textField" => Role::TextInput,
+        "searchBox" => Role::SearchInput,
+        "textFieldWithComboBox" | "comboBoxSelect" | "comboBoxMenuButton" => Role::ComboBox,
+        "listBox" => Role::ListBox,
+        "listBoxOption" => Role::ListBoxOption,
+        "heading" => Role::Heading,
+        "paragraph" => Role::Paragraph,
+        "staticText" | "inlineTextBox" | "labelText" => Role::Label,
+        "image" => Role::Image,
+        "list" => Role::List,
+        "listItem" => Role::ListItem,
+        "rootWebArea" | "webArea" => Role::Document,
+        "slider" => Role::Slider,
+        "progressIndicator" => Role::ProgressIndicator,
+        "tab" => Role::Tab,
+        "tabList" => Role::TabList,
+        "tabPanel" => Role::TabPanel,
+        "table" => Role::Table,
+        "row" => Role::Row,
+        "cell" | "gridCell" => Role::Cell,
+        "columnHeader" => Role::ColumnHeader,
+        "rowHeader" => Role::RowHeader,
+        "dialog" | "alertDialog" => Role::Dialog,
+        "navigation" => Role::Navigation,
+        "main" => Role::Main,
+        "form" => Role::Form,
+        "menu" => Role::Menu,
+        "menuBar" => Role::MenuBar,
+        "menuItem" => Role::MenuItem,
+        "group" => Role::Group,
+        "article" => Role::Article,
+        _ => Role::GenericContainer,
+    }
+}
+
+fn track_accessibility_tree(trigger: On<Add, WebviewSource>, mut commands: Commands) {
+    commands
+        .entity(trigger.entity)
+        .insert(WebviewAxTree::default());
+}
+
+fn expect_new_tree_on_load(trigger: On<LoadStarted>, mut trees: Query<&mut WebviewAxTree>) {
+    if let Ok(mut tree) = trees.get_mut(trigger.webview) {
+        tree.tree.navigating = true;
+    }
+}
+
+fn receive_accessibility_changes(
+    receiver: Res<AccessibilityReceiver>,
+    mut trees: Query<&mut WebviewAxTree>,
+) {
+    while let Ok(AccessibilityMessage {
+        webview,
+        change,
+        value,
+    }) = receiver.0.try_recv()
+    {
+        let Ok(mut tree) = trees.get_mut(webview) else {
+            continue;
+        };
+        match change {
+            AccessibilityChange::Tree => {
+                let Ok(change) = serde_json::from_value(value) else {
+                    continue;
+                };
+                if tree.tree.apply(change) {
+                    tree.structure_changed = true;
+                    tree.bounds_changed = true;
+                }
+            }
+            AccessibilityChange::Location => {
+                let Ok(changes) = serde_json::from_value(value) else {
+                    continue;
+                };
+                if tree.tree.relocate(changes) {
+                    tree.bounds_changed = true;
+                }
+            }
+        }
+    }
+}
+
+/// Spawns, despawns and updates the page node entities of changed trees.
+fn sync_page_nodes(mut commands: Commands, mut trees: Query<(Entity, &mut WebviewAxTree)>) {
+    for (webview, mut tree) in trees.iter_mut() {
+        if !tree.bounds_changed {
+            continue;
+        }
+        let tree = &mut *tree;
+        if tree.structure_changed {
+            let mut removed = Vec::new();
+            tree.entities.retain(|id, entity| {
+                let keep = tree.tree.nodes.contains_key(id);
+                if !keep {
+                    removed.push(*entity);
+                }
+                keep
+            });
+            for &id in tree.tree.nodes.keys() {
+                tree.entities
+                    .entry(id)
+                    .or_insert_with(|| commands.spawn(WebviewAxNode { webview, id }).id());
+            }
+            for (id, node) in tree.tree.nodes.iter() {
+                let children: Vec<Entity> = node
+                    .child_ids
+                    .iter()
+                    .filter_map(|child| tree.entities.get(child).copied())
+                    .collect();
+                commands
+                    .entity(tree.entities[id])
+                    .replace_children(&children);
+            }
+            let root = tree
+                .tree
+                .root
+                .and_then(|id| tree.entities.get(&id).copied());
+            if root != tree.root
+                && let Some(root) = root
+            {
+                commands.entity(webview).add_child(root);
+            }
+            tree.root = root;
+            // Despawning is recursive, so it waits until nodes that moved out
+            // of removed subtrees have been reparented.
+            for entity in removed {
+                commands.entity(entity).try_despawn();
+            }
+        }
+        for (id, entity) in tree.entities.iter() {
+            if let Some(node) = tree.tree.accesskit_node(*id) {
+                commands
+                    .entity(*entity)
+                    .insert(AccessibilityNode::from(node));
+            }
+        }
+        tree.structure_changed = false;
+        tree.bounds_changed = false;
+    }
+}
+
+/// Keeps each webview's own node covering the page, with the transform from
+/// page pixels to physical window pixels through its mesh, sprite or UI node.
+fn update_webview_nodes(
+    mut commands: Commands,
+    mut webviews: Query<(
+        Entity,
+        &WebviewSize,
+        &WebviewDpr,
+        Option<&HostWindow>,
+        &mut WebviewAxTree,
+    )>,
+    projection: WebviewProjection,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    windows: Query<&Window>,
+) {
+    for (webview, size, dpr, host_window, mut tree) in webviews.iter_mut() {
+        let Some(window) = host_window
+            .map(|w| w.0)
+            .or_else(|| primary_window.single().ok())
+        else {
+            continue;
+        };
+        let scale_factor = windows.get(window).map_or(1.0, Window::scale_factor);
+        let Some(transform) = page_to_window(size.0, dpr.0, |pos| {
+            projection
+                .viewport_pos(webview, window, pos)
+                .map(|viewport| viewport * scale_factor)
+        }) else {
+            continue;
+        };
+        if tree.transform == Some(transform) {
+            continue;
+        }
+        tree.transform = Some(transform);
+        let page = size.0 * dpr.0;
+        let mut node = Node::new(Role::Group);
+        node.set_bounds(AxBounds::new(0.0, 0.0, page.x as f64, page.y as f64));
+        node.set_transform(Affine::new(transform));
+        commands
+            .entity(webview)
+            .insert(AccessibilityNode::from(node));
+    }
+}
+
+/// The affine map from page pixels to window pixels, spanned by where the
+/// page's top-left, top-right and bottom-left corners (in DIPs) are shown.
+fn page_to_window(
+    size: Vec2,
+    dpr: f32,
+    to_window: impl Fn(Vec2) -> Option<Vec2>,
+) -> Option<[f64; 6]> {
+    let page = size * dpr;
+    if page.x <= 0.0 || page.y <= 0.0 {
+        return None;
+    }
+    let origin = to_window(Vec2::ZERO)?;
+    let x_axis = (to_window(Vec2::new(size.x, 0.0))? - origin) / page.x;
+    let y_axis = (to_window(Vec2::new(0.0, size.y))? - origin) / page.y;
+    Some([x_axis.x, x_axis.y, y_axis.x, y_axis.y, origin.x, origin.y].map(|c| c as f64))
+}
+
+/// The page actions for the AccessKit requests that target page nodes.
+fn action_scripts(
+    requests: &mut MessageReader<ActionRequest>,
+    nodes: &Query<&WebviewAxNode>,
+    trees: &Query<(&WebviewAxTree, &WebviewSize, &WebviewDpr)>,
+) -> Vec<(Entity, String)> {
+    requests
+        .read()
+        .filter_map(|request| {
+            let node = nodes.get(Entity::from_bits(request.target.0)).ok()?;
+            let (tree, size, dpr) = trees.get(node.webview).ok()?;
+            let (action, value) = match (request.action, &request.data) {
+                (Action::Click, _) => ("click", None),
+                (Action::Focus, _) => ("focus", None),
+                (Action::SetValue, Some(ActionData::Value(value))) => ("value", Some(&**value)),
+                _ => return None,
+            };
+            let script = action_script(&tree.tree, node.id, size.0 * dpr.0, action, value)?;
+            Some((node.webview, script))
+        })
+        .collect()
+}
+
+/// The [`ACTION_JS`] call performing `action` on node `id` of a page `page`
+/// pixels large. Nodes without an element, such as text, have no action.
+fn action_script(
+    tree: &AxTree,
+    id: i32,
+    page: Vec2,
+    action: &str,
+    value: Option<&str>,
+) -> Option<String> {
+    let data = tree.nodes.get(&id)?;
+    let tag = serde_json::to_string(data.string("htmlTag")?).ok()?;
+    let element_id = serde_json::to_string(&data.string("htmlId")).ok()?;
+    let center = tree.bounds(id)?.center() / page;
+    let value = serde_json::to_string(&value).ok()?;
+    Some(format!(
+        "{ACTION_JS}({},{},{tag},{element_id},'{action}',{value})",
+        center.x, center.y
+    ))
+}
+
+fn route_accessibility_actions(
+    mut commands: Commands,
+    mut requests: MessageReader<ActionRequest>,
+    nodes: Query<&WebviewAxNode>,
+    trees: Query<(&WebviewAxTree, &WebviewSize, &WebviewDpr)>,
+    browsers: NonSend<WebviewBrowsers>,
+) {
+    for (webview, script) in action_scripts(&mut requests, &nodes, &trees) {
+        commands.trigger(RequestFocus { webview });
+        browsers.execute_javascript(&webview, &script);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn tree() -> AxTree {
+        let mut tree = AxTree::default();
+        let change = serde_json::from_value(json!({
+            "ax_tree_id": "main",
+            "updates": [{
+                "has_tree_data": true,
+                "tree_data": { "tree_id": "main" },
+                "root_id": 1,
+                "nodes": [
+                    {
+                        "id": 1,
+                        "role": "rootWebArea",
+                        "location": { "x": 0.0, "y": 0.0, "width": 800.0, "height": 600.0 },
+                        "attributes": { "scrollY": 100 },
+                        "child_ids": [2]
+                    },
+                    {
+                        "id": 2,
+                        "role": "genericContainer",
+                        "location": { "x": 10.0, "y": 200.0, "width": 300.0, "height": 100.0 },
+                        "child_ids": [3]
+                    },
+                    {
+                        "id": 3,
+                        "role": "button",
+                        "offset_container_id": 2,
+                        "location": { "x": 5.0, "y": 5.0, "width": 50.0, "height": 20.0 },
+                        "attributes": { "name": "OK", "htmlTag": "button" }
+                    }
+                ]
+            }]
+        }))
+        .unwrap();
+        assert!(tree.apply(change));
+        tree
+    }
+
+    #[test]
+    fn bounds_follow_offset_containers_and_scrolling() {
+        let tree = tree();
+        assert_eq!(
+            tree.bounds(3),
+            Some(Rect::new(15.0, 105.0, 65.0, 125.0)),
+            "button at (5, 5) in a container at (10, 200) of a page scrolled by 100"
+        );
+    }
+
+    #[test]
+    fn actions_name_the_element_and_skip_nodes_without_one() {
+        let tree = tree();
+        let page = Vec2::new(800.0, 600.0);
+        let script = action_script(&tree, 3, page, "click", None).unwrap();
+        assert!(script.starts_with(&format!("{ACTION_JS}(0.05,0.19")));
+        assert!(script.ends_with(",\"button\",null,'click',null)"));
+        assert_eq!(action_script(&tree, 2, page, "click", None), None);
+    }
+
+    #[test]
+    fn iframe_trees_are_ignored_and_cleared_nodes_pruned() {
+        let mut tree = tree();
+        let iframe = serde_json::from_value(json!({
+            "ax_tree_id": "frame",
+            "updates": [{ "tree_data": { "parent_tree_id": "main" }, "root_id": 7 }]
+        }))
+        .unwrap();
+        assert!(!tree.apply(iframe));
+        let unparented = serde_json::from_value(json!({
+            "ax_tree_id": "frame",
+            "updates": [{ "tree_data": { "tree_id": "frame" }, "root_id": 7 }]
+        }))
+        .unwrap();
+        assert!(!tree.apply(unparented));
+
+        let clear = serde_json::from_value(json!({
+            "ax_tree_id": "main",
+            "updates": [{ "node_id_to_clear": 2 }]
+        }))
+        .unwrap();
+        assert!(tree.apply(clear));
+        assert!(tree.nodes.contains_key(&2));
+        assert!(!tree.nodes.contains_key(&3));
+    }
+
+    #[test]
+    fn a_new_main_frame_tree_replaces_the_page_after_a_load_starts() {
+        let mut tree = tree();
+        tree.navigating = true;
+        let next = serde_json::from_value(json!({
+            "ax_tree_id": "next",
+            "updates": [{ "tree_data": { "tree_id": "next" }, "root_id": 1, "nodes": [{ "id": 1 }] }]
+        }))
+        .unwrap();
+        assert!(tree.apply(next));
+        assert_eq!(tree.id.as_deref(), Some("next"));
+        assert!(!tree.navigating);
+        assert_eq!(tree.nodes.len(), 1);
+    }
+}
@@ -0,0 +1,295 @@
+//! Webview audio routed into Bevy's audio engine.
+//!
+//! Webviews spawned with [`WebviewAudioCapture`] get a CEF audio handler, so
+//! their sound no longer reaches the OS device directly. Each PCM packet is
+//! queued into a [`WebviewAudioStream`] played through an [`AudioPlayer`] on
+//! the webview entity, which picks up the entity's [`PlaybackSettings`]: add
+//! `PlaybackSettings { spatial: true, ..default() }` (and a `SpatialListener`)
+//! for positional sound, and use [`AudioSink`] or [`SpatialAudioSink`] for
+//! volume.
+
+use crate::common::WebviewSource;
+use async_channel::Receiver;
+use bevy::audio::{AddAudioSource, AudioSink, Decodable, Source, SpatialAudioSink};
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use bevy_cef_core::prelude::{AudioStreamEvent, AudioStreamMessage, AudioStreamSenderInner};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// How much audio may queue up before the oldest frames are dropped.
+const MAX_BUFFERED_SECS: f32 = 0.5;
+
+/// Captures the webview's audio into Bevy instead of playing it on the OS
+/// device.
+///
+/// Must be present when the webview is spawned; the audio handler is attached
+/// when the browser is created. Keep the entity's [`PlaybackSettings`] mode at
+/// `Once` or `Remove`: `Despawn` would despawn the webview when the page goes
+/// silent.
+///
+/// ```rust,no_run
+/// use bevy::prelude::*;
+/// use bevy_cef::prelude::*;
+///
+/// fn spawn_tv(mut commands: Commands) {
+///     commands.spawn((
+///         WebviewSource::new("https://example.com/video"),
+///         WebviewAudioCapture,
+///         PlaybackSettings {
+///             spatial: true,
+///             ..default()
+///         },
+///     ));
+/// }
+/// ```
+#[derive(Component, Debug, Default, Copy, Clone, Reflect)]
+#[reflect(Component, Debug, Default)]
+pub struct WebviewAudioCapture;
+
+/// Fired on a captured webview when its page starts producing sound.
+#[derive(Debug, EntityEvent, Copy, Clone, Reflect, Serialize, Deserialize)]
+pub struct WebviewAudioStarted {
+    #[event_target]
+    pub webview: Entity,
+    pub sample_rate: u32,
+    pub channels: u16,
+}
+
+/// Fired on a captured webview when its page goes silent, navigates away, or
+/// the stream fails.
+#[derive(Debug, EntityEvent, Copy, Clone, Reflect, Serialize, Deserialize)]
+pub struct WebviewAudioStopped {
+    #[event_target]
+    pub webview: Entity,
+}
+
+/// The live PCM stream of a captured webview, played by an
+/// `AudioPlayer<WebviewAudioStream>` on the webview entity.
+#[derive(Asset, TypePath, Clone, Debug)]
+pub struct WebviewAudioStream {
+    buffer: SharedStreamBuffer,
+    sample_rate: u32,
+    channels: u16,
+}
+
+impl Decodable for WebviewAudioStream {
+    type DecoderItem = f32;
+    type Decoder = WebviewAudioDecoder;
+
+    fn decoder(&self) -> Self::Decoder {
+        WebviewAudioDecoder {
+            buffer: self.buffer.clone(),
+            local: VecDeque::new(),
+            sample_rate: self.sample_rate,
+            channels: self.channels,
+        }
+    }
+}
+
+/// Pulls queued samples on the audio thread, filling gaps with silence until
+/// the stream is finished.
+pub struct WebviewAudioDecoder {
+    buffer: SharedStreamBuffer,
+    local: VecDeque<f32>,
+    sample_rate: u32,
+    channels: u16,
+}
+
+impl Iterator for WebviewAudioDecoder {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if let Some(sample) = self.local.pop_front() {
+            return Some(sample);
+        }
+        let mut buffer = self.buffer.lock().ok()?;
+        if buffer.samples.is_empty() {
+            if buffer.finished {
+                return None;
+            }
+            // Pad a whole frame so channels stay interleaved.
+            self.local
+                .extend(std::iter::repeat_n(0.0, self.channels.max(1) as usize));
+        } else {
+            std::mem::swap(&mut self.local, &mut buffer.samples);
+        }
+        drop(buffer);
+        self.local.pop_front()
+    }
+}
+
+impl Source for WebviewAudioDecoder {
+    fn current_span_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<std::time::Duration> {
+        None
+    }
+}
+
+type SharedStreamBuffer = Arc<Mutex<StreamBuffer>>;
+
+#[derive(Debug, Default)]
+struct StreamBuffer {
+    samples: VecDeque<f32>,
+    /// Capacity in samples, a whole number of frames.
+    capacity: usize,
+    finished: bool,
+}
+
+impl StreamBuffer {
+    fn new(sample_rate: u32, channels: u16) -> Self {
+        let frames = (sample_rate as f32 * MAX_BUFFERED_SECS) as usize;
+        Self {
+            samples: VecDeque::new(),
+            capacity: frames.max(1) * channels.max(1) as usize,
+            finished: false,
+        }
+    }
+
+    /// Queues interleaved samples, dropping the oldest once over capacity.
+    fn push(&mut self, samples: &[f32]) {
+        self.samples.extend(samples);
+        let excess = self.samples.len().saturating_sub(self.capacity);
+        self.samples.drain(..excess);
+    }
+}
+
+pub(crate) struct WebviewAudioPlugin;
+
+impl Plugin for WebviewAudioPlugin {
+    fn build(&self, app: &mut App) {
+        let (tx, rx) = async_channel::unbounded();
+        app.add_audio_source::<WebviewAudioStream>()
+            .insert_resource(AudioStreamSender(tx))
+            .insert_resource(AudioStreamReceiver(rx))
+            .init_resource::<WebviewAudioBuffers>()
+            .register_type::<WebviewAudioCapture>()
+            .register_type::<WebviewAudioStarted>()
+            .register_type::<WebviewAudioStopped>()
+            .add_systems(PreUpdate, drain_audio_streams);
+    }
+}
+
+#[derive(Resource, Debug, Deref)]
+pub(crate) struct AudioStreamSender(pub(crate) AudioStreamSenderInner);
+
+#[derive(Resource, Debug)]
+struct AudioStreamReceiver(Receiver<AudioStreamMessage>);
+
+/// The buffer of each webview whose stream is running.
+#[derive(Resource, Default, Debug)]
+struct WebviewAudioBuffers(HashMap<Entity, SharedStreamBuffer>);
+
+impl WebviewAudioBuffers {
+    fn finish(&mut self, webview: Entity) -> bool {
+        let Some(buffer) = self.0.remove(&webview) else {
+            return false;
+        };
+        if let Ok(mut buffer) = buffer.lock() {
+            buffer.finished = true;
+        }
+        true
+    }
+}
+
+fn drain_audio_streams(
+    mut commands: Commands,
+    receiver: Res<AudioStreamReceiver>,
+    mut buffers: ResMut<WebviewAudioBuffers>,
+    mut streams: ResMut<Assets<WebviewAudioStream>>,
+    mut removed: RemovedComponents<WebviewSource>,
+) {
+    for webview in removed.read() {
+        buffers.finish(webview);
+    }
+    while let Ok(msg) = receiver.0.try_recv() {
+        let webview = msg.webview;
+        match msg.event {
+            AudioStreamEvent::Started {
+                sample_rate,
+                channels,
+            } => {
+                buffers.finish(webview);
+                let buffer = Arc::new(Mutex::new(StreamBuffer::new(sample_rate, channels)));
+                buffers.0.insert(webview, buffer.clone());
+                let stream = streams.add(WebviewAudioStream {
+                    buffer,
+                    sample_rate,
+                    channels,
+                });
+                // A previous sink would keep the entity from playing the new stream.
+                commands
+                    .entity(webview)
+                    .try_remove::<(AudioSink, SpatialAudioSink)>()
+                    .try_insert(AudioPlayer(stream));
+                commands.trigger(WebviewAudioStarted {
+                    webview,
+                    sample_rate,
+                    channels,
+                });
+            }
+            AudioStreamEvent::Packet(samples) => {
+                if let Some(buffer) = buffers.0.get(&webview)
+                    && let Ok(mut buffer) = buffer.lock()
+                {
+                    buffer.push(&samples);
+                }
+            }
+            AudioStreamEvent::Stopped => {
+                if buffers.finish(webview) {
+                    commands.trigger(WebviewAudioStopped { webview });
+                }
+            }
+            AudioStreamEvent::Error(message) => {
+                warn!("bevy_cef: audio capture failed for {webview}: {message}");
+                if buffers.finish(webview) {
+                    commands.trigger(WebviewAudioStopped { webview });
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buffer_drops_the_oldest_frames_when_full() {
+        let mut buffer = StreamBuffer::new(4, 2);
+        buffer.push(&[1.0, 1.0, 2.0, 2.0, 3.0, 3.0]);
+        buffer.push(&[4.0, 4.0]);
+        assert_eq!(buffer.samples, [3.0, 3.0, 4.0, 4.0]);
+    }
+
+    #[test]
+    fn decoder_pads_silence_until_the_stream_finishes() {
+        let buffer = Arc::new(Mutex::new(StreamBuffer::new(48_000, 2)));
+        let mut decoder = WebviewAudioStream {
+            buffer: buffer.clone(),
+            sample_rate: 48_000,
+            channels: 2,
+        }
+        .decoder();
+
+        assert_eq!(decoder.next(), Some(0.0));
+        assert_eq!(decoder.next(), Some(0.0));
+        buffer.lock().unwrap().push(&[0.5, -0.5]);
+        assert_eq!(decoder.next(), Some(0.5));
+        assert_eq!(decoder.next(), Some(-0.5));
+        buffer.lock().unwrap().finished = true;
+        assert_eq!(decoder.next(), None);
+    }
+}
@@ -0,0 +1,543 @@
+//! Chrome DevTools Protocol sessions driven from Rust.
+//!
+//! A [`CdpSession`] on a webview observes its browser's DevTools messages
+//! through CEF directly, so no remote debugging port is opened. [`Cdp`] sends
+//! commands to the session: every result fires a [`CdpResult`] on the webview
+//! and resolves the future [`Cdp::call`] returned. Domain events the session
+//! subscribes to fire [`CdpEvent`]s.
+//!
+//! CEF reports the messages on its UI thread, which is Bevy's main thread
+//! with the external message pump and CEF's own thread with the multi-threaded
+//! message loop. Either way they reach Bevy through a channel drained in
+//! `PreUpdate`.
+
+use crate::lifecycle::WebviewLifecycle;
+use crate::webview::WebviewBrowsers;
+use async_channel::{Receiver, Sender};
+use bevy::ecs::system::SystemParam;
+use bevy::platform::collections::{HashMap, HashSet};
+use bevy::prelude::*;
+use bevy_cef_core::prelude::{CdpMessage, CdpSenderInner};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::fmt;
+
+pub(crate) struct CdpPlugin;
+
+impl Plugin for CdpPlugin {
+    fn build(&self, app: &mut App) {
+        let (tx, rx) = async_channel::unbounded();
+        app.insert_resource(CdpState {
+            sender: tx,
+            attached: HashSet::default(),
+            opening: HashSet::default(),
+            next_id: 0,
+            waiters: HashMap::default(),
+        })
+        .insert_resource(CdpReceiver(rx))
+        .register_type::<CdpSession>()
+        .add_systems(PreUpdate, drain_cdp_messages);
+
+        app.add_systems(Update, sync_cdp_sessions);
+    }
+}
+
+/// Opens a Chrome DevTools Protocol session on the webview.
+///
+/// Commands are sent through [`Cdp`] once the webview's browser is up. The
+/// session survives the browser being recreated, e.g. after a discard, but
+/// commands still pending then fail with [`CdpError::Closed`]. Remove the
+/// component to close the session.
+///
+/// Domain events are only fired for the methods listed in `events`, and only
+/// after their domain is enabled, e.g. with `Network.enable`.
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_cef::prelude::*;
+///
+/// fn spawn_traced(mut commands: Commands) {
+///     commands
+///         .spawn((
+///             WebviewSource::new("https://example.com"),
+///             CdpSession::with_events(["Network.requestWillBeSent", "Runtime.*"]),
+///         ))
+///         .observe(|event: On<CdpEvent>| {
+///             info!("{}: {}", event.method, event.params);
+///         });
+/// }
+/// ```
+#[derive(Component, Debug, Clone, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Debug, Default)]
+pub struct CdpSession {
+    /// Domain events to fire as [`CdpEvent`]s: a method such as
+    /// `Network.requestWillBeSent`, a whole domain such as `Runtime.*`, or `*`
+    /// for every event.
+    pub events: Vec<String>,
+}
+
+impl CdpSession {
+    /// A session firing the domain events matching `events`.
+    pub fn with_events(events: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            events: events.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    fn subscribes(&self, method: &str) -> bool {
+        self.events
+            .iter()
+            .any(|event| match event.strip_suffix('*') {
+                Some("") => true,
+                Some(domain) => domain.ends_with('.') && method.starts_with(domain),
+                None => event == method,
+            })
+    }
+}
+
+/// A typed Chrome DevTools Protocol command, sent with [`Cdp::call`].
+///
+/// The command itself serializes to the CDP `params`.
+///
+/// ```rust
+/// use bevy_cef::prelude::*;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize)]
+/// #[serde(rename_all = "camelCase")]
+/// struct Evaluate {
+///     expression: String,
+///     return_by_value: bool,
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct Evaluated {
+///     result: serde_json::Value,
+/// }
+///
+/// impl CdpCommand for Evaluate {
+///     const METHOD: &'static str = "Runtime.evaluate";
+///     type Response = Evaluated;
+/// }
+/// ```
+pub trait CdpCommand: Serialize {
+    /// The CDP method, e.g. `Runtime.evaluate`.
+    const METHOD: &'static str;
+    /// The command's result.
+    type Response: DeserializeOwned + Send + 'static;
+}
+
+/// Why a CDP command produced no result.
+#[derive(Debug, Clone, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum CdpError {
+    /// The webview has no [`CdpSession`], or its browser is not up.
+    NoSession,
+    /// The session closed before the result arrived.
+    Closed,
+    /// The browser refused the command.
+    NotSent,
+    /// The page answered with a protocol error.
+    Protocol { code: i64, message: String },
+    /// The params or the result did not match the command's types.
+    Json(String),
+}
+
+impl fmt::Display for CdpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoSession => write!(f, "the webview has no open CDP session"),
+            Self::Closed => write!(f, "the CDP session closed before the result arrived"),
+            Self::NotSent => write!(f, "the browser refused the CDP command"),
+            Self::Protocol { code, message } => write!(f, "CDP error {code}: {message}"),
+            Self::Json(error) => write!(f, "CDP JSON mismatch: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for CdpError {}
+
+/// Fired on a webview when a CDP command sent through [`Cdp`] completes.
+#[derive(Debug, EntityEvent, Clone, PartialEq)]
+pub struct CdpResult {
+    #[event_target]
+    pub webview: Entity,
+    /// The id [`Cdp::send`] returned.
+    pub id: i32,
+    pub result: Result<Value, CdpError>,
+}
+
+/// Fired on a webview for each CDP domain event its [`CdpSession`] subscribes
+/// to.
+#[derive(Debug, EntityEvent, Clone, PartialEq)]
+pub struct CdpEvent {
+    #[event_target]
+    pub webview: Entity,
+    /// The event, e.g. `Network.requestWillBeSent`.
+    pub method: String,
+    pub params: Value,
+}
+
+impl CdpEvent {
+    /// Deserializes the event's params.
+    pub fn parse<T: DeserializeOwned>(&self) -> Result<T, CdpError> {
+        T::deserialize(&self.params).map_err(|e| CdpError::Json(e.to_string()))
+    }
+}
+
+/// Sends Chrome DevTools Protocol commands to webviews with a [`CdpSession`].
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy::tasks::IoTaskPool;
+/// use bevy_cef::prelude::*;
+/// use serde_json::json;
+///
+/// fn count_nodes(mut cdp: Cdp, webviews: Query<Entity, With<CdpSession>>) {
+///     for webview in webviews.iter() {
+///         let document = cdp.call_raw(webview, "DOM.getDocument", json!({ "depth": -1 }));
+///         IoTaskPool::get()
+///             .spawn(async move {
+///                 if let Ok(document) = document.await {
+///                     info!("{}", document["root"]["childNodeCount"]);
+///                 }
+///             })
+///             .detach();
+///     }
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct Cdp<'w> {
+    state: ResMut<'w, CdpState>,
+    browsers: NonSend<'w, WebviewBrowsers>,
+}
+
+impl Cdp<'_> {
+    /// Sends the command `method` with `params` and returns its id. The result
+    /// fires a [`CdpResult`] with that id on the webview.
+    pub fn send_raw(
+        &mut self,
+        webview: Entity,
+        method: &str,
+        params: Value,
+    ) -> Result<i32, CdpError> {
+        if !self.state.attached.contains(&webview) {
+            return Err(CdpError::NoSession);
+        }
+        self.state.next_id = self.state.next_id.wrapping_add(1);
+        let id = self.state.next_id;
+        let message = json!({ "id": id, "method": method, "params": params }).to_string();
+        if !self.browsers.send_cdp(&webview, &message) {
+            return Err(CdpError::NotSent);
+        }
+        Ok(id)
+    }
+
+    /// Sends a typed command and returns its id, like [`Cdp::send_raw`].
+    pub fn send<C: CdpCommand>(&mut self, webview: Entity, command: &C) -> Result<i32, CdpError> {
+        let params = serde_json::to_value(command).map_err(|e| CdpError::Json(e.to_string()))?;
+        self.send_raw(webview, C::METHOD, params)
+    }
+
+    /// Sends the command `method` with `params` and resolves to its result.
+    /// The future can be awaited on any task pool.
+    pub fn call_raw(
+        &mut self,
+        webview: Entity,
+        method: &str,
+        params: Value,
+    ) -> impl Future<Output = Result<Value, CdpError>> + Send + 'static {
+        let waiter = self.send_raw(webview, method, params).map(|id| {
+            let (tx, rx) = async_channel::bounded(1);
+            self.state.waiters.insert((webview, id), tx);
+            rx
+        });
+        async move { waiter?.recv().await.unwrap_or(Err(CdpError::Closed)) }
+    }
+
+    /// Sends a typed command and resolves to its typed result.
+    pub fn call<C: CdpCommand>(
+        &mut self,
+        webview: Entity,
+        command: &C,
+    ) -> impl Future<Output = Result<C::Response, CdpError>> + Send + 'static {
+        let call = serde_json::to_value(command)
+            .map_err(|e| CdpError::Json(e.to_string()))
+            .map(|params| self.call_raw(webview, C::METHOD, params));
+        async move {
+            let value = call?.await?;
+            serde_json::from_value(value).map_err(|e| CdpError::Json(e.to_string()))
+        }
+    }
+}
+
+#[derive(Resource, Debug)]
+pub struct CdpState {
+    sender: CdpSenderInner,
+    /// Webviews whose browser is observed.
+    attached: HashSet<Entity>,
+    /// Webviews whose browser CEF's thread was asked to observe, with the
+    /// multi-threaded message loop.
+    opening: HashSet<Entity>,
+    next_id: i32,
+    /// Futures returned by [`Cdp::call_raw`], by webview and command id.
+    waiters: HashMap<(Entity, i32), Sender<Result<Value, CdpError>>>,
+}
+
+impl CdpState {
+    /// Forgets the webview's browser. Pending calls resolve with
+    /// [`CdpError::Closed`] as their senders drop.
+    fn detach(&mut self, webview: Entity) -> bool {
+        self.waiters.retain(|(w, _), _| *w != webview);
+        let opening = self.opening.remove(&webview);
+        self.attached.remove(&webview) || opening
+    }
+
+    /// Resolves the pending call `id` and fires its [`CdpResult`].
+    fn resolve(
+        &mut self,
+        commands: &mut Commands,
+        sessions: &Query<&CdpSession>,
+        webview: Entity,
+        id: i32,
+        result: Result<Value, CdpError>,
+    ) {
+        if let Some(waiter) = self.waiters.remove(&(webview, id)) {
+            let _ = waiter.try_send(result.clone());
+        }
+        if sessions.contains(webview) {
+            commands.trigger(CdpResult {
+                webview,
+                id,
+                result,
+            });
+        }
+    }
+}
+
+#[derive(Resource, Debug)]
+struct CdpReceiver(Receiver<CdpMessage>);
+
+fn drain_cdp_messages(
+    mut commands: Commands,
+    receiver: Res<CdpReceiver>,
+    mut state: ResMut<CdpState>,
+    sessions: Query<&CdpSession>,
+) {
+    while let Ok(msg) = receiver.0.try_recv() {
+        match msg {
+            CdpMessage::Result {
+                webview,
+                id,
+                success,
+                result,
+            } => {
+                let result = if success {
+                    Ok(result)
+                } else {
+                    Err(protocol_error(&result))
+                };
+                state.resolve(&mut commands, &sessions, webview, id, result);
+            }
+            CdpMessage::NotSent { webview, id } => {
+                state.resolve(
+                    &mut commands,
+                    &sessions,
+                    webview,
+                    id,
+                    Err(CdpError::NotSent),
+                );
+            }
+            CdpMessage::Opened { webview, success } => {
+                if state.opening.remove(&webview) && success {
+                    state.attached.insert(webview);
+                } else if !success {
+                    warn!("bevy_cef: could not open a CDP session on {webview}");
+                }
+            }
+            CdpMessage::Event {
+                webview,
+                method,
+                params,
+            } => {
+                if sessions.get(webview).is_ok_and(|s| s.subscribes(&method)) {
+                    commands.trigger(CdpEvent {
+                        webview,
+                        method,
+                        params,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// CDP reports failures as `{"code": .., "message": ..}`.
+fn protocol_error(error: &Value) -> CdpError {
+    CdpError::Protocol {
+        code: error["code"].as_i64().unwrap_or_default(),
+        message: error["message"].as_str().unwrap_or_default().to_string(),
+    }
+}
+
+fn sync_cdp_sessions(
+    mut browsers: NonSendMut<WebviewBrowsers>,
+    mut state: ResMut<CdpState>,
+    mut removed: RemovedComponents<CdpSession>,
+    sessions: Query<
+        (Entity, &WebviewLifecycle),
+        (
+            With<CdpSession>,
+            Or<(Added<CdpSession>, Changed<WebviewLifecycle>)>,
+        ),
+    >,
+) {
+    for webview in removed.read() {
+        if state.detach(webview) {
+            browsers.close_cdp(&webview);
+        }
+    }
+    for (webview, lifecycle) in sessions.iter() {
+        if !lifecycle.has_browser() {
+            state.detach(webview);
+        } else if !state.attached.contains(&webview) && !state.opening.contains(&webview) {
+            match browsers.open_cdp(&webview, state.sender.clone()) {
+                Some(true) => {
+                    state.attached.insert(webview);
+                }
+                Some(false) => {}
+                // Commands are refused with `NoSession` until CEF's thread
+                // reports the session open.
+                None => {
+                    state.opening.insert(webview);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_subscribes_to_methods_and_domains() {
+        let session = CdpSession::with_events(["Network.requestWillBeSent", "Runtime.*"]);
+        assert!(session.subscribes("Network.requestWillBeSent"));
+        assert!(session.subscribes("Runtime.exceptionThrown"));
+        assert!(!session.subscribes("Network.responseReceived"));
+        assert!(!session.subscribes("RuntimeX.event"));
+        assert!(CdpSession::with_events(["*"]).subscribes("Page.loadEventFired"));
+        assert!(!CdpSession::default().subscribes("Page.loadEventFired"));
+    }
+
+    #[test]
+    fn protocol_errors_resolve_pending_calls() {
+        let (sender, receiver) = async_channel::unbounded();
+        let (waiter, call) = async_channel::bounded(1);
+        let mut world = World::new();
+        let webview = world.spawn(CdpSession::default()).id();
+        world.insert_resource(CdpReceiver(receiver));
+        world.insert_resource(CdpState {
+            sender: sender.clone(),
+            attached: HashSet::from_iter([webview]),
+            opening: HashSet::default(),
+            next_id: 7,
+            waiters: HashMap::from_iter([((webview, 7), waiter)]),
+        });
+        let mut schedule = Schedule::default();
+        schedule.add_systems(drain_cdp_messages);
+
+        sender
+            .send_blocking(CdpMessage::Result {
+                webview,
+                id: 7,
+                success: false,
+                result: json!({ "code": -32601, "message": "'Foo.bar' wasn't found" }),
+            })
+            .unwrap();
+        schedule.run(&mut world);
+
+        assert_eq!(
+            call.try_recv().unwrap(),
+            Err(CdpError::Protocol {
+                code: -32601,
+                message: "'Foo.bar' wasn't found".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn refused_commands_and_failed_opens_do_not_leave_calls_pending() {
+        let (sender, receiver) = async_channel::unbounded();
+        let (waiter, call) = async_channel::bounded(1);
+        let mut world = World::new();
+        let opened = world.spawn(CdpSession::default()).id();
+        let refused = world.spawn(CdpSession::default()).id();
+        world.insert_resource(CdpReceiver(receiver));
+        world.insert_resource(CdpState {
+            sender: sender.clone(),
+            attached: HashSet::from_iter([opened]),
+            opening: HashSet::from_iter([refused]),
+            next_id: 3,
+            waiters: HashMap::from_iter([((opened, 3), waiter)]),
+        });
+        let mut schedule = Schedule::default();
+        schedule.add_systems(drain_cdp_messages);
+
+        for message in [
+            CdpMessage::NotSent {
+                webview: opened,
+                id: 3,
+            },
+            CdpMessage::Opened {
+                webview: refused,
+                success: false,
+            },
+        ] {
+            sender.send_blocking(message).unwrap();
+        }
+        schedule.run(&mut world);
+
+        assert_eq!(call.try_recv().unwrap(), Err(CdpError::NotSent));
+        let state = world.resource::<CdpState>();
+        assert!(!state.attached.contains(&refused));
+        assert!(state.opening.is_empty());
+    }
+
+    #[test]
+    fn commands_after_a_refused_open_resolve_as_not_sent() {
+        let (sender, receiver) = async_channel::unbounded();
+        let (waiter, call) = async_channel::bounded(1);
+        let mut world = World::new();
+        let webview = world.spawn(CdpSession::default()).id();
+        world.insert_resource(CdpReceiver(receiver));
+        world.insert_resource(CdpState {
+            sender: sender.clone(),
+            attached: HashSet::default(),
+            opening: HashSet::from_iter([webview]),
+            next_id: 5,
+            waiters: HashMap::from_iter([((webview, 5), waiter)]),
+        });
+        let mut schedule = Schedule::default();
+        schedule.add_systems(drain_cdp_messages);
+
+        // CEF's thread refuses the session, then the command queued behind it.
+        for message in [
+            CdpMessage::Opened {
+                webview,
+                success: false,
+            },
+            CdpMessage::NotSent { webview, id: 5 },
+        ] {
+            sender.send_blocking(message).unwrap();
+        }
+        schedule.run(&mut world);
+
+        assert_eq!(call.try_recv().unwrap(), Err(CdpError::NotSent));
+        let state = world.resource::<CdpState>();
+        assert!(!state.attached.contains(&webview));
+        assert!(state.opening.is_empty());
+        assert!(state.waiters.is_empty());
+    }
+}
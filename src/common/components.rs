@@ -4,7 +4,9 @@ use bevy_cef_core::prelude::RetainedIoSurface;
 use bevy_cef_core::prelude::{HOST_CEF, SCHEME_CEF};
 use serde::{Deserialize, Serialize};
 
+use crate::common::ResolvedBridgePolicy;
 use crate::title::WebviewTitle;
+use crate::user_script::ResolvedUserScripts;
 
 pub(crate) struct WebviewCoreComponentsPlugin;
 
@@ -17,7 +19,8 @@ impl Plugin for WebviewCoreComponentsPlugin {
             .register_type::<AudioMuted>()
             .register_type::<PreloadScripts>()
             .register_type::<WebviewDpr>()
-            .register_type::<WebviewTextureTarget>();
+            .register_type::<WebviewTextureTarget>()
+            .register_type::<WebviewUvMapping>();
     }
 }
 
@@ -36,7 +39,9 @@ impl Plugin for WebviewCoreComponentsPlugin {
     AudioMuted,
     PreloadScripts,
     WebviewDpr,
-    WebviewTitle
+    WebviewTitle,
+    ResolvedUserScripts,
+    ResolvedBridgePolicy
 )]
 pub enum WebviewSource {
     /// A remote or local URL (e.g. `"https://..."` or `"cef://localhost/file.html"`).
@@ -97,6 +102,8 @@ impl Default for WebviewSize {
 /// the host window's `scale_factor()` at spawn, refreshed on
 /// `WindowScaleFactorChanged`. User code normally does not need to write this
 /// component, but may override it (e.g. to force 2× rendering for screenshots).
+/// Webviews with no window to follow, e.g. in a headless app, keep the value
+/// they were spawned with.
 ///
 /// `WebviewSize` is interpreted in logical pixels (DIP). The actual GPU
 /// texture CEF allocates is `WebviewSize × WebviewDpr` physical pixels.
@@ -120,6 +127,22 @@ impl Default for WebviewDpr {
 #[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
 pub struct HostWindow(pub Entity);
 
+/// Maps pointer hits on a mesh webview through the mesh's `ATTRIBUTE_UV_0`
+/// instead of the plane of its bounding box.
+///
+/// Needed for curved screens, cylinders and other non-planar meshes, where the
+/// plane mapping puts clicks in the wrong place. Every pointer path honors it:
+/// hover, clicks, wheel, drag regions, transparent-pixel hit-testing and
+/// [`WebviewRayPointer`](crate::prelude::WebviewRayPointer).
+///
+/// Each hit is a CPU ray cast against every triangle of the webview entity's
+/// own `Mesh3d`, so keep such meshes reasonably small. The mesh must be a
+/// triangle list with UVs and stay in main-world memory
+/// (`RenderAssetUsages::MAIN_WORLD` must not be dropped).
+#[derive(Reflect, Component, Debug, Copy, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[reflect(Component, Debug, Default, Serialize, Deserialize)]
+pub struct WebviewUvMapping;
+
 /// This component is used to specify the zoom level of the webview.
 ///
 /// Specify 0.0 to reset the zoom level to the default.
@@ -134,7 +157,11 @@ pub struct AudioMuted(pub bool);
 
 /// This component is used to preload scripts in the webview.
 ///
-/// Scripts specified in this component are executed before the scripts in the HTML.
+/// Scripts specified in this component are executed before the scripts in the HTML,
+/// in every frame and on every page. Changes apply from the next page load.
+///
+/// Use [`UserScripts`](crate::prelude::UserScripts) to scope scripts to specific URLs,
+/// the main frame, or to run them after the DOM is ready.
 #[derive(Reflect, Component, Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 #[reflect(Component, Debug, Default, Serialize, Deserialize)]
 pub struct PreloadScripts(pub Vec<String>);
@@ -185,9 +212,10 @@ pub(crate) struct WebviewIoSurface(pub(crate) RetainedIoSurface);
 /// finished load would clobber the placeholder), or one handle shared between
 /// two webviews (last blit wins; a warning is logged).
 ///
-/// Platform: the texture is only written on macOS (GPU IOSurface path). On
-/// Linux/Windows the component is inert — note the browser itself is still
-/// created and keeps painting CPU frames that nothing consumes.
+/// Platform: on macOS the page texture is injected on the GPU (IOSurface
+/// path). On Linux/Windows each CPU frame is copied into the image asset
+/// itself, so the pixels are readable from the main world as well, e.g. to
+/// save a thumbnail.
 ///
 /// Rebind contract: when the injected GPU texture is (re)created — first
 /// frame, resize, handle swap — bevy_cef touches this `Image` asset so
@@ -3,6 +3,7 @@
 //! `WebviewDpiPlugin` maintains each webview's `WebviewDpr` component,
 //! seeding it from the host window at spawn and refreshing it when the
 //! host window's `scale_factor` changes (monitor move, OS DPI setting).
+//! Webviews without any window keep the `WebviewDpr` they were spawned with.
 //! The change is then committed to CEF via `notify_screen_info_changed`.
 
 use crate::common::{HostWindow, WebviewDpr, WebviewSource};
@@ -10,10 +11,7 @@ use crate::webview::WebviewSet;
 use bevy::prelude::*;
 use bevy::window::{PrimaryWindow, WindowScaleFactorChanged};
 
-#[cfg(not(target_os = "windows"))]
-use bevy_cef_core::prelude::Browsers;
-#[cfg(target_os = "windows")]
-use bevy_cef_core::prelude::BrowsersProxy;
+use crate::webview::WebviewBrowsers;
 
 pub struct WebviewDpiPlugin;
 
@@ -28,17 +26,10 @@ impl Plugin for WebviewDpiPlugin {
                 .in_set(WebviewSet::DpiSeed),
         );
 
-        #[cfg(not(target_os = "windows"))]
         app.add_systems(
             Update,
             commit_webview_dpr_system.in_set(WebviewSet::CommitResize),
         );
-
-        #[cfg(target_os = "windows")]
-        app.add_systems(
-            Update,
-            commit_webview_dpr_system_win.in_set(WebviewSet::CommitResize),
-        );
     }
 }
 
@@ -48,15 +39,13 @@ fn seed_webview_dpr_system(
     primary: Query<&Window, With<PrimaryWindow>>,
 ) {
     for (mut dpr, host) in webviews.iter_mut() {
-        let resolved = host
+        // Headless webviews keep the `WebviewDpr` they were spawned with.
+        if let Some(window) = host
             .and_then(|hw| windows.get(hw.0).ok())
             .or_else(|| primary.single().ok())
-            .map(|w| w.scale_factor())
-            .unwrap_or_else(|| {
-                warn!("No window found when seeding WebviewDpr; falling back to 1.0");
-                1.0
-            });
-        dpr.0 = resolved;
+        {
+            dpr.0 = window.scale_factor();
+        }
     }
 }
 
@@ -77,9 +66,8 @@ fn refresh_on_scale_factor_changed_system(
     }
 }
 
-#[cfg(not(target_os = "windows"))]
 fn commit_webview_dpr_system(
-    browsers: NonSend<Browsers>,
+    browsers: NonSend<WebviewBrowsers>,
     webviews: Query<(Entity, &WebviewDpr), Changed<WebviewDpr>>,
 ) {
     for (entity, dpr) in webviews.iter() {
@@ -88,17 +76,6 @@ fn commit_webview_dpr_system(
     }
 }
 
-#[cfg(target_os = "windows")]
-fn commit_webview_dpr_system_win(
-    proxy: Res<BrowsersProxy>,
-    webviews: Query<(Entity, &WebviewDpr), Changed<WebviewDpr>>,
-) {
-    for (entity, dpr) in webviews.iter() {
-        proxy.set_dpr(&entity, dpr.0);
-        proxy.notify_screen_info_changed(&entity);
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -127,6 +104,18 @@ mod tests {
         assert_eq!(dpr.0, 1.0);
     }
 
+    #[test]
+    fn seed_keeps_spawned_dpr_when_no_windows_exist() {
+        let mut app = make_app();
+        let entity = app
+            .world_mut()
+            .spawn((WebviewSource::new("https://example.com"), WebviewDpr(2.0)))
+            .id();
+        app.update();
+        let dpr = app.world().get::<WebviewDpr>(entity).unwrap();
+        assert_eq!(dpr.0, 2.0);
+    }
+
     #[test]
     fn seed_uses_primary_window_scale_factor_when_no_host_window() {
         let mut app = make_app();
@@ -1,10 +1,21 @@
+mod bridge;
+mod brp;
+mod control;
 mod host_emit;
 mod js_emit;
 
 use crate::common::ipc::js_emit::IpcRawEventPlugin;
 use bevy::prelude::*;
 
+use crate::common::ipc::bridge::BridgePolicyPlugin;
+use crate::common::ipc::brp::BrpPolicyPlugin;
+use crate::common::ipc::control::ControlPlugin;
 use crate::common::ipc::host_emit::HostEmitPlugin;
+pub(crate) use bridge::{BridgeAccess, ResolvedBridgePolicy};
+pub use bridge::{DefaultWebviewBridgePolicy, WebviewBridgePolicy};
+pub(crate) use brp::WebviewBrpSender;
+pub use brp::{BRP_ACCESS_DENIED, DefaultWebviewBrpPolicy, WebviewBrpPolicy};
+pub(crate) use control::{ControlMessageSender, WebviewControl, drain_control_messages};
 pub use host_emit::*;
 pub use js_emit::*;
 
@@ -12,6 +23,12 @@ pub struct IpcPlugin;
 
 impl Plugin for IpcPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((IpcRawEventPlugin, HostEmitPlugin));
+        app.add_plugins((
+            IpcRawEventPlugin,
+            HostEmitPlugin,
+            ControlPlugin,
+            BrpPolicyPlugin,
+            BridgePolicyPlugin,
+        ));
     }
 }
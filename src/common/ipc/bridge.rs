@@ -0,0 +1,234 @@
+//! Which frames get `window.cef` and the custom extensions.
+//!
+//! Each webview's [`WebviewBridgePolicy`], or [`DefaultWebviewBridgePolicy`]
+//! when it has none, is resolved into an internal [`ResolvedBridgePolicy`]. The
+//! resolved policy is handed to CEF when the browser is created and re-sent to
+//! the render process whenever it changes, which then hides `window.cef` and
+//! the extensions from frames whose origin it does not admit. Messages from
+//! those frames are dropped on arrival as well, through [`BridgeAccess`].
+
+use crate::common::ResolvedWebviewUri;
+use crate::webview::WebviewBrowsers;
+use crate::webview::WebviewSet;
+use bevy::ecs::system::SystemParam;
+use bevy::platform::collections::{HashMap, HashSet};
+use bevy::prelude::*;
+use bevy_cef_core::prelude::*;
+
+pub(crate) struct BridgePolicyPlugin;
+
+impl Plugin for BridgePolicyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DefaultWebviewBridgePolicy>()
+            .register_type::<WebviewBridgePolicy>()
+            .register_type::<DefaultWebviewBridgePolicy>()
+            .add_systems(
+                Update,
+                resolve_bridge_policies.before(WebviewSet::CreateBrowser),
+            );
+
+        app.add_systems(
+            Update,
+            push_bridge_policies
+                .after(WebviewSet::CreateBrowser)
+                .run_if(any_changed_bridge_policy),
+        );
+    }
+}
+
+/// Which frames of a webview get `window.cef` and the [`CefExtensions`].
+///
+/// Origins are written `scheme://host[:port]`, e.g. `https://example.com`,
+/// and `*` admits every origin. Frames at other origins get neither the
+/// bridge nor the extensions, and anything they still manage to send is
+/// dropped with a warning. Webviews without one use
+/// [`DefaultWebviewBridgePolicy`], which admits `cef://localhost` only.
+/// Changes apply from the next document load.
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_cef::prelude::*;
+///
+/// fn spawn_store(mut commands: Commands) {
+///     commands.spawn((
+///         WebviewSource::new("https://store.example.com"),
+///         WebviewBridgePolicy::allow_origins(["https://store.example.com"])
+///             .with_extension("analytics", ["*"]),
+///     ));
+/// }
+/// ```
+#[derive(Component, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Component, Debug, Default)]
+pub struct WebviewBridgePolicy {
+    /// Origins that get `window.cef`.
+    pub origins: Vec<String>,
+    /// Origins per extension name. Extensions not listed follow `origins`.
+    pub extensions: HashMap<String, Vec<String>>,
+}
+
+impl Default for WebviewBridgePolicy {
+    /// Admits `cef://localhost` only.
+    fn default() -> Self {
+        Self::allow_origins(["cef://localhost"])
+    }
+}
+
+impl WebviewBridgePolicy {
+    /// Hides the bridge and every extension from all frames.
+    pub fn deny_all() -> Self {
+        Self::allow_origins(Vec::<String>::new())
+    }
+
+    /// Exposes the bridge and every extension to all frames.
+    pub fn allow_all() -> Self {
+        Self::allow_origins(["*"])
+    }
+
+    /// Exposes the bridge and every extension to frames at `origins`.
+    pub fn allow_origins(origins: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            origins: origins.into_iter().map(Into::into).collect(),
+            extensions: HashMap::default(),
+        }
+    }
+
+    /// Exposes the extension registered as `name` to frames at `origins`
+    /// instead.
+    pub fn with_extension(
+        mut self,
+        name: impl Into<String>,
+        origins: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.extensions
+            .insert(name.into(), origins.into_iter().map(Into::into).collect());
+        self
+    }
+
+    fn payload(&self) -> BridgePolicyPayload {
+        BridgePolicyPayload {
+            origins: self.origins.clone(),
+            extensions: self.extensions.clone(),
+        }
+    }
+}
+
+/// The [`WebviewBridgePolicy`] of webviews that have none.
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq, Deref, DerefMut, Reflect)]
+#[reflect(Resource, Debug, Default)]
+pub struct DefaultWebviewBridgePolicy(pub WebviewBridgePolicy);
+
+/// The policy actually shipped to CEF.
+#[derive(Component, Debug, Clone, PartialEq, Default)]
+pub(crate) struct ResolvedBridgePolicy(pub(crate) BridgePolicyPayload);
+
+/// Checks the origin attached to messages coming from a webview's pages.
+#[derive(SystemParam)]
+pub(crate) struct BridgeAccess<'w, 's> {
+    policies: Query<'w, 's, &'static ResolvedBridgePolicy>,
+}
+
+impl BridgeAccess<'_, '_> {
+    /// Whether frames at `origin` may use `window.cef` on `webview`. Denied
+    /// messages are logged, naming `what` was sent.
+    pub(crate) fn admits(&self, webview: Entity, origin: &str, what: &str) -> bool {
+        let admitted = self
+            .policies
+            .get(webview)
+            .is_ok_and(|policy| policy.0.exposes_api(origin));
+        if !admitted {
+            warn!(
+                "bevy_cef: denied {what} from {origin} on {webview}: origin not admitted by its bridge policy"
+            );
+        }
+        admitted
+    }
+}
+
+fn resolve_bridge_policies(
+    default_policy: Res<DefaultWebviewBridgePolicy>,
+    mut removed: RemovedComponents<WebviewBridgePolicy>,
+    mut webviews: Query<(
+        Entity,
+        Option<Ref<WebviewBridgePolicy>>,
+        &mut ResolvedBridgePolicy,
+    )>,
+) {
+    let removed: HashSet<Entity> = removed.read().collect();
+    for (entity, policy, mut resolved) in webviews.iter_mut() {
+        let changed = resolved.is_added()
+            || removed.contains(&entity)
+            || match &policy {
+                Some(policy) => policy.is_changed(),
+                None => default_policy.is_changed(),
+            };
+        if !changed {
+            continue;
+        }
+        let policy = policy.as_deref().unwrap_or(&default_policy.0);
+        resolved.set_if_neq(ResolvedBridgePolicy(policy.payload()));
+    }
+}
+
+fn any_changed_bridge_policy(
+    webviews: Query<Entity, (Changed<ResolvedBridgePolicy>, With<ResolvedWebviewUri>)>,
+) -> bool {
+    !webviews.is_empty()
+}
+
+fn push_bridge_policies(
+    browsers: NonSend<WebviewBrowsers>,
+    webviews: Query<
+        (Entity, &ResolvedBridgePolicy),
+        (Changed<ResolvedBridgePolicy>, With<ResolvedWebviewUri>),
+    >,
+    added: Query<Entity, Added<ResolvedWebviewUri>>,
+) {
+    for (entity, policy) in webviews.iter() {
+        // Freshly created browsers received the policy through their extra info.
+        if added.contains(entity) {
+            continue;
+        }
+        browsers.set_bridge_policy(&entity, &policy.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> (World, Schedule) {
+        let mut world = World::new();
+        world.init_resource::<DefaultWebviewBridgePolicy>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(resolve_bridge_policies);
+        (world, schedule)
+    }
+
+    fn resolved_origins(world: &World, entity: Entity) -> Vec<String> {
+        world
+            .get::<ResolvedBridgePolicy>(entity)
+            .unwrap()
+            .0
+            .origins
+            .clone()
+    }
+
+    #[test]
+    fn webview_policy_overrides_the_default_until_removed() {
+        let (mut world, mut schedule) = setup();
+        world.resource_mut::<DefaultWebviewBridgePolicy>().0 = WebviewBridgePolicy::deny_all();
+        let webview = world
+            .spawn((
+                ResolvedBridgePolicy::default(),
+                WebviewBridgePolicy::allow_origins(["https://example.com"]),
+            ))
+            .id();
+
+        schedule.run(&mut world);
+        assert_eq!(resolved_origins(&world, webview), ["https://example.com"]);
+
+        world.entity_mut(webview).remove::<WebviewBridgePolicy>();
+        schedule.run(&mut world);
+        assert!(resolved_origins(&world, webview).is_empty());
+    }
+}
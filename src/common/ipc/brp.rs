@@ -0,0 +1,362 @@
+//! Which pages may call the Bevy Remote Protocol through `window.cef.brp`.
+//!
+//! Every call arrives as a [`WebviewBrpRequest`] tagged with its webview and
+//! the calling frame's origin. [`forward_brp_requests`] checks it against the
+//! webview's bridge policy, then against its [`WebviewBrpPolicy`], or
+//! [`DefaultWebviewBrpPolicy`] when the webview has none, and only then hands it
+//! to `bevy_remote`. Denied calls
+//! reject in the page with [`BRP_ACCESS_DENIED`].
+
+use crate::common::BridgeAccess;
+use crate::diagnostics::WebviewMetrics;
+use async_channel::{Receiver, Sender};
+use bevy::platform::time::Instant;
+use bevy::prelude::*;
+use bevy::tasks::IoTaskPool;
+use bevy_cef_core::prelude::{WebviewBrpRequest, WebviewBrpSenderInner, origin_allowed};
+use bevy_remote::{BrpError, BrpMessage, BrpSender, error_codes};
+use serde_json::Value;
+use std::time::Duration;
+
+/// The BRP error code of calls denied by a [`WebviewBrpPolicy`].
+pub const BRP_ACCESS_DENIED: i16 = -32000;
+
+/// The built-in methods that name the components they touch, the only ones a
+/// [`WebviewBrpPolicy`] with a component allowlist admits.
+const COMPONENT_METHODS: [&str; 7] = [
+    "world.get_components",
+    "world.get_components+watch",
+    "world.query",
+    "world.spawn_entity",
+    "world.insert_components",
+    "world.remove_components",
+    "world.mutate_components",
+];
+
+pub(crate) struct BrpPolicyPlugin;
+
+impl Plugin for BrpPolicyPlugin {
+    fn build(&self, app: &mut App) {
+        let (tx, rx) = async_channel::unbounded();
+        app.insert_resource(WebviewBrpSender(tx))
+            .insert_resource(WebviewBrpReceiver(rx))
+            .init_resource::<DefaultWebviewBrpPolicy>()
+            .register_type::<WebviewBrpPolicy>()
+            .register_type::<DefaultWebviewBrpPolicy>()
+            .add_systems(PreUpdate, forward_brp_requests);
+    }
+}
+
+/// What a webview's pages may do through `window.cef.brp`.
+///
+/// Webviews without one use [`DefaultWebviewBrpPolicy`], which only admits
+/// pages served from `cef://localhost`.
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_cef::prelude::*;
+///
+/// fn spawn_scoreboard(mut commands: Commands) {
+///     commands.spawn((
+///         WebviewSource::new("https://scores.example.com"),
+///         WebviewBrpPolicy::allow_origins(["https://scores.example.com"])
+///             .with_methods(["world.query"])
+///             .with_components(["game::Score"]),
+///     ));
+/// }
+/// ```
+#[derive(Component, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Component, Debug, Default)]
+pub struct WebviewBrpPolicy {
+    /// Origins allowed to call BRP, written `scheme://host[:port]`, e.g.
+    /// `https://example.com`. `*` admits every origin.
+    pub origins: Vec<String>,
+    /// BRP methods the allowed origins may call. `None` allows all of them.
+    pub methods: Option<Vec<String>>,
+    /// Component type paths the calls may name, e.g. `game::Score`. `None`
+    /// allows all of them. When set, only calls to the `world.*` methods that
+    /// name components are allowed, and each must name at least one.
+    pub components: Option<Vec<String>>,
+}
+
+impl Default for WebviewBrpPolicy {
+    /// Admits `cef://localhost` with every method and component.
+    fn default() -> Self {
+        Self::allow_origins(["cef://localhost"])
+    }
+}
+
+impl WebviewBrpPolicy {
+    /// Denies BRP to every page.
+    pub fn deny_all() -> Self {
+        Self::allow_origins(Vec::<String>::new())
+    }
+
+    /// Admits every origin with every method and component.
+    pub fn allow_all() -> Self {
+        Self::allow_origins(["*"])
+    }
+
+    /// Admits `origins` with every method and component.
+    pub fn allow_origins(origins: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            origins: origins.into_iter().map(Into::into).collect(),
+            methods: None,
+            components: None,
+        }
+    }
+
+    /// Restricts the allowed origins to `methods`.
+    pub fn with_methods(mut self, methods: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.methods = Some(methods.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Restricts the allowed origins to naming `components`.
+    pub fn with_components(
+        mut self,
+        components: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.components = Some(components.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Returns why a call from `origin` is denied, if it is.
+    fn check(&self, origin: &str, method: &str, params: Option<&Value>) -> Result<(), String> {
+        if !origin_allowed(&self.origins, origin) {
+            return Err(format!("origin {origin} may not call BRP"));
+        }
+        if let Some(methods) = &self.methods
+            && !methods.iter().any(|m| m == method)
+        {
+            return Err(format!("method {method} is not allowed for {origin}"));
+        }
+        let Some(components) = &self.components else {
+            return Ok(());
+        };
+        let requested = requested_components(params);
+        if !COMPONENT_METHODS.contains(&method) || requested.is_empty() {
+            return Err(format!(
+                "{origin} may only call methods that name allowed components"
+            ));
+        }
+        if let Some(denied) = requested
+            .into_iter()
+            .find(|c| !components.iter().any(|allowed| allowed == c))
+        {
+            return Err(format!("component {denied} is not allowed for {origin}"));
+        }
+        Ok(())
+    }
+}
+
+/// The [`WebviewBrpPolicy`] of webviews that have none.
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq, Deref, DerefMut, Reflect)]
+#[reflect(Resource, Debug, Default)]
+pub struct DefaultWebviewBrpPolicy(pub WebviewBrpPolicy);
+
+#[derive(Resource, Debug, Deref)]
+pub(crate) struct WebviewBrpSender(pub(crate) WebviewBrpSenderInner);
+
+#[derive(Resource, Debug)]
+struct WebviewBrpReceiver(Receiver<WebviewBrpRequest>);
+
+fn forward_brp_requests(
+    receiver: Res<WebviewBrpReceiver>,
+    brp: Res<BrpSender>,
+    default_policy: Res<DefaultWebviewBrpPolicy>,
+    policies: Query<&WebviewBrpPolicy>,
+    bridge: BridgeAccess,
+    metrics: Option<Res<WebviewMetrics>>,
+) {
+    while let Ok(request) = receiver.0.try_recv() {
+        let _span = info_span!(
+            "webview_ipc_dispatch",
+            webview = ?request.webview,
+            method = %request.message.method
+        )
+        .entered();
+        let policy = policies.get(request.webview).unwrap_or(&default_policy.0);
+        let message = request.message;
+        let checked = if bridge.admits(request.webview, &request.origin, "cef.brp") {
+            policy.check(&request.origin, &message.method, message.params.as_ref())
+        } else {
+            Err(format!("origin {} may not use window.cef", request.origin))
+        };
+        match checked {
+            Ok(()) => {
+                let message = match &metrics {
+                    Some(metrics) => timed(message, metrics.brp_round_trips()),
+                    None => message,
+                };
+                if let Err(e) = brp.try_send(message) {
+                    warn!("bevy_cef: dropped BRP call from {}: {e}", request.webview);
+                    reject(
+                        e.into_inner(),
+                        error_codes::INTERNAL_ERROR,
+                        "BRP is not accepting requests".to_string(),
+                    );
+                }
+            }
+            Err(reason) => {
+                warn!(
+                    "bevy_cef: denied BRP call from {}: {reason}",
+                    request.webview
+                );
+                reject(
+                    message,
+                    BRP_ACCESS_DENIED,
+                    format!("BRP access denied: {reason}"),
+                );
+            }
+        }
+    }
+}
+
+/// Rejects `message`'s call in the page.
+fn reject(message: BrpMessage, code: i16, reason: String) {
+    let _ = message.sender.try_send(Err(BrpError {
+        code,
+        message: reason,
+        data: None,
+    }));
+}
+
+/// Passes the answers to `message` on to the page through a task that reports
+/// how long the first one took.
+fn timed(mut message: BrpMessage, round_trips: Sender<Duration>) -> BrpMessage {
+    let (tx, rx) = async_channel::unbounded();
+    let page = std::mem::replace(&mut message.sender, tx);
+    let start = Instant::now();
+    IoTaskPool::get()
+        .spawn(async move {
+            let mut first = true;
+            while let Ok(result) = rx.recv().await {
+                if std::mem::take(&mut first) {
+                    let _ = round_trips.try_send(start.elapsed());
+                }
+                if page.send(result).await.is_err() {
+                    break;
+                }
+            }
+        })
+        .detach();
+    message
+}
+
+/// The component type paths named by BRP `params`, as the built-in `world.*`
+/// methods take them: `component`, `components` (a list or a map keyed by
+/// type path), `data.components`/`option`/`has` and `filter.with`/`without`.
+fn requested_components(params: Option<&Value>) -> Vec<&str> {
+    let mut names = Vec::new();
+    let Some(params) = params else {
+        return names;
+    };
+    let lists = [
+        params.get("component"),
+        params.get("components"),
+        params.pointer("/data/components"),
+        params.pointer("/data/option"),
+        params.pointer("/data/has"),
+        params.pointer("/filter/with"),
+        params.pointer("/filter/without"),
+    ];
+    for value in lists.into_iter().flatten() {
+        match value {
+            Value::String(name) => names.push(name.as_str()),
+            Value::Array(items) => names.extend(items.iter().filter_map(Value::as_str)),
+            Value::Object(map) => names.extend(map.keys().map(String::as_str)),
+            _ => {}
+        }
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn default_policy_admits_only_cef_localhost() {
+        let policy = WebviewBrpPolicy::default();
+        assert!(policy.check("cef://localhost", "world.query", None).is_ok());
+        assert!(
+            policy
+                .check("https://example.com", "world.query", None)
+                .is_err()
+        );
+        assert!(
+            WebviewBrpPolicy::deny_all()
+                .check("cef://localhost", "greet", None)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn methods_and_components_are_allowlisted() {
+        let policy = WebviewBrpPolicy::allow_all()
+            .with_methods(["world.get_components", "world.query"])
+            .with_components(["game::Score"]);
+        let score = json!({ "entity": 4, "components": ["game::Score"] });
+        let transform = json!({ "data": { "components": ["bevy_transform::components::transform::Transform"] } });
+
+        assert!(
+            policy
+                .check("https://a.com", "world.get_components", Some(&score))
+                .is_ok()
+        );
+        assert!(
+            policy
+                .check("https://a.com", "world.despawn_entity", None)
+                .is_err()
+        );
+        assert!(
+            policy
+                .check("https://a.com", "world.query", Some(&transform))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn component_allowlists_deny_calls_naming_no_components() {
+        let policy = WebviewBrpPolicy::allow_all().with_components(["game::Score"]);
+
+        assert!(
+            policy
+                .check(
+                    "https://a.com",
+                    "world.despawn_entity",
+                    Some(&json!({ "entity": 4 }))
+                )
+                .is_err()
+        );
+        assert!(
+            policy
+                .check(
+                    "https://a.com",
+                    "world.get_resources",
+                    Some(&json!({ "resource": "game::Score" }))
+                )
+                .is_err()
+        );
+        assert!(
+            policy
+                .check(
+                    "https://a.com",
+                    "world.spawn_entity",
+                    Some(&json!({ "components": {} }))
+                )
+                .is_err()
+        );
+        assert!(
+            policy
+                .check(
+                    "https://a.com",
+                    "world.spawn_entity",
+                    Some(&json!({ "components": { "game::Score": 0 } }))
+                )
+                .is_ok()
+        );
+    }
+}
@@ -0,0 +1,58 @@
+//! Internal commands sent from page JavaScript to bevy_cef's own plugins.
+//!
+//! Pages reach these through helpers on `window.cef` (for example
+//! `cef.gamepad.release()`); each call arrives as a [`ControlMessage`] on a
+//! channel separate from `cef.emit`, so user `JsEmitEventPlugin` receivers
+//! never see them. [`drain_control_messages`] republishes them as
+//! [`WebviewControl`] messages for the interested plugins, dropping those from
+//! origins the webview's bridge policy does not admit.
+
+use crate::common::BridgeAccess;
+use async_channel::Receiver;
+use bevy::prelude::*;
+use bevy_cef_core::prelude::{ControlMessage, ControlMessageSenderInner};
+
+pub(crate) struct ControlPlugin;
+
+impl Plugin for ControlPlugin {
+    fn build(&self, app: &mut App) {
+        let (tx, rx) = async_channel::unbounded();
+        app.insert_resource(ControlMessageSender(tx))
+            .insert_resource(ControlMessageReceiver(rx))
+            .add_message::<WebviewControl>()
+            .add_systems(PreUpdate, drain_control_messages);
+    }
+}
+
+/// A control command issued by a webview's page.
+#[derive(Message, Debug, Clone, PartialEq, Eq)]
+pub(crate) struct WebviewControl {
+    pub webview: Entity,
+    /// Dotted command name, e.g. `gamepad.release`.
+    pub name: String,
+    /// JSON-encoded argument; `null` when the command takes none.
+    pub payload: String,
+}
+
+#[derive(Resource, Debug, Deref)]
+pub(crate) struct ControlMessageSender(pub(crate) ControlMessageSenderInner);
+
+#[derive(Resource, Debug)]
+struct ControlMessageReceiver(Receiver<ControlMessage>);
+
+pub(crate) fn drain_control_messages(
+    receiver: Res<ControlMessageReceiver>,
+    mut writer: MessageWriter<WebviewControl>,
+    bridge: BridgeAccess,
+) {
+    while let Ok(msg) = receiver.0.try_recv() {
+        if !bridge.admits(msg.webview, &msg.origin, &msg.name) {
+            continue;
+        }
+        writer.write(WebviewControl {
+            webview: msg.webview,
+            name: msg.name,
+            payload: msg.payload,
+        });
+    }
+}
@@ -1,8 +1,7 @@
+use crate::diagnostics::WebviewMetrics;
+use crate::webview::WebviewBrowsers;
+use bevy::log::tracing::span::EnteredSpan;
 use bevy::prelude::*;
-#[cfg(not(target_os = "windows"))]
-use bevy_cef_core::prelude::Browsers;
-#[cfg(target_os = "windows")]
-use bevy_cef_core::prelude::BrowsersProxy;
 use serde::{Deserialize, Serialize};
 
 /// A trigger event to emit an event from the host to the webview.
@@ -34,24 +33,30 @@ impl Plugin for HostEmitPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<HostEmitEvent>();
 
-        #[cfg(not(target_os = "windows"))]
         app.add_observer(host_emit);
-
-        #[cfg(target_os = "windows")]
-        app.add_observer(host_emit_win);
     }
 }
 
-#[cfg(not(target_os = "windows"))]
-fn host_emit(trigger: On<HostEmitEvent>, browsers: NonSend<Browsers>) {
-    if let Ok(v) = serde_json::to_value(&trigger.payload) {
-        browsers.emit_event(&trigger.webview, trigger.id.clone(), &v);
-    }
+fn emit_span(event: &HostEmitEvent) -> EnteredSpan {
+    info_span!(
+        "webview_ipc_dispatch",
+        webview = ?event.webview,
+        id = %event.id,
+        bytes = event.payload.len()
+    )
+    .entered()
 }
 
-#[cfg(target_os = "windows")]
-fn host_emit_win(trigger: On<HostEmitEvent>, proxy: Res<BrowsersProxy>) {
+fn host_emit(
+    trigger: On<HostEmitEvent>,
+    browsers: NonSend<WebviewBrowsers>,
+    metrics: Option<ResMut<WebviewMetrics>>,
+) {
+    let _span = emit_span(&trigger);
+    if let Some(mut metrics) = metrics {
+        metrics.ipc_out(trigger.payload.len());
+    }
     if let Ok(v) = serde_json::to_value(&trigger.payload) {
-        proxy.emit_event(&trigger.webview, trigger.id.clone(), &v);
+        browsers.emit_event(&trigger.webview, &trigger.id, &v);
     }
 }
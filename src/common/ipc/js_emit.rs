@@ -1,3 +1,5 @@
+use crate::common::BridgeAccess;
+use crate::diagnostics::WebviewMetrics;
 use async_channel::{Receiver, Sender};
 use bevy::prelude::*;
 use bevy_cef_core::prelude::*;
@@ -49,8 +51,22 @@ impl<E: DeserializeOwned> Default for JsEmitEventPlugin<E> {
 fn receive_events<E: DeserializeOwned + Send + Sync + 'static>(
     mut commands: Commands,
     receiver: ResMut<IpcEventRawReceiver>,
+    bridge: BridgeAccess,
+    mut metrics: Option<ResMut<WebviewMetrics>>,
 ) {
     while let Ok(event) = receiver.0.try_recv() {
+        let _span = info_span!(
+            "webview_ipc_dispatch",
+            webview = ?event.webview,
+            bytes = event.payload.len()
+        )
+        .entered();
+        if !bridge.admits(event.webview, &event.origin, "cef.emit") {
+            continue;
+        }
+        if let Some(metrics) = metrics.as_deref_mut() {
+            metrics.ipc_in(event.payload.len());
+        }
         if let Ok(payload) = serde_json::from_str::<E>(&event.payload) {
             commands.trigger(Receive {
                 webview: event.webview,
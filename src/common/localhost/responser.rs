@@ -1,15 +1,23 @@
 use crate::common::localhost::asset_loader::CefResponseHandle;
 use crate::common::{ResolvedWebviewUri, WebviewSource};
+use crate::diagnostics::WebviewMetrics;
+use crate::webview::WebviewBrowsers;
 use bevy::platform::collections::{HashMap, HashSet};
+use bevy::platform::time::Instant;
 use bevy::prelude::*;
 use bevy_cef_core::prelude::*;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
 
 static INLINE_ID_COUNTER: AtomicU64 = AtomicU64::new(0);
 
 /// Prefix for inline HTML URIs within the `cef://localhost/` scheme.
 const INLINE_PREFIX: &str = "__inline__/";
 
+/// When a pending `cef://localhost` request reached Bevy.
+#[derive(Component)]
+struct RequestedAt(Instant);
+
 /// Cleanup marker that stays on the entity. Removed on despawn to clean up the store.
 #[derive(Component)]
 pub(crate) struct InlineHtmlId(pub(crate) String);
@@ -37,11 +45,7 @@ impl Plugin for ResponserPlugin {
             .add_systems(PreUpdate, resolve_webview_source)
             .add_systems(Update, (coming_request, responser));
 
-        #[cfg(not(target_os = "windows"))]
         app.add_systems(Update, hot_reload.run_if(any_changed_assets));
-
-        #[cfg(target_os = "windows")]
-        app.add_systems(Update, hot_reload_win.run_if(any_changed_assets));
     }
 }
 
@@ -89,6 +93,7 @@ fn coming_request(
     requester_receiver: Res<RequesterReceiver>,
     asset_server: Res<AssetServer>,
     store: Res<InlineHtmlStore>,
+    mut metrics: Option<ResMut<WebviewMetrics>>,
 ) {
     while let Ok(request) = requester_receiver.0.try_recv() {
         if let Some(id) = extract_inline_id(&request.uri) {
@@ -105,10 +110,14 @@ fn coming_request(
                 },
             };
             let _ = request.responser.0.send_blocking(response);
+            if let Some(metrics) = metrics.as_deref_mut() {
+                metrics.localhost_answered(Duration::ZERO);
+            }
         } else {
             commands.spawn((
                 CefResponseHandle(asset_server.load(request.uri)),
                 request.responser,
+                RequestedAt(Instant::now()),
             ));
         }
     }
@@ -127,9 +136,10 @@ fn responser(
     mut handle_stores: Local<HashSet<Handle<CefResponse>>>,
     responses: Res<Assets<CefResponse>>,
     asset_server: Res<AssetServer>,
-    handles: Query<(Entity, &CefResponseHandle, &Responser)>,
+    handles: Query<(Entity, &CefResponseHandle, &Responser, &RequestedAt)>,
+    mut metrics: Option<ResMut<WebviewMetrics>>,
 ) {
-    for (entity, handle, responser) in handles.iter() {
+    for (entity, handle, responser, requested_at) in handles.iter() {
         if let Some(response) = responses.get(&handle.0) {
             let _ = responser.0.send_blocking(response.clone());
             commands.entity(entity).despawn();
@@ -145,16 +155,15 @@ fn responser(
                 data: b"Asset load failed".to_vec(),
             });
             commands.entity(entity).despawn();
+        } else {
+            continue;
+        }
+        if let Some(metrics) = metrics.as_deref_mut() {
+            metrics.localhost_answered(requested_at.0.elapsed());
         }
     }
 }
 
-#[cfg(not(target_os = "windows"))]
-fn hot_reload(browsers: NonSend<Browsers>) {
+fn hot_reload(browsers: NonSend<WebviewBrowsers>) {
     browsers.reload();
 }
-
-#[cfg(target_os = "windows")]
-fn hot_reload_win(proxy: Res<BrowsersProxy>) {
-    proxy.reload();
-}
@@ -1,4 +1,6 @@
 use crate::RunOnMainThread;
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+use crate::prelude::WebviewBrowsers;
 use bevy::prelude::*;
 use bevy_cef_core::prelude::*;
 use cef::args::Args;
@@ -6,18 +8,22 @@ use cef::{Settings, api_hash, execute_process, initialize, shutdown, sys};
 
 /// Controls the CEF message loop.
 ///
-/// On macOS (and future Linux), uses `external_message_pump` and calls
+/// On macOS and Linux, uses `external_message_pump` and calls
 /// [`CefDoMessageLoopWork`](https://cef-builds.spotifycdn.com/docs/106.1/cef__app_8h.html#a830ae43dcdffcf4e719540204cefdb61)
 /// every frame.
 ///
-/// On Windows, uses `multi_threaded_message_loop` where CEF owns its own UI
-/// thread. Bevy systems communicate with CEF through [`BrowsersProxy`] and a
-/// command channel instead of calling CEF APIs directly.
+/// On Windows, and on Linux with `multi_threaded_message_loop`, uses
+/// `multi_threaded_message_loop` where CEF owns its own UI thread. Bevy systems
+/// still go through `WebviewBrowsers`, which forwards each call to CEF over
+/// [`BrowsersProxy`] and a command channel instead of calling CEF APIs directly.
 pub struct MessageLoopPlugin {
     pub config: CommandLineConfig,
     pub extensions: CefExtensions,
     pub root_cache_path: Option<String>,
     pub no_sandbox: bool,
+    /// Runs CEF on its own UI thread on Linux. Windows always does; ignored on
+    /// macOS.
+    pub multi_threaded_message_loop: bool,
 }
 
 impl Plugin for MessageLoopPlugin {
@@ -30,10 +36,13 @@ impl Plugin for MessageLoopPlugin {
 
         let _ = api_hash(sys::CEF_API_VERSION_LAST, 0);
         let args = Args::new();
+        let multi_threaded = cfg!(target_os = "windows")
+            || (cfg!(target_os = "linux") && self.multi_threaded_message_loop);
 
-        // On Windows with multi_threaded_message_loop, the on_schedule_message_pump_work
-        // callback is never invoked by CEF, so we create a dummy channel. The sender
-        // is never used but BrowserProcessAppBuilder::build() still requires it.
+        // With multi_threaded_message_loop, the on_schedule_message_pump_work
+        // callback is never invoked by CEF, so on Windows we create a dummy channel.
+        // The sender is never used but BrowserProcessAppBuilder::build() still
+        // requires it. Linux drops the receiver when it runs multi-threaded.
         #[cfg(target_os = "windows")]
         let (tx, _rx) = std::sync::mpsc::channel();
         #[cfg(not(target_os = "windows"))]
@@ -64,6 +73,7 @@ impl Plugin for MessageLoopPlugin {
             self.root_cache_path.as_deref(),
             render_process_binary.as_deref(),
             self.no_sandbox,
+            multi_threaded,
         );
         #[cfg(target_os = "macos")]
         cef_initialize(
@@ -75,25 +85,29 @@ impl Plugin for MessageLoopPlugin {
 
         app.insert_non_send(cef_app);
 
-        // On Windows, CEF runs its own message loop thread (multi_threaded_message_loop).
-        // We insert a BrowsersProxy and CommandChannelReceiver instead of the
-        // external-pump timer infrastructure.
-        // We also create the texture delivery channel here so the receiver side
-        // is available to Bevy systems, and the sender can be passed to
-        // `init_cef_browsers()` on the CEF UI thread.
-        #[cfg(target_os = "windows")]
-        {
+        // With multi_threaded_message_loop, always on Windows and on Linux when
+        // opted in, CEF runs its own UI thread. WebviewBrowsers becomes a proxy
+        // that enqueues CefCommands through a BrowsersProxy, so every system
+        // written against WebviewBackend runs unchanged. Frames come back over a
+        // texture channel whose sender is passed to `init_cef_browsers()` on the
+        // CEF UI thread.
+        #[cfg(any(target_os = "windows", target_os = "linux"))]
+        if multi_threaded {
             let (cmd_tx, cmd_rx) = async_channel::unbounded::<CefCommand>();
             let (tex_tx, tex_rx) = async_channel::unbounded::<RenderTextureMessage>();
-            app.insert_resource(BrowsersProxy::new(cmd_tx));
+            let proxy = BrowsersProxy::new(cmd_tx);
+            app.insert_non_send(WebviewBrowsers(Box::new(ProxyBackend::new(
+                proxy.clone(),
+                tex_rx,
+            ))));
+            app.insert_resource(proxy);
             app.insert_resource(CommandChannelReceiver(cmd_rx));
-            app.insert_resource(TextureReceiverRes(tex_rx));
             app.insert_resource(TextureSenderRes(tex_tx));
         }
 
-        // On non-Windows platforms, use the external message pump.
+        // Otherwise, use the external message pump.
         #[cfg(not(target_os = "windows"))]
-        {
+        if !multi_threaded {
             app.insert_non_send(MessageLoopWorkingReceiver(rx));
             app.add_systems(Main, cef_do_message_loop_work);
 
@@ -168,6 +182,7 @@ fn cef_initialize(
     root_cache_path: Option<&str>,
     render_process_binary: Option<&std::path::Path>,
     no_sandbox: bool,
+    multi_threaded: bool,
 ) {
     // Ensure the cache directory exists before CEF tries to use it.
     // Empty/whitespace paths are valid (CEF treats them as "use default"), so skip those.
@@ -186,10 +201,8 @@ fn cef_initialize(
         no_sandbox: no_sandbox as _,
         root_cache_path: root_cache_path.unwrap_or_default().into(),
         windowless_rendering_enabled: true as _,
-        #[cfg(target_os = "windows")]
-        multi_threaded_message_loop: true as _,
-        #[cfg(not(target_os = "windows"))]
-        external_message_pump: true as _,
+        multi_threaded_message_loop: multi_threaded as _,
+        external_message_pump: !multi_threaded as _,
         disable_signal_handlers: false as _,
         ..Default::default()
     };
@@ -207,28 +220,20 @@ fn cef_initialize(
 
 /// Receives [`CefCommand`]s from the [`BrowsersProxy`] resource.
 ///
-/// Inserted as a Bevy [`Resource`] on Windows where the multi-threaded message
-/// loop architecture is used. The CEF-side drain task reads from the receiver
-/// end to execute commands on the CEF UI thread.
-#[cfg(target_os = "windows")]
+/// Inserted as a Bevy [`Resource`] on Windows, and on Linux when opted in, where
+/// the multi-threaded message loop architecture is used. The CEF-side drain
+/// task reads from the receiver end to execute commands on the CEF UI thread.
+#[cfg(any(target_os = "windows", target_os = "linux"))]
 #[derive(Resource)]
 pub struct CommandChannelReceiver(pub async_channel::Receiver<CefCommand>);
 
-/// Holds the receiver end of the texture delivery channel on Windows.
-///
-/// On macOS/Linux the receiver lives inside `NonSend<Browsers>`, but on Windows
-/// `Browsers` is not initialised on Bevy's main thread.  This resource makes
-/// the receiver available to the `send_render_textures` system.
-#[cfg(target_os = "windows")]
-#[derive(Resource)]
-pub struct TextureReceiverRes(pub async_channel::Receiver<RenderTextureMessage>);
-
-/// Holds the sender end of the texture delivery channel on Windows.
+/// Holds the sender end of the texture delivery channel of the multi-threaded
+/// message loop.
 ///
 /// This is inserted as a Bevy resource so that it can later be passed to
 /// `init_cef_browsers()` on the CEF UI thread to wire up the
 /// `BrowsersCefSide` texture delivery path.
-#[cfg(target_os = "windows")]
+#[cfg(any(target_os = "windows", target_os = "linux"))]
 #[derive(Resource)]
 pub struct TextureSenderRes(pub async_channel::Sender<RenderTextureMessage>);
 
@@ -255,6 +260,7 @@ fn cef_do_message_loop_work(
             return;
         }
         *last_execution = Some(now);
+        let _span = info_span!("cef_do_message_loop_work").entered();
         cef::do_message_loop_work();
         *max_delay_timer = MessageLoopWorkingMaxDelayTimer::default();
         timer.take();
@@ -0,0 +1,51 @@
+//! Page console output.
+//!
+//! Everything a page logs with `console.*` is still written to Bevy's log, and
+//! also fires a [`WebviewConsoleMessage`] on the webview.
+
+use async_channel::Receiver;
+use bevy::prelude::*;
+use bevy_cef_core::prelude::{ConsoleMessage, ConsoleMessageSenderInner, WebviewConsoleLevel};
+use serde::{Deserialize, Serialize};
+
+pub(super) struct ConsolePlugin;
+
+impl Plugin for ConsolePlugin {
+    fn build(&self, app: &mut App) {
+        let (tx, rx) = async_channel::unbounded();
+        app.insert_resource(ConsoleMessageSender(tx))
+            .insert_resource(ConsoleMessageReceiver(rx))
+            .register_type::<WebviewConsoleMessage>()
+            .add_systems(PreUpdate, drain_console_messages);
+    }
+}
+
+/// Fired when a webview's page logs to its console.
+#[derive(Debug, EntityEvent, Clone, Reflect, Serialize, Deserialize)]
+pub struct WebviewConsoleMessage {
+    #[event_target]
+    pub webview: Entity,
+    pub level: WebviewConsoleLevel,
+    pub message: String,
+    /// The script that logged the message.
+    pub source: String,
+    pub line: i32,
+}
+
+#[derive(Resource, Debug, Deref)]
+pub(crate) struct ConsoleMessageSender(pub(crate) ConsoleMessageSenderInner);
+
+#[derive(Resource, Debug)]
+struct ConsoleMessageReceiver(Receiver<ConsoleMessage>);
+
+fn drain_console_messages(mut commands: Commands, receiver: Res<ConsoleMessageReceiver>) {
+    while let Ok(msg) = receiver.0.try_recv() {
+        commands.trigger(WebviewConsoleMessage {
+            webview: msg.webview,
+            level: msg.level,
+            message: msg.message,
+            source: msg.source,
+            line: msg.line,
+        });
+    }
+}
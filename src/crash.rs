@@ -0,0 +1,342 @@
+//! Render-process crashes and hangs.
+//!
+//! When a webview's render process dies, [`WebviewCrashed`] fires and the
+//! webview's [`CrashRecoveryPolicy`] decides what happens next. A watchdog
+//! pings each live page's main thread every
+//! [`WebviewWatchdog::ping_interval`]; a page that leaves a ping unanswered for
+//! [`WebviewWatchdog::timeout`] fires [`WebviewUnresponsive`], and
+//! [`WebviewResponsive`] once it answers again.
+
+use crate::common::{ResolvedWebviewUri, WebviewSource};
+use crate::navigation::{LoadStarted, RequestNavigate, RequestReload};
+use crate::webview::WebviewBrowsers;
+use async_channel::Receiver;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use bevy_cef_core::prelude::{
+    RenderProcessEvent, RenderProcessMessage, RenderProcessSenderInner, WebviewTermination,
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Fired when a webview's render process terminates.
+///
+/// The webview keeps showing its last frame and page IPC stops until the page
+/// is reloaded or navigated, which [`CrashRecoveryPolicy`] can do
+/// automatically.
+#[derive(Debug, EntityEvent, Copy, Clone, Reflect, Serialize, Deserialize)]
+pub struct WebviewCrashed {
+    #[event_target]
+    pub webview: Entity,
+    pub status: WebviewTermination,
+    pub error_code: i32,
+}
+
+/// Fired when a webview's page has not answered a watchdog ping within
+/// [`WebviewWatchdog::timeout`], typically because a script is stuck in a long
+/// loop.
+#[derive(Debug, EntityEvent, Copy, Clone, Reflect, Serialize, Deserialize)]
+pub struct WebviewUnresponsive {
+    #[event_target]
+    pub webview: Entity,
+}
+
+/// Fired when a page reported by [`WebviewUnresponsive`] answers again.
+#[derive(Debug, EntityEvent, Copy, Clone, Reflect, Serialize, Deserialize)]
+pub struct WebviewResponsive {
+    #[event_target]
+    pub webview: Entity,
+}
+
+/// What to do when the webview's render process terminates.
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_cef::prelude::*;
+///
+/// fn spawn_hud(mut commands: Commands) {
+///     commands.spawn((
+///         WebviewSource::new("https://example.com/hud"),
+///         CrashRecoveryPolicy::ErrorPage("crashed.html".to_string()),
+///     ));
+/// }
+/// ```
+#[derive(Component, Debug, Default, Clone, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default)]
+pub enum CrashRecoveryPolicy {
+    /// Leave the webview showing its last frame.
+    #[default]
+    Ignore,
+    /// Reload the page that crashed.
+    Reload,
+    /// Navigate to `cef://localhost/<path>`. The termination is appended as a
+    /// fragment such as `#reason=crashed&code=-1`.
+    ErrorPage(String),
+}
+
+/// Settings of the unresponsive-page watchdog.
+#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Resource, Debug, Default)]
+pub struct WebviewWatchdog {
+    /// Ping pages at all. Defaults to `true`.
+    pub enabled: bool,
+    /// How often each page is pinged. Defaults to one second.
+    pub ping_interval: Duration,
+    /// How long a ping may go unanswered before the page counts as
+    /// unresponsive. Defaults to five seconds.
+    pub timeout: Duration,
+}
+
+impl Default for WebviewWatchdog {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ping_interval: Duration::from_secs(1),
+            timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+/// Marks a webview whose render process is gone until its next page load.
+#[derive(Component, Debug)]
+struct Crashed;
+
+pub(crate) struct CrashPlugin;
+
+impl Plugin for CrashPlugin {
+    fn build(&self, app: &mut App) {
+        let (tx, rx) = async_channel::unbounded();
+        app.insert_resource(RenderProcessSender(tx))
+            .insert_resource(RenderProcessReceiver(rx))
+            .init_resource::<WebviewWatchdog>()
+            .init_resource::<PingStates>()
+            .register_type::<WebviewCrashed>()
+            .register_type::<WebviewUnresponsive>()
+            .register_type::<WebviewResponsive>()
+            .register_type::<CrashRecoveryPolicy>()
+            .register_type::<WebviewWatchdog>()
+            .add_observer(apply_crash_recovery)
+            .add_observer(reset_on_load)
+            .add_systems(PreUpdate, drain_render_process_events);
+
+        app.add_systems(Update, ping_webviews);
+    }
+}
+
+#[derive(Resource, Debug, Deref)]
+pub(crate) struct RenderProcessSender(pub(crate) RenderProcessSenderInner);
+
+#[derive(Resource, Debug)]
+struct RenderProcessReceiver(Receiver<RenderProcessMessage>);
+
+/// Watchdog state of every pinged webview.
+#[derive(Resource, Default, Debug)]
+struct PingStates(HashMap<Entity, PingState>);
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct PingState {
+    since_ping: Duration,
+    /// How long the outstanding ping has gone unanswered.
+    waiting: Option<Duration>,
+    unresponsive: bool,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct PingTick {
+    send_ping: bool,
+    became_unresponsive: bool,
+}
+
+impl PingState {
+    /// Only one ping is outstanding at a time; a hung page would just queue
+    /// more.
+    fn tick(&mut self, delta: Duration, settings: &WebviewWatchdog) -> PingTick {
+        let mut tick = PingTick::default();
+        if let Some(waiting) = self.waiting.as_mut() {
+            *waiting += delta;
+            if !self.unresponsive && *waiting >= settings.timeout {
+                self.unresponsive = true;
+                tick.became_unresponsive = true;
+            }
+            return tick;
+        }
+        self.since_ping += delta;
+        if self.since_ping >= settings.ping_interval {
+            self.since_ping = Duration::ZERO;
+            self.waiting = Some(Duration::ZERO);
+            tick.send_ping = true;
+        }
+        tick
+    }
+
+    /// Returns `true` when the page was unresponsive until now.
+    fn pong(&mut self) -> bool {
+        self.waiting = None;
+        std::mem::take(&mut self.unresponsive)
+    }
+}
+
+fn drain_render_process_events(
+    mut commands: Commands,
+    receiver: Res<RenderProcessReceiver>,
+    mut states: ResMut<PingStates>,
+) {
+    while let Ok(msg) = receiver.0.try_recv() {
+        let webview = msg.webview;
+        match msg.event {
+            RenderProcessEvent::Terminated {
+                status,
+                error_code,
+                error,
+            } => {
+                warn!("bevy_cef: render process of {webview} terminated ({status:?}): {error}");
+                states.0.remove(&webview);
+                commands.entity(webview).try_insert(Crashed);
+                commands.trigger(WebviewCrashed {
+                    webview,
+                    status,
+                    error_code,
+                });
+            }
+            RenderProcessEvent::Pong => {
+                if let Some(state) = states.0.get_mut(&webview)
+                    && state.pong()
+                {
+                    commands.trigger(WebviewResponsive { webview });
+                }
+            }
+        }
+    }
+}
+
+fn apply_crash_recovery(
+    trigger: On<WebviewCrashed>,
+    mut commands: Commands,
+    policies: Query<&CrashRecoveryPolicy>,
+) {
+    let webview = trigger.webview;
+    match policies.get(webview) {
+        Ok(CrashRecoveryPolicy::Reload) => {
+            commands.trigger(RequestReload { webview });
+        }
+        Ok(CrashRecoveryPolicy::ErrorPage(path)) => {
+            commands.trigger(RequestNavigate {
+                webview,
+                url: error_page_url(path, trigger.status, trigger.error_code),
+            });
+        }
+        Ok(CrashRecoveryPolicy::Ignore) | Err(_) => {}
+    }
+}
+
+/// A new page load may run in a new render process, which drops any ping
+/// still in flight to the old one.
+fn reset_on_load(trigger: On<LoadStarted>, mut commands: Commands, mut states: ResMut<PingStates>) {
+    commands.entity(trigger.webview).try_remove::<Crashed>();
+    if let Some(state) = states.0.get_mut(&trigger.webview) {
+        state.waiting = None;
+    }
+}
+
+fn error_page_url(path: &str, status: WebviewTermination, error_code: i32) -> String {
+    let reason = match status {
+        WebviewTermination::Abnormal => "abnormal",
+        WebviewTermination::Killed => "killed",
+        WebviewTermination::Crashed => "crashed",
+        WebviewTermination::OutOfMemory => "oom",
+        WebviewTermination::LaunchFailed => "launch-failed",
+        WebviewTermination::IntegrityFailure => "integrity-failure",
+    };
+    format!(
+        "cef://localhost/{}#reason={reason}&code={error_code}",
+        path.trim_start_matches('/')
+    )
+}
+
+type PingedWebviews<'w, 's> = Query<
+    'w,
+    's,
+    Entity,
+    (
+        With<WebviewSource>,
+        With<ResolvedWebviewUri>,
+        Without<Crashed>,
+    ),
+>;
+
+/// Advances every live webview's watchdog and returns the webviews to ping.
+fn watchdog_pings(
+    commands: &mut Commands,
+    webviews: &PingedWebviews,
+    settings: &WebviewWatchdog,
+    delta: Duration,
+    states: &mut PingStates,
+) -> Vec<Entity> {
+    if !settings.enabled {
+        states.0.clear();
+        return Vec::new();
+    }
+    states.0.retain(|webview, _| webviews.contains(*webview));
+    let mut pings = Vec::new();
+    for webview in webviews.iter() {
+        let tick = states.0.entry(webview).or_default().tick(delta, settings);
+        if tick.send_ping {
+            pings.push(webview);
+        }
+        if tick.became_unresponsive {
+            commands.trigger(WebviewUnresponsive { webview });
+        }
+    }
+    pings
+}
+
+fn ping_webviews(
+    mut commands: Commands,
+    browsers: NonSend<WebviewBrowsers>,
+    time: Res<Time>,
+    settings: Res<WebviewWatchdog>,
+    webviews: PingedWebviews,
+    mut states: ResMut<PingStates>,
+) {
+    for webview in watchdog_pings(
+        &mut commands,
+        &webviews,
+        &settings,
+        time.delta(),
+        &mut states,
+    ) {
+        browsers.ping(&webview);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unanswered_ping_marks_the_page_unresponsive_until_a_pong() {
+        let settings = WebviewWatchdog {
+            enabled: true,
+            ping_interval: Duration::from_secs(1),
+            timeout: Duration::from_secs(2),
+        };
+        let second = Duration::from_secs(1);
+        let mut state = PingState::default();
+
+        assert!(state.tick(second, &settings).send_ping);
+        assert_eq!(state.tick(second, &settings), PingTick::default());
+        assert!(state.tick(second, &settings).became_unresponsive);
+        assert_eq!(state.tick(second, &settings), PingTick::default());
+        assert!(state.pong());
+        assert!(state.tick(second, &settings).send_ping);
+        assert!(!state.pong());
+    }
+
+    #[test]
+    fn error_page_url_carries_the_termination() {
+        assert_eq!(
+            error_page_url("/errors/crash.html", WebviewTermination::OutOfMemory, 0),
+            "cef://localhost/errors/crash.html#reason=oom&code=0"
+        );
+    }
+}
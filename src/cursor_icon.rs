@@ -1,50 +1,239 @@
-use async_channel::{Receiver, Sender};
+//! Page cursors.
+//!
+//! CEF reports each webview's cursor, including CSS `cursor: url(..)` bitmaps,
+//! tagged with the webview. The cursor of the webview under the mouse is
+//! applied to that webview's host window only; when the mouse leaves every
+//! webview, the window goes back to the default cursor.
+
+use crate::common::{HostWindow, WebviewSource};
+use crate::resize::cursor::SystemCursorOverride;
+use crate::system_param::pointer::hovered_webview;
+use async_channel::Receiver;
+use bevy::asset::RenderAssetUsages;
+use bevy::picking::hover::HoverMap;
+use bevy::platform::collections::HashMap;
 use bevy::prelude::*;
-use bevy::window::{CursorIcon, SystemCursorIcon};
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::window::{CursorIcon, CustomCursor, CustomCursorImage, PrimaryWindow, SystemCursorIcon};
+use bevy_cef_core::prelude::{CursorChangedMessage, CursorChangedSenderInner, WebviewCursor};
 
-/// This plugin manages the system cursor icon by receiving updates from CEF and applying them to the application window's cursor icon.
+/// This plugin manages the cursor icon by receiving updates from CEF and applying them to the host window of the hovered webview.
 pub(super) struct SystemCursorIconPlugin;
 
 impl Plugin for SystemCursorIconPlugin {
     fn build(&self, app: &mut App) {
         let (tx, rx) = async_channel::unbounded();
-        app.insert_resource(SystemCursorIconSender(tx))
-            .insert_resource(SystemCursorIconReceiver(rx))
-            .init_resource::<crate::resize::cursor::SystemCursorOverride>()
-            .add_systems(Update, update_cursor_icon);
+        app.insert_resource(CursorChangedSender(tx))
+            .insert_resource(CursorChangedReceiver(rx))
+            .init_resource::<SystemCursorOverride>()
+            .init_resource::<WebviewCursors>()
+            .add_systems(Update, (receive_cursor_changes, update_cursor_icon).chain());
     }
 }
 
 #[derive(Resource, Debug, Deref)]
-pub(crate) struct SystemCursorIconSender(Sender<SystemCursorIcon>);
+pub(crate) struct CursorChangedSender(pub(crate) CursorChangedSenderInner);
 
 #[derive(Resource, Debug)]
-pub(crate) struct SystemCursorIconReceiver(pub(crate) Receiver<SystemCursorIcon>);
+struct CursorChangedReceiver(Receiver<CursorChangedMessage>);
+
+#[derive(Resource, Default, Debug)]
+struct WebviewCursors {
+    /// The latest cursor each webview asked for.
+    requested: HashMap<Entity, CursorIcon>,
+    /// The cursor bevy_cef last put on each window.
+    applied: HashMap<Entity, CursorIcon>,
+    /// Images of the custom cursors still requested, keyed by size and pixels,
+    /// so a page re-sending the same cursor reuses its asset.
+    images: HashMap<(u32, u32, Vec<u8>), Handle<Image>>,
+}
+
+impl WebviewCursors {
+    fn custom_image(
+        &mut self,
+        images: &mut Assets<Image>,
+        rgba: Vec<u8>,
+        width: u32,
+        height: u32,
+    ) -> Handle<Image> {
+        self.images
+            .entry((width, height, rgba))
+            .or_insert_with_key(|(width, height, rgba)| {
+                images.add(cursor_image(rgba.clone(), *width, *height))
+            })
+            .clone()
+    }
+
+    /// Drops the cached images no webview requests anymore.
+    fn release_unused_images(&mut self) {
+        let requested = &self.requested;
+        self.images.retain(|_, handle| {
+            requested.values().any(|icon| {
+                matches!(
+                    icon,
+                    CursorIcon::Custom(CustomCursor::Image(image)) if image.handle == *handle
+                )
+            })
+        });
+    }
+
+    /// The window cursor changes needed so that only `targets` carry a
+    /// bevy_cef cursor; windows no longer targeted go back to the default.
+    fn changes(&mut self, targets: Vec<(Entity, CursorIcon)>) -> Vec<(Entity, CursorIcon)> {
+        let mut changes = Vec::new();
+        let released: Vec<Entity> = self
+            .applied
+            .keys()
+            .filter(|window| targets.iter().all(|(target, _)| target != *window))
+            .copied()
+            .collect();
+        for window in released {
+            self.applied.remove(&window);
+            changes.push((window, CursorIcon::System(SystemCursorIcon::Default)));
+        }
+        for (window, icon) in targets {
+            if self.applied.get(&window) != Some(&icon) {
+                self.applied.insert(window, icon.clone());
+                changes.push((window, icon));
+            }
+        }
+        changes
+    }
+}
+
+fn receive_cursor_changes(
+    receiver: Res<CursorChangedReceiver>,
+    mut cursors: ResMut<WebviewCursors>,
+    mut images: ResMut<Assets<Image>>,
+    mut removed: RemovedComponents<WebviewSource>,
+) {
+    for webview in removed.read() {
+        cursors.requested.remove(&webview);
+    }
+    while let Ok(msg) = receiver.0.try_recv() {
+        let icon = match msg.cursor {
+            WebviewCursor::System(icon) => CursorIcon::System(icon),
+            WebviewCursor::Custom {
+                rgba,
+                width,
+                height,
+                hotspot,
+            } => CursorIcon::Custom(CustomCursor::Image(CustomCursorImage {
+                handle: cursors.custom_image(&mut images, rgba, width, height),
+                hotspot,
+                ..default()
+            })),
+        };
+        cursors.requested.insert(msg.webview, icon);
+    }
+    cursors.release_unused_images();
+}
+
+fn cursor_image(rgba: Vec<u8>, width: u32, height: u32) -> Image {
+    Image::new(
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        rgba,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::MAIN_WORLD,
+    )
+}
 
 fn update_cursor_icon(
     mut commands: Commands,
-    cursor_icon_receiver: Res<SystemCursorIconReceiver>,
-    cursor_override: Res<crate::resize::cursor::SystemCursorOverride>,
-    windows: Query<Entity>,
+    mut cursors: ResMut<WebviewCursors>,
+    cursor_override: Res<SystemCursorOverride>,
+    hover_map: Res<HoverMap>,
+    parents: Query<(Option<&ChildOf>, Has<WebviewSource>)>,
+    host_windows: Query<&HostWindow>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    windows: Query<Entity, With<Window>>,
 ) {
-    // Override takes priority over CEF cursor.
-    if let Some(override_icon) = cursor_override.get() {
-        for entity in windows.iter() {
-            commands
-                .entity(entity)
-                .try_insert(CursorIcon::System(override_icon));
-        }
-        // Drain the receiver so it doesn't pile up.
-        while cursor_icon_receiver.0.try_recv().is_ok() {}
-        return;
+    let targets = if let Some(override_icon) = cursor_override.get() {
+        // Override takes priority over CEF cursor.
+        windows
+            .iter()
+            .map(|window| (window, CursorIcon::System(override_icon)))
+            .collect()
+    } else {
+        hovered_webview(&hover_map, &parents)
+            .and_then(|webview| {
+                let window = host_windows
+                    .get(webview)
+                    .map(|w| w.0)
+                    .ok()
+                    .or_else(|| primary_window.single().ok())?;
+                let icon = cursors
+                    .requested
+                    .get(&webview)
+                    .cloned()
+                    .unwrap_or(CursorIcon::System(SystemCursorIcon::Default));
+                Some((window, icon))
+            })
+            .into_iter()
+            .collect()
+    };
+    for (window, icon) in cursors.changes(targets) {
+        commands.entity(window).try_insert(icon);
     }
+}
 
-    // Original CEF cursor path.
-    while let Ok(cursor_icon) = cursor_icon_receiver.0.try_recv() {
-        windows.iter().for_each(|window| {
-            commands
-                .entity(window)
-                .try_insert(CursorIcon::System(cursor_icon));
-        });
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_the_hovered_window_keeps_a_cursor() {
+        let a = Entity::from_raw_u32(1).unwrap();
+        let b = Entity::from_raw_u32(2).unwrap();
+        let text = CursorIcon::System(SystemCursorIcon::Text);
+        let mut cursors = WebviewCursors::default();
+
+        assert_eq!(
+            cursors.changes(vec![(a, text.clone())]),
+            vec![(a, text.clone())]
+        );
+        assert!(cursors.changes(vec![(a, text.clone())]).is_empty());
+        assert_eq!(
+            cursors.changes(vec![(b, text.clone())]),
+            vec![
+                (a, CursorIcon::System(SystemCursorIcon::Default)),
+                (b, text.clone()),
+            ]
+        );
+        assert_eq!(
+            cursors.changes(Vec::new()),
+            vec![(b, CursorIcon::System(SystemCursorIcon::Default))]
+        );
+    }
+
+    #[test]
+    fn repeated_custom_cursors_share_one_image() {
+        let webview = Entity::from_raw_u32(1).unwrap();
+        let mut images = Assets::<Image>::default();
+        let mut cursors = WebviewCursors::default();
+
+        let first = cursors.custom_image(&mut images, vec![0; 16], 2, 2);
+        let second = cursors.custom_image(&mut images, vec![0; 16], 2, 2);
+        assert_eq!(first, second);
+        assert_eq!(images.len(), 1);
+
+        cursors.requested.insert(
+            webview,
+            CursorIcon::Custom(CustomCursor::Image(CustomCursorImage {
+                handle: first,
+                ..default()
+            })),
+        );
+        cursors.release_unused_images();
+        assert_eq!(cursors.images.len(), 1);
+
+        cursors.requested.remove(&webview);
+        cursors.release_unused_images();
+        assert!(cursors.images.is_empty());
     }
 }
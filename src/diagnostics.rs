@@ -0,0 +1,324 @@
+//! Webview costs in Bevy's [`DiagnosticsStore`].
+//!
+//! [`WebviewDiagnosticsPlugin`] is opt-in: add it next to
+//! [`CefPlugin`](crate::CefPlugin), e.g. with Bevy's `LogDiagnosticsPlugin` to
+//! print the values. While it is added, the systems that deliver paints, IPC,
+//! BRP calls and `cef://localhost` responses count what they handle in
+//! `WebviewMetrics`, and `record_diagnostics` turns the counts into one
+//! measurement per frame.
+//!
+//! The message-loop pump, paint uploads and IPC dispatch are wrapped in
+//! `tracing` spans regardless, so they show up in Tracy and in `bevy/trace`
+//! flamegraphs.
+
+use crate::common::WebviewSource;
+use crate::frame_pacing::WebviewPainted;
+use crate::lifecycle::WebviewLifecycle;
+use async_channel::{Receiver, Sender};
+use bevy::diagnostic::{
+    Diagnostic, DiagnosticPath, Diagnostics, DiagnosticsStore, RegisterDiagnostic,
+};
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+#[cfg(not(target_os = "macos"))]
+use bevy_cef_core::prelude::RenderTextureMessage;
+use std::time::Duration;
+
+/// Adds webview measurements to the [`DiagnosticsStore`].
+///
+/// ```rust,no_run
+/// use bevy::diagnostic::LogDiagnosticsPlugin;
+/// use bevy::prelude::*;
+/// use bevy_cef::prelude::*;
+///
+/// App::new().add_plugins((
+///     DefaultPlugins,
+///     CefPlugin::default(),
+///     WebviewDiagnosticsPlugin,
+///     LogDiagnosticsPlugin::default(),
+/// ));
+/// ```
+pub struct WebviewDiagnosticsPlugin;
+
+impl WebviewDiagnosticsPlugin {
+    /// Webviews with a live browser.
+    pub const BROWSERS: DiagnosticPath = DiagnosticPath::const_new("bevy_cef/browsers");
+    /// Bytes of CPU frames copied into images per frame. Linux and Windows
+    /// only; macOS hands frames to the GPU without a copy.
+    pub const UPLOAD_BYTES: DiagnosticPath = DiagnosticPath::const_new("bevy_cef/upload_bytes");
+    /// Milliseconds from a begin-frame to the paint it produced. Only measured
+    /// where bevy_cef sends begin-frames (macOS, and Linux without
+    /// `multi_threaded_message_loop`).
+    pub const BEGIN_FRAME_LATENCY: DiagnosticPath =
+        DiagnosticPath::const_new("bevy_cef/begin_frame_latency");
+    /// `window.cef.emit` messages received per frame from origins the bridge
+    /// policy admits.
+    pub const IPC_MESSAGES_IN: DiagnosticPath =
+        DiagnosticPath::const_new("bevy_cef/ipc_messages_in");
+    /// Payload bytes of `window.cef.emit` messages received per frame.
+    pub const IPC_BYTES_IN: DiagnosticPath = DiagnosticPath::const_new("bevy_cef/ipc_bytes_in");
+    /// [`HostEmitEvent`](crate::prelude::HostEmitEvent)s sent per frame.
+    pub const IPC_MESSAGES_OUT: DiagnosticPath =
+        DiagnosticPath::const_new("bevy_cef/ipc_messages_out");
+    /// Payload bytes of [`HostEmitEvent`](crate::prelude::HostEmitEvent)s sent
+    /// per frame.
+    pub const IPC_BYTES_OUT: DiagnosticPath = DiagnosticPath::const_new("bevy_cef/ipc_bytes_out");
+    /// Milliseconds from a `window.cef.brp` call reaching Bevy to its answer.
+    pub const BRP_ROUND_TRIP: DiagnosticPath = DiagnosticPath::const_new("bevy_cef/brp_round_trip");
+    /// `cef://localhost` requests answered per frame.
+    pub const LOCALHOST_REQUESTS: DiagnosticPath =
+        DiagnosticPath::const_new("bevy_cef/localhost_requests");
+    /// Milliseconds from a `cef://localhost` request reaching Bevy to its
+    /// answer, e.g. while the asset loads.
+    pub const LOCALHOST_LATENCY: DiagnosticPath =
+        DiagnosticPath::const_new("bevy_cef/localhost_latency");
+
+    /// Paints per second of `webview`, enabled while it has a live browser.
+    pub fn paints_per_second(webview: Entity) -> DiagnosticPath {
+        DiagnosticPath::new(format!("bevy_cef/paints_per_second/{webview}"))
+    }
+}
+
+impl Plugin for WebviewDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_diagnostic(Diagnostic::new(Self::BROWSERS))
+            .register_diagnostic(Diagnostic::new(Self::UPLOAD_BYTES).with_suffix(" B"))
+            .register_diagnostic(Diagnostic::new(Self::BEGIN_FRAME_LATENCY).with_suffix(" ms"))
+            .register_diagnostic(Diagnostic::new(Self::IPC_MESSAGES_IN))
+            .register_diagnostic(Diagnostic::new(Self::IPC_BYTES_IN).with_suffix(" B"))
+            .register_diagnostic(Diagnostic::new(Self::IPC_MESSAGES_OUT))
+            .register_diagnostic(Diagnostic::new(Self::IPC_BYTES_OUT).with_suffix(" B"))
+            .register_diagnostic(Diagnostic::new(Self::BRP_ROUND_TRIP).with_suffix(" ms"))
+            .register_diagnostic(Diagnostic::new(Self::LOCALHOST_REQUESTS))
+            .register_diagnostic(Diagnostic::new(Self::LOCALHOST_LATENCY).with_suffix(" ms"))
+            .init_resource::<WebviewMetrics>()
+            .add_systems(
+                Last,
+                (register_paint_diagnostics, record_diagnostics).chain(),
+            );
+    }
+}
+
+/// What the instrumented systems handled since the last frame. Only present
+/// while [`WebviewDiagnosticsPlugin`] is added, so the systems take it as an
+/// `Option`.
+#[derive(Resource)]
+pub(crate) struct WebviewMetrics {
+    ipc_messages_in: u32,
+    ipc_bytes_in: usize,
+    ipc_messages_out: u32,
+    ipc_bytes_out: usize,
+    localhost_requests: u32,
+    localhost_latency: Vec<Duration>,
+    begin_frame_latency: Vec<Duration>,
+    /// BRP answers are awaited off the main thread, so their round trips come
+    /// back through a channel.
+    brp_round_trips: (Sender<Duration>, Receiver<Duration>),
+}
+
+impl Default for WebviewMetrics {
+    fn default() -> Self {
+        Self {
+            ipc_messages_in: 0,
+            ipc_bytes_in: 0,
+            ipc_messages_out: 0,
+            ipc_bytes_out: 0,
+            localhost_requests: 0,
+            localhost_latency: Vec::new(),
+            begin_frame_latency: Vec::new(),
+            brp_round_trips: async_channel::unbounded(),
+        }
+    }
+}
+
+impl WebviewMetrics {
+    pub(crate) fn ipc_in(&mut self, bytes: usize) {
+        self.ipc_messages_in += 1;
+        self.ipc_bytes_in += bytes;
+    }
+
+    pub(crate) fn ipc_out(&mut self, bytes: usize) {
+        self.ipc_messages_out += 1;
+        self.ipc_bytes_out += bytes;
+    }
+
+    pub(crate) fn localhost_answered(&mut self, latency: Duration) {
+        self.localhost_requests += 1;
+        self.localhost_latency.push(latency);
+    }
+
+    pub(crate) fn begin_frame_painted(&mut self, latency: Duration) {
+        self.begin_frame_latency.push(latency);
+    }
+
+    pub(crate) fn brp_round_trips(&self) -> Sender<Duration> {
+        self.brp_round_trips.0.clone()
+    }
+}
+
+/// Mean of `samples` in milliseconds, or `None` without any.
+fn mean_millis(samples: &[Duration]) -> Option<f64> {
+    (!samples.is_empty()).then(|| {
+        samples.iter().map(Duration::as_secs_f64).sum::<f64>() * 1000.0 / samples.len() as f64
+    })
+}
+
+/// Registers the paint rate of each new webview, and enables it only while the
+/// webview has a browser, so discarded, closed and despawned webviews drop
+/// out.
+fn register_paint_diagnostics(
+    mut store: ResMut<DiagnosticsStore>,
+    webviews: Query<(Entity, &WebviewLifecycle), (With<WebviewSource>, Changed<WebviewLifecycle>)>,
+    mut removed: RemovedComponents<WebviewSource>,
+) {
+    for (webview, lifecycle) in webviews.iter() {
+        let path = WebviewDiagnosticsPlugin::paints_per_second(webview);
+        if store.get(&path).is_none() {
+            store.add(Diagnostic::new(path.clone()).with_suffix(" fps"));
+        }
+        if let Some(diagnostic) = store.get_mut(&path) {
+            diagnostic.is_enabled = lifecycle.has_browser();
+        }
+    }
+    for webview in removed.read() {
+        if let Some(diagnostic) =
+            store.get_mut(&WebviewDiagnosticsPlugin::paints_per_second(webview))
+        {
+            diagnostic.is_enabled = false;
+        }
+    }
+}
+
+fn record_diagnostics(
+    mut diagnostics: Diagnostics,
+    time: Res<Time>,
+    mut metrics: ResMut<WebviewMetrics>,
+    webviews: Query<(Entity, &WebviewLifecycle), With<WebviewSource>>,
+    mut painted: MessageReader<WebviewPainted>,
+    #[cfg(not(target_os = "macos"))] mut textures: MessageReader<RenderTextureMessage>,
+    mut paints: Local<HashMap<Entity, u32>>,
+) {
+    diagnostics.add_measurement(&WebviewDiagnosticsPlugin::BROWSERS, || {
+        webviews
+            .iter()
+            .filter(|(_, lifecycle)| lifecycle.has_browser())
+            .count() as f64
+    });
+
+    paints.clear();
+    for WebviewPainted(webview) in painted.read() {
+        *paints.entry(*webview).or_default() += 1;
+    }
+    let delta = time.delta_secs_f64();
+    if delta > 0.0 {
+        for (webview, _) in webviews
+            .iter()
+            .filter(|(_, lifecycle)| lifecycle.has_browser())
+        {
+            let count = paints.get(&webview).copied().unwrap_or_default();
+            diagnostics.add_measurement(
+                &WebviewDiagnosticsPlugin::paints_per_second(webview),
+                || count as f64 / delta,
+            );
+        }
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let bytes: usize = textures.read().map(|texture| texture.buffer.len()).sum();
+        diagnostics.add_measurement(&WebviewDiagnosticsPlugin::UPLOAD_BYTES, || bytes as f64);
+    }
+
+    let metrics = &mut *metrics;
+    let counts = [
+        (
+            WebviewDiagnosticsPlugin::IPC_MESSAGES_IN,
+            std::mem::take(&mut metrics.ipc_messages_in) as f64,
+        ),
+        (
+            WebviewDiagnosticsPlugin::IPC_BYTES_IN,
+            std::mem::take(&mut metrics.ipc_bytes_in) as f64,
+        ),
+        (
+            WebviewDiagnosticsPlugin::IPC_MESSAGES_OUT,
+            std::mem::take(&mut metrics.ipc_messages_out) as f64,
+        ),
+        (
+            WebviewDiagnosticsPlugin::IPC_BYTES_OUT,
+            std::mem::take(&mut metrics.ipc_bytes_out) as f64,
+        ),
+        (
+            WebviewDiagnosticsPlugin::LOCALHOST_REQUESTS,
+            std::mem::take(&mut metrics.localhost_requests) as f64,
+        ),
+    ];
+    for (path, count) in counts {
+        diagnostics.add_measurement(&path, || count);
+    }
+
+    // Latencies are only measured when something happened, so a quiet frame
+    // does not drag the average towards zero.
+    let brp: Vec<Duration> =
+        std::iter::from_fn(|| metrics.brp_round_trips.1.try_recv().ok()).collect();
+    let latencies = [
+        (WebviewDiagnosticsPlugin::BRP_ROUND_TRIP, brp),
+        (
+            WebviewDiagnosticsPlugin::LOCALHOST_LATENCY,
+            std::mem::take(&mut metrics.localhost_latency),
+        ),
+        (
+            WebviewDiagnosticsPlugin::BEGIN_FRAME_LATENCY,
+            std::mem::take(&mut metrics.begin_frame_latency),
+        ),
+    ];
+    for (path, samples) in latencies {
+        if let Some(mean) = mean_millis(&samples) {
+            diagnostics.add_measurement(&path, || mean);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latency_is_the_mean_in_milliseconds() {
+        assert_eq!(mean_millis(&[]), None);
+        assert_eq!(
+            mean_millis(&[Duration::from_secs(1), Duration::from_secs(3)]),
+            Some(2000.0)
+        );
+    }
+
+    #[test]
+    fn paint_rates_are_enabled_only_while_the_browser_lives() {
+        let mut app = App::new();
+        app.init_resource::<DiagnosticsStore>()
+            .add_systems(Update, register_paint_diagnostics);
+        let webview = app
+            .world_mut()
+            .spawn((
+                WebviewSource::new("https://example.com"),
+                WebviewLifecycle::Ready,
+            ))
+            .id();
+        let path = WebviewDiagnosticsPlugin::paints_per_second(webview);
+        let enabled = |app: &App| {
+            app.world()
+                .resource::<DiagnosticsStore>()
+                .get(&path)
+                .unwrap()
+                .is_enabled
+        };
+
+        app.update();
+        assert!(enabled(&app));
+
+        *app.world_mut()
+            .get_mut::<WebviewLifecycle>(webview)
+            .unwrap() = WebviewLifecycle::Closing;
+        app.update();
+        assert!(!enabled(&app));
+    }
+}
@@ -0,0 +1,534 @@
+//! Discarding and restoring browsers to stay within a [`WebviewBudget`].
+//!
+//! When more browsers are live than the budget allows, the webviews that have
+//! been hidden the longest (see `throttle.rs`) have their browser closed. The
+//! entity keeps its last frame as a frozen texture and a [`WebviewSnapshot`] of
+//! its URL, its back/forward list and its scroll position. As soon as the
+//! webview is visible or focused again, the browser is recreated, reloads its
+//! back/forward list entry by entry, and is scrolled back once it is on the
+//! snapshot page.
+
+use crate::common::{ResolvedWebviewUri, WebviewControl, WebviewDpr, WebviewSize, WebviewSource};
+use crate::focus::FocusedWebview;
+use crate::lifecycle::{LifecycleTransition, WebviewLifecycle};
+use crate::navigation::{AddressChanged, LoadingStateChanged};
+use crate::throttle::HiddenWebviews;
+use crate::webview::WebviewBrowsers;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Control message the page sends (debounced) whenever its main frame scrolls.
+const SCROLL_CONTROL: &str = "page.scroll";
+
+/// How many back/forward entries are remembered. Each one is loaded again when
+/// a discarded webview is restored.
+const MAX_HISTORY: usize = 20;
+
+/// Rough per-browser cost besides its texture: the render process, V8 heap and
+/// compositor state.
+const BROWSER_BASE_BYTES: u64 = 48 * 1024 * 1024;
+
+/// Limits how many browsers stay alive at once.
+///
+/// Both limits are off by default. When either is exceeded, hidden webviews are
+/// discarded, least recently visible first. Visible and focused webviews are
+/// never discarded, so the budget can be exceeded while they need it.
+///
+/// ```rust,no_run
+/// use bevy::prelude::*;
+/// use bevy_cef::prelude::*;
+///
+/// App::new()
+///     .add_plugins(CefPlugin::default())
+///     .insert_resource(WebviewBudget {
+///         max_live: Some(8),
+///         ..default()
+///     });
+/// ```
+#[derive(Resource, Debug, Default, Clone, PartialEq, Reflect)]
+#[reflect(Resource, Debug, Default)]
+pub struct WebviewBudget {
+    /// Maximum number of live browsers.
+    pub max_live: Option<usize>,
+    /// Approximate memory cap in bytes, estimated per browser from a fixed
+    /// overhead plus its texture size.
+    pub max_memory: Option<u64>,
+}
+
+/// What a discarded webview remembers about its page. Present only while the
+/// webview is discarded.
+#[derive(Component, Debug, Clone, PartialEq, Reflect, Serialize, Deserialize)]
+#[reflect(Component, Debug)]
+pub struct WebviewSnapshot {
+    /// The page the browser showed when it was discarded.
+    pub url: String,
+    /// The main frame's back/forward list, oldest first. The recreated browser
+    /// loads these entries in order to rebuild it.
+    pub history: Vec<String>,
+    /// The index of `url` in `history`.
+    pub history_index: usize,
+    /// Last scroll offset of the main frame, in CSS pixels, as reported by a
+    /// page the bridge policy admits.
+    pub scroll: Vec2,
+}
+
+/// Fired when a webview's browser is closed to stay within the
+/// [`WebviewBudget`]. The [`WebviewSnapshot`] is already on the entity.
+#[derive(Debug, EntityEvent, Clone, Reflect, Serialize, Deserialize)]
+pub struct WebviewDiscarded {
+    #[event_target]
+    pub webview: Entity,
+    pub url: String,
+}
+
+/// Fired when a discarded webview's browser is recreated.
+#[derive(Debug, EntityEvent, Clone, Reflect, Serialize, Deserialize)]
+pub struct WebviewRestored {
+    #[event_target]
+    pub webview: Entity,
+    pub url: String,
+}
+
+pub(crate) struct DiscardPlugin;
+
+impl Plugin for DiscardPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WebviewBudget>()
+            .register_required_components::<WebviewSource, PageState>()
+            .register_type::<WebviewBudget>()
+            .register_type::<WebviewSnapshot>()
+            .register_type::<WebviewDiscarded>()
+            .register_type::<WebviewRestored>()
+            .add_observer(record_address)
+            .add_systems(
+                Update,
+                (
+                    record_scroll.run_if(on_message::<WebviewControl>),
+                    restore_webviews,
+                )
+                    .before(crate::webview::WebviewSet::CreateBrowser),
+            );
+
+        app.add_observer(continue_restore)
+            .add_systems(Update, discard_over_budget.after(restore_webviews));
+    }
+}
+
+/// The page state tracked for every webview while its browser is live.
+#[derive(Component, Debug, Default)]
+pub(crate) struct PageState {
+    url: Option<String>,
+    history: NavigationHistory,
+    scroll: Vec2,
+    last_visible: Duration,
+}
+
+/// The main frame's back/forward list, rebuilt from address changes.
+///
+/// CEF does not say which way a navigation went, so moving to the entry just
+/// before or after the current one counts as going back or forward, and any
+/// other URL as a new entry that drops the forward ones.
+#[derive(Debug, Default, Clone, PartialEq)]
+struct NavigationHistory {
+    entries: Vec<String>,
+    index: usize,
+}
+
+impl NavigationHistory {
+    fn visit(&mut self, url: &str) {
+        let at = |offset: isize| {
+            self.index
+                .checked_add_signed(offset)
+                .and_then(|i| self.entries.get(i))
+                .is_some_and(|entry| entry == url)
+        };
+        if at(0) {
+            return;
+        }
+        if at(-1) {
+            self.index -= 1;
+        } else if at(1) {
+            self.index += 1;
+        } else {
+            self.entries.truncate(self.index + 1);
+            self.entries.push(url.to_string());
+            if self.entries.len() > MAX_HISTORY {
+                self.entries.remove(0);
+            }
+            self.index = self.entries.len() - 1;
+        }
+    }
+
+    /// The history a snapshot describes, or just `url` when `entries` does
+    /// not have it at `index`.
+    fn restored(entries: &[String], index: usize, url: &str) -> Self {
+        if entries.get(index).is_some_and(|entry| entry == url) {
+            Self {
+                entries: entries.to_vec(),
+                index,
+            }
+        } else {
+            Self {
+                entries: vec![url.to_string()],
+                index: 0,
+            }
+        }
+    }
+}
+
+/// What a restored browser still has to do to get back to its snapshot: load
+/// the rest of its back/forward list, go back to the snapshot entry, then
+/// scroll. Each step runs once the previous load stops.
+#[derive(Component, Debug, Default, PartialEq)]
+struct PendingRestore {
+    forward: VecDeque<String>,
+    back: usize,
+    scroll: Vec2,
+}
+
+/// A step of [`PendingRestore`].
+#[derive(Debug, PartialEq)]
+enum RestoreStep {
+    Load(String),
+    Run(String),
+}
+
+impl PendingRestore {
+    /// Where to create the browser for `history`, and the steps after that.
+    fn new(history: &NavigationHistory, scroll: Vec2) -> (String, Self) {
+        let mut forward: VecDeque<String> = history.entries.iter().cloned().collect();
+        let first = forward.pop_front().unwrap_or_default();
+        let back = history.entries.len().saturating_sub(history.index + 1);
+        (
+            first,
+            Self {
+                forward,
+                back,
+                scroll,
+            },
+        )
+    }
+
+    fn next(&mut self) -> Option<RestoreStep> {
+        if let Some(url) = self.forward.pop_front() {
+            return Some(RestoreStep::Load(url));
+        }
+        match std::mem::take(&mut self.back) {
+            0 => {}
+            back => return Some(RestoreStep::Run(format!("history.go(-{back});"))),
+        }
+        let scroll = std::mem::take(&mut self.scroll);
+        (scroll != Vec2::ZERO).then(|| RestoreStep::Run(scroll_script(scroll)))
+    }
+
+    fn is_done(&self) -> bool {
+        self.forward.is_empty() && self.back == 0 && self.scroll == Vec2::ZERO
+    }
+}
+
+fn record_address(
+    trigger: On<AddressChanged>,
+    mut states: Query<(&mut PageState, Has<PendingRestore>)>,
+) {
+    // While a restored browser reloads its history, the page state already
+    // holds the snapshot's.
+    let Ok((mut state, false)) = states.get_mut(trigger.webview) else {
+        return;
+    };
+    state.history.visit(&trigger.url);
+    if state.url.as_ref() != Some(&trigger.url) {
+        state.scroll = Vec2::ZERO;
+    }
+    state.url = Some(trigger.url.clone());
+}
+
+fn record_scroll(mut er: MessageReader<WebviewControl>, mut states: Query<&mut PageState>) {
+    for msg in er.read().filter(|msg| msg.name == SCROLL_CONTROL) {
+        if let Ok(mut state) = states.get_mut(msg.webview)
+            && let Ok([x, y]) = serde_json::from_str::<[f32; 2]>(&msg.payload)
+        {
+            state.scroll = Vec2::new(x, y);
+        }
+    }
+}
+
+fn restore_webviews(
+    mut commands: Commands,
+    hidden: Res<HiddenWebviews>,
+    focused: Res<FocusedWebview>,
+    mut discarded: Query<(
+        Entity,
+        &WebviewSnapshot,
+        &mut PageState,
+        Has<ResolvedWebviewUri>,
+    )>,
+) {
+    for (webview, snapshot, mut state, recreated) in discarded.iter_mut() {
+        let mut entity = commands.entity(webview);
+        if recreated {
+            // The source changed while discarded, which already made a new
+            // browser for the new page.
+        } else if !hidden.contains(&webview) || focused.0 == Some(webview) {
+            let history = NavigationHistory::restored(
+                &snapshot.history,
+                snapshot.history_index,
+                &snapshot.url,
+            );
+            let (first, restore) = PendingRestore::new(&history, snapshot.scroll);
+            // Re-adding the resolved URI makes `create_webview` build a fresh
+            // browser with the entity's size, DPR and scripts.
+            entity.insert((ResolvedWebviewUri(first), restore));
+            state.url = Some(snapshot.url.clone());
+            state.history = history;
+        } else {
+            continue;
+        }
+        entity.remove::<WebviewSnapshot>();
+        commands.trigger(WebviewRestored {
+            webview,
+            url: snapshot.url.clone(),
+        });
+    }
+}
+
+/// A live browser that could be discarded.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct LiveWebview {
+    entity: Entity,
+    bytes: u64,
+    last_visible: Duration,
+    discardable: bool,
+}
+
+fn estimated_bytes(size: Vec2, dpr: f32) -> u64 {
+    let pixels = (size.x * dpr).max(0.0) as u64 * (size.y * dpr).max(0.0) as u64;
+    // CEF's paint buffer plus the Bevy texture.
+    BROWSER_BASE_BYTES + pixels * 4 * 2
+}
+
+/// The webviews to discard to get back within `budget`, least recently
+/// visible first.
+fn select_discards(live: &[LiveWebview], budget: &WebviewBudget) -> Vec<Entity> {
+    let mut count = live.len();
+    let mut bytes: u64 = live.iter().map(|w| w.bytes).sum();
+    let over = |count: usize, bytes: u64| {
+        budget.max_live.is_some_and(|max| count > max)
+            || budget.max_memory.is_some_and(|max| bytes > max)
+    };
+    let mut candidates: Vec<&LiveWebview> = live.iter().filter(|w| w.discardable).collect();
+    candidates.sort_by_key(|w| w.last_visible);
+    let mut discards = Vec::new();
+    for candidate in candidates {
+        if !over(count, bytes) {
+            break;
+        }
+        count -= 1;
+        bytes -= candidate.bytes;
+        discards.push(candidate.entity);
+    }
+    discards
+}
+
+type LiveQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static ResolvedWebviewUri,
+        &'static WebviewSize,
+        &'static WebviewDpr,
+        &'static mut PageState,
+        &'static mut WebviewLifecycle,
+    ),
+    Without<WebviewSnapshot>,
+>;
+
+/// Updates visibility times, then discards what the budget requires, turning
+/// each discarded webview's page state into a [`WebviewSnapshot`].
+fn take_discards(
+    commands: &mut Commands,
+    live: &mut LiveQuery,
+    budget: &WebviewBudget,
+    hidden: &HiddenWebviews,
+    focused: &FocusedWebview,
+    now: Duration,
+) -> Vec<Entity> {
+    let mut candidates = Vec::new();
+    for (entity, _, size, dpr, mut state, _) in live.iter_mut() {
+        let discardable = hidden.contains(&entity) && focused.0 != Some(entity);
+        if !discardable {
+            state.last_visible = now;
+        }
+        candidates.push(LiveWebview {
+            entity,
+            bytes: estimated_bytes(size.0, dpr.0),
+            last_visible: state.last_visible,
+            discardable,
+        });
+    }
+    let discards = select_discards(&candidates, budget);
+    for &webview in &discards {
+        let Ok((_, uri, _, _, mut state, mut lifecycle)) = live.get_mut(webview) else {
+            continue;
+        };
+        lifecycle.set_if_neq(lifecycle.apply(LifecycleTransition::CloseRequested));
+        let state = std::mem::take(&mut *state);
+        let url = state.url.unwrap_or_else(|| uri.0.clone());
+        commands
+            .entity(webview)
+            .remove::<ResolvedWebviewUri>()
+            .insert(WebviewSnapshot {
+                url: url.clone(),
+                history: state.history.entries,
+                history_index: state.history.index,
+                scroll: state.scroll,
+            });
+        commands.trigger(WebviewDiscarded { webview, url });
+    }
+    discards
+}
+
+fn discard_over_budget(
+    mut commands: Commands,
+    mut browsers: NonSendMut<WebviewBrowsers>,
+    mut live: LiveQuery,
+    budget: Res<WebviewBudget>,
+    hidden: Res<HiddenWebviews>,
+    focused: Res<FocusedWebview>,
+    time: Res<Time>,
+) {
+    let discards = take_discards(
+        &mut commands,
+        &mut live,
+        &budget,
+        &hidden,
+        &focused,
+        time.elapsed(),
+    );
+    for webview in discards {
+        browsers.close(&webview);
+    }
+}
+
+fn scroll_script(scroll: Vec2) -> String {
+    format!("window.scrollTo({}, {});", scroll.x, scroll.y)
+}
+
+fn continue_restore(
+    trigger: On<LoadingStateChanged>,
+    mut commands: Commands,
+    browsers: NonSend<WebviewBrowsers>,
+    mut pending: Query<&mut PendingRestore>,
+) {
+    if trigger.is_loading {
+        return;
+    }
+    let Ok(mut restore) = pending.get_mut(trigger.webview) else {
+        return;
+    };
+    match restore.next() {
+        Some(RestoreStep::Load(url)) => browsers.navigate(&trigger.webview, &url),
+        Some(RestoreStep::Run(script)) => browsers.execute_javascript(&trigger.webview, &script),
+        None => {}
+    }
+    if restore.is_done() {
+        commands.entity(trigger.webview).remove::<PendingRestore>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn webview(n: u32, last_visible_secs: u64, discardable: bool) -> LiveWebview {
+        LiveWebview {
+            entity: Entity::from_raw_u32(n).unwrap(),
+            bytes: 100,
+            last_visible: Duration::from_secs(last_visible_secs),
+            discardable,
+        }
+    }
+
+    #[test]
+    fn least_recently_visible_hidden_webviews_go_first() {
+        let live = [
+            webview(1, 30, true),
+            webview(2, 10, true),
+            webview(3, 0, false),
+            webview(4, 20, true),
+        ];
+        let budget = WebviewBudget {
+            max_live: Some(2),
+            ..default()
+        };
+        assert_eq!(
+            select_discards(&live, &budget),
+            vec![live[1].entity, live[3].entity]
+        );
+
+        let budget = WebviewBudget {
+            max_memory: Some(350),
+            ..default()
+        };
+        assert_eq!(select_discards(&live, &budget), vec![live[1].entity]);
+        assert!(select_discards(&live, &WebviewBudget::default()).is_empty());
+    }
+
+    #[test]
+    fn history_follows_back_forward_and_new_entries() {
+        let mut history = NavigationHistory::default();
+        for url in ["a", "b", "c", "b", "a", "b"] {
+            history.visit(url);
+        }
+        assert_eq!(history.entries, ["a", "b", "c"]);
+        assert_eq!(history.index, 1);
+
+        history.visit("d");
+        assert_eq!(history.entries, ["a", "b", "d"]);
+        assert_eq!(history.index, 2);
+    }
+
+    #[test]
+    fn restore_reloads_the_history_then_goes_back_and_scrolls() {
+        let history = NavigationHistory {
+            entries: vec!["a".into(), "b".into(), "c".into()],
+            index: 1,
+        };
+        let (first, mut restore) = PendingRestore::new(&history, Vec2::new(0.0, 40.0));
+        assert_eq!(first, "a");
+        assert_eq!(restore.next(), Some(RestoreStep::Load("b".into())));
+        assert_eq!(restore.next(), Some(RestoreStep::Load("c".into())));
+        assert_eq!(
+            restore.next(),
+            Some(RestoreStep::Run("history.go(-1);".into()))
+        );
+        assert!(!restore.is_done());
+        assert_eq!(
+            restore.next(),
+            Some(RestoreStep::Run(scroll_script(Vec2::new(0.0, 40.0))))
+        );
+        assert!(restore.is_done());
+        assert_eq!(restore.next(), None);
+    }
+
+    #[test]
+    fn inconsistent_snapshot_history_restores_only_the_url() {
+        let entries = vec!["a".to_string(), "b".to_string()];
+        assert_eq!(
+            NavigationHistory::restored(&entries, 1, "b"),
+            NavigationHistory {
+                entries: entries.clone(),
+                index: 1,
+            }
+        );
+        assert_eq!(
+            NavigationHistory::restored(&entries, 5, "b"),
+            NavigationHistory {
+                entries: vec!["b".into()],
+                index: 0,
+            }
+        );
+    }
+}
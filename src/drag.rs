@@ -152,8 +152,7 @@ fn on_drag_press(
     regions_q: Query<&DraggableRegions>,
     transforms_q: Query<(&GlobalTransform, &Transform), With<WebviewSource>>,
     cameras_q: Query<(&Camera, &GlobalTransform)>,
-    #[cfg(not(target_os = "windows"))] browsers: NonSend<bevy_cef_core::prelude::Browsers>,
-    #[cfg(target_os = "windows")] browsers: Res<bevy_cef_core::prelude::BrowsersProxy>,
+    browsers: NonSend<crate::webview::WebviewBrowsers>,
 ) {
     // Ignore if already dragging.
     if drag_state.is_dragging() {
@@ -200,14 +199,6 @@ fn on_drag_press(
     });
 
     // Clear CEF hover state — the webview is being dragged, not hovered.
-    #[cfg(not(target_os = "windows"))]
-    browsers.send_mouse_move(
-        &webview,
-        std::iter::empty::<&MouseButton>(),
-        pixel_pos,
-        true,
-    );
-    #[cfg(target_os = "windows")]
     browsers.send_mouse_move(&webview, &[], pixel_pos, true);
 }
 
@@ -282,8 +273,7 @@ fn restore_hover_after_drag(
     mut pending: ResMut<InteractionEndPending>,
     windows: Query<&Window>,
     pointer: WebviewPointer,
-    #[cfg(not(target_os = "windows"))] browsers: NonSend<bevy_cef_core::prelude::Browsers>,
-    #[cfg(target_os = "windows")] browsers: Res<bevy_cef_core::prelude::BrowsersProxy>,
+    browsers: NonSend<crate::webview::WebviewBrowsers>,
 ) {
     let Some(entity) = pending.webview.take() else {
         return;
@@ -295,9 +285,6 @@ fn restore_hover_after_drag(
         return;
     };
 
-    #[cfg(not(target_os = "windows"))]
-    browsers.send_mouse_move(&entity, std::iter::empty::<&MouseButton>(), pos, false);
-    #[cfg(target_os = "windows")]
     browsers.send_mouse_move(&entity, &[], pos, false);
 }
 
@@ -0,0 +1,361 @@
+//! Drag-and-drop between the OS, the game and webviews.
+//!
+//! Files dragged from the OS arrive as Bevy [`FileDragAndDrop`] messages. The
+//! webview under the mouse (mesh, sprite or UI, found through picking) gets
+//! them as a CEF drag: enter when the cursor reaches it, over as it moves,
+//! leave when it moves off or the drag is cancelled, and drop on release.
+//!
+//! Drags started by a page are reported as [`WebviewDragStarted`]. While the
+//! button is held, mouse input over the webview drives the drag so in-page
+//! drag-and-drop works; releasing elsewhere cancels it on the page side and
+//! leaves the data to the game.
+
+use crate::system_param::projection::WebviewProjection;
+use crate::webview::WebviewBrowsers;
+use async_channel::Receiver;
+use bevy::ecs::system::SystemParam;
+use bevy::picking::hover::HoverMap;
+use bevy::prelude::*;
+use bevy_cef_core::prelude::{DragStartedMessage, DragStartedSenderInner, WebviewDragData};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Fired on a webview when its page starts dragging something, such as a link,
+/// an image or an element with `draggable="true"`.
+///
+/// The drag ends when the left mouse button is released; if that happens
+/// outside the webview, the drop is the game's to handle.
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_cef::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     commands.add_observer(|trigger: On<WebviewDragStarted>| {
+///         if let Some(item) = &trigger.data.text {
+///             info!("dragging inventory item {item}");
+///         }
+///     });
+/// }
+/// ```
+#[derive(Debug, EntityEvent, Clone, Reflect, Serialize, Deserialize)]
+pub struct WebviewDragStarted {
+    #[event_target]
+    pub webview: Entity,
+    pub data: WebviewDragData,
+    /// Where the drag started, in DIP relative to the top-left of the webview.
+    pub position: Vec2,
+}
+
+pub(crate) struct DragDropPlugin;
+
+impl Plugin for DragDropPlugin {
+    fn build(&self, app: &mut App) {
+        let (tx, rx) = async_channel::unbounded();
+        app.insert_resource(DragStartedSender(tx))
+            .insert_resource(DragStartedReceiver(rx))
+            .init_resource::<PageDrag>()
+            .init_resource::<FileDrag>()
+            .register_type::<WebviewDragStarted>()
+            .add_systems(PreUpdate, drain_drag_started);
+
+        app.add_systems(Update, (forward_file_drags, end_page_drag));
+    }
+}
+
+#[derive(Resource, Debug, Deref)]
+pub(crate) struct DragStartedSender(pub(crate) DragStartedSenderInner);
+
+#[derive(Resource, Debug)]
+struct DragStartedReceiver(Receiver<DragStartedMessage>);
+
+/// The webview whose page drag is in flight.
+#[derive(Resource, Default, Debug)]
+struct PageDrag(Option<Entity>);
+
+/// A CEF drag-target call replaying an OS file drag.
+#[derive(Debug, Clone, PartialEq)]
+enum FileDragOp {
+    Enter {
+        webview: Entity,
+        files: Vec<PathBuf>,
+        position: Vec2,
+    },
+    Over {
+        webview: Entity,
+        position: Vec2,
+    },
+    Leave {
+        webview: Entity,
+    },
+    Drop {
+        webview: Entity,
+        position: Vec2,
+    },
+}
+
+/// The OS file drag over the game window, if any.
+#[derive(Resource, Default, Debug)]
+struct FileDrag {
+    window: Option<Entity>,
+    files: Vec<PathBuf>,
+    /// The webview the drag is over and the last position sent to it.
+    target: Option<(Entity, Vec2)>,
+}
+
+impl FileDrag {
+    /// Follows the cursor onto `hit`, leaving the previous webview when it
+    /// changes.
+    fn track(&mut self, hit: Option<(Entity, Vec2)>) -> Vec<FileDragOp> {
+        if self.files.is_empty() {
+            return Vec::new();
+        }
+        match (self.target, hit) {
+            (Some((current, last)), Some((webview, position))) if current == webview => {
+                if last == position {
+                    return Vec::new();
+                }
+                self.target = hit;
+                vec![FileDragOp::Over { webview, position }]
+            }
+            (previous, hit) => {
+                self.target = hit;
+                let leave = previous.map(|(webview, _)| FileDragOp::Leave { webview });
+                let enter = hit.map(|(webview, position)| FileDragOp::Enter {
+                    webview,
+                    files: self.files.clone(),
+                    position,
+                });
+                leave.into_iter().chain(enter).collect()
+            }
+        }
+    }
+
+    fn cancel(&mut self) -> Vec<FileDragOp> {
+        self.files.clear();
+        self.window = None;
+        self.target
+            .take()
+            .map(|(webview, _)| FileDragOp::Leave { webview })
+            .into_iter()
+            .collect()
+    }
+
+    /// Drops onto `hit`. Platforms that report no hover beforehand drop
+    /// `dropped` with a fresh enter.
+    fn drop_files(
+        &mut self,
+        dropped: Vec<PathBuf>,
+        hit: Option<(Entity, Vec2)>,
+    ) -> Vec<FileDragOp> {
+        if self.files.is_empty() {
+            self.files = dropped;
+        }
+        let mut ops = self.track(hit);
+        if let Some((webview, position)) = self.target.take() {
+            ops.push(FileDragOp::Drop { webview, position });
+        }
+        self.files.clear();
+        self.window = None;
+        ops
+    }
+}
+
+#[derive(SystemParam)]
+struct FileDragInput<'w, 's> {
+    reader: MessageReader<'w, 's, FileDragAndDrop>,
+    drag: ResMut<'w, FileDrag>,
+    hover_map: Res<'w, HoverMap>,
+    windows: Query<'w, 's, &'static Window>,
+    projection: WebviewProjection<'w, 's>,
+}
+
+impl FileDragInput<'_, '_> {
+    /// Turns this frame's file drag messages and cursor movement into CEF
+    /// drag-target calls.
+    fn ops(&mut self) -> Vec<FileDragOp> {
+        let mut ops = Vec::new();
+        let mut dropped = Vec::new();
+        for event in self.reader.read() {
+            match event {
+                FileDragAndDrop::HoveredFile { window, path_buf } => {
+                    self.drag.window = Some(*window);
+                    self.drag.files.push(path_buf.clone());
+                }
+                FileDragAndDrop::DroppedFile { window, path_buf } => {
+                    self.drag.window = Some(*window);
+                    dropped.push(path_buf.clone());
+                }
+                FileDragAndDrop::HoveredFileCanceled { .. } => {
+                    ops.extend(self.drag.cancel());
+                }
+            }
+        }
+        if self.drag.files.is_empty() && dropped.is_empty() {
+            return ops;
+        }
+        let hit = self.webview_under_cursor();
+        if dropped.is_empty() {
+            ops.extend(self.drag.track(hit));
+        } else {
+            ops.extend(self.drag.drop_files(dropped, hit));
+        }
+        ops
+    }
+
+    /// The nearest webview picked under the mouse and the cursor's DIP
+    /// position on it.
+    fn webview_under_cursor(&self) -> Option<(Entity, Vec2)> {
+        let cursor = self
+            .windows
+            .get(self.drag.window?)
+            .ok()?
+            .cursor_position()?;
+        let webview = self.projection.hovered(&self.hover_map)?;
+        Some((webview, self.projection.project(webview, cursor)?))
+    }
+}
+
+fn drain_drag_started(
+    mut commands: Commands,
+    receiver: Res<DragStartedReceiver>,
+    mut page_drag: ResMut<PageDrag>,
+) {
+    while let Ok(msg) = receiver.0.try_recv() {
+        page_drag.0 = Some(msg.webview);
+        commands.trigger(WebviewDragStarted {
+            webview: msg.webview,
+            data: msg.data,
+            position: msg.position,
+        });
+    }
+}
+
+/// Whether the page drag in flight has been released; a release over the
+/// webview has already dropped it through the regular mouse path.
+fn page_drag_released(
+    page_drag: &mut PageDrag,
+    buttons: &ButtonInput<MouseButton>,
+) -> Option<Entity> {
+    if buttons.pressed(MouseButton::Left) {
+        return None;
+    }
+    page_drag.0.take()
+}
+
+fn forward_file_drags(mut input: FileDragInput, browsers: NonSend<WebviewBrowsers>) {
+    for op in input.ops() {
+        match op {
+            FileDragOp::Enter {
+                webview,
+                files,
+                position,
+            } => browsers.drag_target_enter(&webview, &files, position),
+            FileDragOp::Over { webview, position } => browsers.drag_target_over(&webview, position),
+            FileDragOp::Leave { webview } => browsers.drag_target_leave(&webview),
+            FileDragOp::Drop { webview, position } => browsers.drag_target_drop(&webview, position),
+        }
+    }
+}
+
+fn end_page_drag(
+    mut page_drag: ResMut<PageDrag>,
+    buttons: Res<ButtonInput<MouseButton>>,
+    browsers: NonSend<WebviewBrowsers>,
+) {
+    if let Some(webview) = page_drag_released(&mut page_drag, &buttons) {
+        browsers.cancel_page_drag(&webview);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hovering(files: &[&str]) -> FileDrag {
+        FileDrag {
+            window: None,
+            files: files.iter().map(PathBuf::from).collect(),
+            target: None,
+        }
+    }
+
+    #[test]
+    fn drag_moves_between_webviews() {
+        let a = Entity::from_raw_u32(1).unwrap();
+        let b = Entity::from_raw_u32(2).unwrap();
+        let mut drag = hovering(&["a.png"]);
+        assert_eq!(
+            drag.track(Some((a, Vec2::ZERO))),
+            vec![FileDragOp::Enter {
+                webview: a,
+                files: vec![PathBuf::from("a.png")],
+                position: Vec2::ZERO,
+            }]
+        );
+        assert!(drag.track(Some((a, Vec2::ZERO))).is_empty());
+        assert_eq!(
+            drag.track(Some((a, Vec2::ONE))),
+            vec![FileDragOp::Over {
+                webview: a,
+                position: Vec2::ONE,
+            }]
+        );
+        assert_eq!(
+            drag.track(Some((b, Vec2::ONE))),
+            vec![
+                FileDragOp::Leave { webview: a },
+                FileDragOp::Enter {
+                    webview: b,
+                    files: vec![PathBuf::from("a.png")],
+                    position: Vec2::ONE,
+                },
+            ]
+        );
+        assert_eq!(drag.track(None), vec![FileDragOp::Leave { webview: b }]);
+    }
+
+    #[test]
+    fn drop_without_hover_enters_first() {
+        let webview = Entity::from_raw_u32(1).unwrap();
+        let mut drag = FileDrag::default();
+        let ops = drag.drop_files(vec![PathBuf::from("a.txt")], Some((webview, Vec2::ONE)));
+        assert_eq!(
+            ops,
+            vec![
+                FileDragOp::Enter {
+                    webview,
+                    files: vec![PathBuf::from("a.txt")],
+                    position: Vec2::ONE,
+                },
+                FileDragOp::Drop {
+                    webview,
+                    position: Vec2::ONE,
+                },
+            ]
+        );
+        assert!(drag.files.is_empty());
+        assert!(drag.target.is_none());
+    }
+
+    #[test]
+    fn cancel_leaves_current_webview() {
+        let webview = Entity::from_raw_u32(1).unwrap();
+        let mut drag = hovering(&["a.png"]);
+        drag.track(Some((webview, Vec2::ZERO)));
+        assert_eq!(drag.cancel(), vec![FileDragOp::Leave { webview }]);
+        assert!(drag.track(Some((webview, Vec2::ZERO))).is_empty());
+    }
+
+    #[test]
+    fn page_drag_ends_on_release() {
+        let webview = Entity::from_raw_u32(1).unwrap();
+        let mut page_drag = PageDrag(Some(webview));
+        let mut buttons = ButtonInput::<MouseButton>::default();
+        buttons.press(MouseButton::Left);
+        assert_eq!(page_drag_released(&mut page_drag, &buttons), None);
+        buttons.release(MouseButton::Left);
+        assert_eq!(page_drag_released(&mut page_drag, &buttons), Some(webview));
+        assert_eq!(page_drag_released(&mut page_drag, &buttons), None);
+    }
+}
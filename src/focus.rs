@@ -1,16 +1,28 @@
 //! Process-wide webview focus.
 //!
-//! Tracks the active webview in [`FocusedWebview`] (set on pointer press by any
-//! display path) and pushes it into CEF as single-browser focus, so keyboard and
-//! IME reach only the focused webview.
+//! Tracks the active webview in [`FocusedWebview`] and pushes it into CEF as
+//! single-browser focus, so keyboard and IME reach only the focused webview.
+//! Focus is set on pointer press by any display path, by [`RequestFocus`] /
+//! [`RequestBlur`], by [`AutoFocus`] on load, and by Tab traversal leaving a
+//! page. It is dropped when the focused webview is despawned or hidden.
 
 use crate::common::WebviewSource;
+use crate::navigation::LoadFinished;
 use crate::system_param::pointer::find_webview_entity;
+use crate::webview::WebviewBrowsers;
+use async_channel::Receiver;
 use bevy::prelude::*;
-#[cfg(not(target_os = "windows"))]
-use bevy_cef_core::prelude::Browsers;
-#[cfg(target_os = "windows")]
-use bevy_cef_core::prelude::BrowsersProxy;
+use bevy_cef_core::prelude::{TakeFocusMessage, TakeFocusSenderInner};
+use serde::{Deserialize, Serialize};
+
+/// The CSS selector of elements that can take keyboard focus in a page,
+/// shared by Tab traversal and gamepad spatial navigation. Contains no `'`, so
+/// scripts pass it as a single-quoted string.
+pub(crate) const FOCUSABLE_SELECTOR: &str = r#"a[href],button,input,select,textarea,summary,[tabindex]:not([tabindex="-1"]),[contenteditable="true"]"#;
+
+/// Focuses the first (`true`) or last (`false`) element matching the selector
+/// of a page entered through Tab traversal.
+const ENTER_PAGE_JS: &str = r#"(function(forward,sel){var els=Array.prototype.filter.call(document.querySelectorAll(sel),function(e){return !e.disabled&&e.getClientRects().length>0;});var t=forward?els[0]:els[els.length-1];if(t)t.focus();})"#;
 
 /// The webview that currently holds input focus, if any.
 ///
@@ -19,23 +31,90 @@ use bevy_cef_core::prelude::BrowsersProxy;
 #[derive(Resource, Default, Debug)]
 pub struct FocusedWebview(pub Option<Entity>);
 
+/// Moves keyboard focus to the target webview.
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_cef::prelude::*;
+///
+/// fn open_menu(mut commands: Commands, menu: Single<Entity, With<WebviewSource>>) {
+///     commands.trigger(RequestFocus { webview: *menu });
+/// }
+/// ```
+#[derive(Debug, EntityEvent, Copy, Clone, Reflect, Serialize, Deserialize)]
+pub struct RequestFocus {
+    #[event_target]
+    pub webview: Entity,
+}
+
+/// Drops keyboard focus from the target webview if it holds it.
+#[derive(Debug, EntityEvent, Copy, Clone, Reflect, Serialize, Deserialize)]
+pub struct RequestBlur {
+    #[event_target]
+    pub webview: Entity,
+}
+
+/// Fired on a webview when it gains (`focused: true`) or loses focus.
+///
+/// When focus moves between webviews, the blur fires before the focus.
+#[derive(Debug, EntityEvent, Copy, Clone, Reflect, Serialize, Deserialize)]
+pub struct WebviewFocusChanged {
+    #[event_target]
+    pub webview: Entity,
+    pub focused: bool,
+}
+
+/// Gives the webview focus whenever its main frame finishes loading.
+#[derive(Component, Debug, Default, Copy, Clone, Reflect)]
+#[reflect(Component, Debug, Default)]
+pub struct AutoFocus;
+
+/// Position of a webview in Tab traversal between webviews, like HTML
+/// `tabindex`.
+///
+/// When Tab moves past a page's last focusable element (or Shift-Tab past its
+/// first), focus moves to the next (or previous) visible webview in ascending
+/// index order, ties broken by spawn order. Webviews without this component
+/// count as index `0`; negative indices are skipped.
+#[derive(Component, Debug, Default, Copy, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Component, Debug, Default)]
+pub struct WebviewTabIndex(pub i32);
+
 /// Wires the process-wide focus model: the [`FocusedWebview`] resource, a press
-/// observer on every `WebviewSource`, and the system that drives CEF focus.
+/// observer on every `WebviewSource`, the focus requests and events, and the
+/// system that drives CEF focus.
 pub(crate) struct FocusPlugin;
 
 impl Plugin for FocusPlugin {
     fn build(&self, app: &mut App) {
+        let (tx, rx) = async_channel::unbounded();
         app.init_resource::<FocusedWebview>()
-            .add_systems(Update, setup_focus_observers);
+            .insert_resource(TakeFocusSender(tx))
+            .insert_resource(TakeFocusReceiver(rx))
+            .register_type::<RequestFocus>()
+            .register_type::<RequestBlur>()
+            .register_type::<WebviewFocusChanged>()
+            .register_type::<AutoFocus>()
+            .register_type::<WebviewTabIndex>()
+            .add_observer(apply_request_focus)
+            .add_observer(apply_request_blur)
+            .add_observer(auto_focus_on_load)
+            .add_systems(
+                Update,
+                (setup_focus_observers, drop_lost_focus, emit_focus_changed).chain(),
+            );
 
-        #[cfg(not(target_os = "windows"))]
-        app.add_systems(Update, apply_webview_focus);
-
-        #[cfg(target_os = "windows")]
-        app.add_systems(Update, apply_webview_focus_win);
+        app.add_systems(PreUpdate, drain_take_focus)
+            .add_systems(Update, apply_webview_focus.after(drop_lost_focus));
     }
 }
 
+#[derive(Resource, Debug, Deref)]
+pub(crate) struct TakeFocusSender(pub(crate) TakeFocusSenderInner);
+
+#[derive(Resource, Debug)]
+struct TakeFocusReceiver(Receiver<TakeFocusMessage>);
+
 fn setup_focus_observers(mut commands: Commands, webviews: Query<Entity, Added<WebviewSource>>) {
     for entity in webviews.iter() {
         commands.entity(entity).observe(set_focus_on_press);
@@ -52,10 +131,151 @@ fn set_focus_on_press(
     }
 }
 
-#[cfg(not(target_os = "windows"))]
+fn apply_request_focus(
+    trigger: On<RequestFocus>,
+    webviews: Query<(), With<WebviewSource>>,
+    mut focused: ResMut<FocusedWebview>,
+) {
+    if webviews.contains(trigger.webview) {
+        focused.0 = Some(trigger.webview);
+    }
+}
+
+fn apply_request_blur(trigger: On<RequestBlur>, mut focused: ResMut<FocusedWebview>) {
+    if focused.0 == Some(trigger.webview) {
+        focused.0 = None;
+    }
+}
+
+fn auto_focus_on_load(
+    trigger: On<LoadFinished>,
+    auto_focus: Query<(), With<AutoFocus>>,
+    mut focused: ResMut<FocusedWebview>,
+) {
+    if auto_focus.contains(trigger.webview) {
+        focused.0 = Some(trigger.webview);
+    }
+}
+
+/// Releases focus held by a webview that was despawned or hidden. Webviews
+/// without a visibility component, such as texture targets and headless ones,
+/// count as visible.
+fn drop_lost_focus(
+    mut focused: ResMut<FocusedWebview>,
+    webviews: Query<Option<&InheritedVisibility>, With<WebviewSource>>,
+) {
+    if let Some(webview) = focused.0
+        && !webviews
+            .get(webview)
+            .is_ok_and(|v| v.is_none_or(|v| v.get()))
+    {
+        focused.0 = None;
+    }
+}
+
+fn emit_focus_changed(
+    mut commands: Commands,
+    focused: Res<FocusedWebview>,
+    webviews: Query<(), With<WebviewSource>>,
+    mut prev: Local<Option<Entity>>,
+) {
+    if !focused.is_changed() || focused.0 == *prev {
+        return;
+    }
+    if let Some(p) = prev.take()
+        && webviews.contains(p)
+    {
+        commands.trigger(WebviewFocusChanged {
+            webview: p,
+            focused: false,
+        });
+    }
+    if let Some(current) = focused.0 {
+        commands.trigger(WebviewFocusChanged {
+            webview: current,
+            focused: true,
+        });
+    }
+    *prev = focused.0;
+}
+
+/// The webview Tab traversal lands on after leaving `current`, wrapping around.
+///
+/// `candidates` are `(webview, tab index)` pairs; negative indices are skipped.
+/// Returns `current` itself when it is the only candidate.
+fn next_in_tab_order(
+    mut candidates: Vec<(Entity, i32)>,
+    current: Entity,
+    forward: bool,
+) -> Option<Entity> {
+    candidates.retain(|(_, index)| *index >= 0);
+    candidates.sort_by_key(|(entity, index)| (*index, *entity));
+    if candidates.is_empty() {
+        return None;
+    }
+    let len = candidates.len();
+    let next = match candidates.iter().position(|(e, _)| *e == current) {
+        Some(i) if forward => (i + 1) % len,
+        Some(i) => (i + len - 1) % len,
+        None if forward => 0,
+        None => len - 1,
+    };
+    Some(candidates[next].0)
+}
+
+fn enter_page_script(forward: bool) -> String {
+    format!("{ENTER_PAGE_JS}({forward},'{FOCUSABLE_SELECTOR}');")
+}
+
+/// Webviews Tab traversal can land on, with their visibility and tab index.
+/// Webviews without a visibility component count as visible.
+type TabCandidates<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        Option<&'static InheritedVisibility>,
+        Option<&'static WebviewTabIndex>,
+    ),
+    With<WebviewSource>,
+>;
+
+/// Moves [`FocusedWebview`] for each Tab traversal that left a page, returning
+/// the webviews to enter and the direction.
+fn take_focus_targets(
+    receiver: &TakeFocusReceiver,
+    focused: &mut FocusedWebview,
+    webviews: &TabCandidates,
+) -> Vec<(Entity, bool)> {
+    let mut targets = Vec::new();
+    while let Ok(msg) = receiver.0.try_recv() {
+        let candidates = webviews
+            .iter()
+            .filter(|(_, visibility, _)| visibility.is_none_or(|v| v.get()))
+            .map(|(entity, _, index)| (entity, index.map_or(0, |i| i.0)))
+            .collect();
+        if let Some(next) = next_in_tab_order(candidates, msg.webview, msg.next) {
+            focused.0 = Some(next);
+            targets.push((next, msg.next));
+        }
+    }
+    targets
+}
+
+fn drain_take_focus(
+    receiver: Res<TakeFocusReceiver>,
+    mut focused: ResMut<FocusedWebview>,
+    webviews: TabCandidates,
+    browsers: NonSend<WebviewBrowsers>,
+) {
+    for (webview, forward) in take_focus_targets(&receiver, &mut focused, &webviews) {
+        browsers.execute_javascript(&webview, &enter_page_script(forward));
+    }
+}
+
 fn apply_webview_focus(
     focused: Res<FocusedWebview>,
-    browsers: NonSend<Browsers>,
+    browsers: NonSend<WebviewBrowsers>,
     webviews: Query<Entity, With<WebviewSource>>,
     mut prev: Local<Option<Entity>>,
 ) {
@@ -84,39 +304,105 @@ fn apply_webview_focus(
     }
 }
 
-#[cfg(target_os = "windows")]
-fn apply_webview_focus_win(
-    focused: Res<FocusedWebview>,
-    proxy: Res<BrowsersProxy>,
-    webviews: Query<Entity, With<WebviewSource>>,
-    mut prev: Local<Option<Entity>>,
-) {
-    if !focused.is_changed() {
-        return;
-    }
-    match focused.0 {
-        Some(target) => {
-            for webview in webviews.iter() {
-                proxy.set_focus(&webview, webview == target);
-            }
-            *prev = Some(target);
-        }
-        // NOTE: blur ONLY the previously-focused webview, never blur-all (see
-        // the non-Windows variant for the first-frame rationale). A genuine
-        // Some→None transition releases CEF focus on the webview that held it.
-        None => {
-            if let Some(p) = prev.take() {
-                proxy.set_focus(&p, false);
-            }
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     use bevy::ecs::system::SystemState;
 
+    #[derive(Resource, Default)]
+    struct FocusEvents(Vec<(Entity, bool)>);
+
+    fn setup() -> (World, Schedule) {
+        let mut world = World::new();
+        world.init_resource::<FocusedWebview>();
+        world.init_resource::<FocusEvents>();
+        world.add_observer(apply_request_focus);
+        world.add_observer(apply_request_blur);
+        world.add_observer(
+            |on: On<WebviewFocusChanged>, mut events: ResMut<FocusEvents>| {
+                events.0.push((on.webview, on.focused));
+            },
+        );
+        let mut schedule = Schedule::default();
+        schedule.add_systems((drop_lost_focus, emit_focus_changed).chain());
+        (world, schedule)
+    }
+
+    fn spawn_webview(world: &mut World) -> Entity {
+        world
+            .spawn((WebviewSource::inline("x"), InheritedVisibility::VISIBLE))
+            .id()
+    }
+
+    #[test]
+    fn request_focus_moves_focus_and_fires_events() {
+        let (mut world, mut schedule) = setup();
+        let a = spawn_webview(&mut world);
+        let b = spawn_webview(&mut world);
+        world.trigger(RequestFocus { webview: a });
+        schedule.run(&mut world);
+        world.trigger(RequestFocus { webview: b });
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<FocusedWebview>().0, Some(b));
+        assert_eq!(
+            world.resource::<FocusEvents>().0,
+            vec![(a, true), (a, false), (b, true)]
+        );
+    }
+
+    #[test]
+    fn request_blur_only_blurs_the_focused_webview() {
+        let (mut world, mut schedule) = setup();
+        let a = spawn_webview(&mut world);
+        let b = spawn_webview(&mut world);
+        world.trigger(RequestFocus { webview: a });
+        world.trigger(RequestBlur { webview: b });
+        assert_eq!(world.resource::<FocusedWebview>().0, Some(a));
+        world.trigger(RequestBlur { webview: a });
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<FocusedWebview>().0, None);
+    }
+
+    #[test]
+    fn focus_is_dropped_when_hidden_or_despawned() {
+        let (mut world, mut schedule) = setup();
+        let a = spawn_webview(&mut world);
+        world.trigger(RequestFocus { webview: a });
+        schedule.run(&mut world);
+        world.entity_mut(a).insert(InheritedVisibility::HIDDEN);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<FocusedWebview>().0, None);
+
+        let b = spawn_webview(&mut world);
+        world.trigger(RequestFocus { webview: b });
+        schedule.run(&mut world);
+        world.despawn(b);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<FocusedWebview>().0, None);
+    }
+
+    #[test]
+    fn focus_is_kept_by_webviews_without_visibility() {
+        let (mut world, mut schedule) = setup();
+        let target = world.spawn(WebviewSource::inline("x")).id();
+        world.trigger(RequestFocus { webview: target });
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<FocusedWebview>().0, Some(target));
+    }
+
+    #[test]
+    fn tab_order_wraps_and_skips_negative_indices() {
+        let mut world = World::new();
+        let [a, b, c] = [(); 3].map(|_| world.spawn_empty().id());
+        let candidates = vec![(c, 0), (a, 1), (b, -1)];
+        assert_eq!(next_in_tab_order(candidates.clone(), c, true), Some(a));
+        assert_eq!(next_in_tab_order(candidates.clone(), a, true), Some(c));
+        assert_eq!(next_in_tab_order(candidates.clone(), c, false), Some(a));
+        assert_eq!(next_in_tab_order(vec![(a, 0)], a, true), Some(a));
+        assert_eq!(next_in_tab_order(vec![(b, -1)], a, true), None);
+    }
+
     #[test]
     fn resolves_webview_ancestor_from_child() {
         let mut world = World::new();
@@ -142,4 +428,10 @@ mod tests {
 
         assert_eq!(find_webview_entity(orphan, &parents), None);
     }
+
+    #[test]
+    fn enter_page_script_passes_the_shared_selector() {
+        assert!(!FOCUSABLE_SELECTOR.contains('\''));
+        assert!(enter_page_script(false).ends_with(&format!("(false,'{FOCUSABLE_SELECTOR}');")));
+    }
 }
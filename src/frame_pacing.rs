@@ -0,0 +1,310 @@
+//! Demand-driven begin-frames.
+//!
+//! On macOS, and on Linux without `multi_threaded_message_loop`, a webview only
+//! paints when it is sent a begin-frame. Each webview ticks at its maximum rate
+//! ([`WebviewFrameRate`], or the global
+//! [`BeginFrameInterval`](crate::prelude::BeginFrameInterval)) while its frames
+//! keep painting, and backs off towards [`IdleBeginFrameInterval`] once they
+//! stop. Input, resizes, navigation, host emits and [`RequestFrame`] wake it
+//! up again. Begin-frames go out on app frames, so a webview running at the
+//! app's frame rate ticks once per presented frame instead of drifting against
+//! it.
+
+use crate::diagnostics::WebviewMetrics;
+use crate::prelude::*;
+use crate::system_param::pointer::find_webview_entity;
+use crate::throttle::{HiddenWebviews, frame_interval};
+use bevy::input::keyboard::KeyboardInput;
+use bevy::input::mouse::{MouseButtonInput, MouseWheel};
+use bevy::input::touch::TouchInput;
+use bevy::picking::hover::HoverMap;
+use bevy::platform::collections::HashMap;
+use bevy::platform::time::Instant;
+use bevy::prelude::*;
+use bevy::window::CursorMoved;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Begin-frames a webview may go without a paint before it backs off. Covers
+/// the frame or two a paint takes to reach Bevy.
+const UNPAINTED_GRACE: u32 = 2;
+
+/// A Trigger event to paint a webview once, even if nothing on the page
+/// changed, e.g. to capture it at a known point.
+///
+/// The frame arrives like any other. A webview that had gone idle runs at its
+/// full rate again. Webviews hidden by throttling do not paint.
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_cef::prelude::*;
+///
+/// fn capture(mut commands: Commands, webviews: Query<Entity, With<WebviewSource>>) {
+///     for webview in webviews.iter() {
+///         commands.trigger(RequestFrame { webview });
+///     }
+/// }
+/// ```
+#[derive(Reflect, Debug, Copy, Clone, Serialize, Deserialize, EntityEvent)]
+#[reflect(Serialize, Deserialize)]
+pub struct RequestFrame {
+    #[event_target]
+    pub webview: Entity,
+}
+
+/// The longest gap between begin-frames of a webview whose page stopped
+/// painting.
+///
+/// Idle webviews keep polling at this interval, so changes Bevy cannot see
+/// coming, such as page timers or network responses, still show up. Defaults
+/// to 100 ms. Has no effect where CEF paces frames itself (Windows, and Linux
+/// with `multi_threaded_message_loop`).
+#[derive(Resource, Debug, Copy, Clone, PartialEq, Reflect)]
+#[reflect(Resource, Debug, Default)]
+pub struct IdleBeginFrameInterval(pub Duration);
+
+impl Default for IdleBeginFrameInterval {
+    fn default() -> Self {
+        Self(Duration::from_millis(100))
+    }
+}
+
+/// A webview painted a frame.
+#[derive(Message, Debug, Copy, Clone)]
+pub(crate) struct WebviewPainted(pub(crate) Entity);
+
+/// When a webview gets its next begin-frame.
+#[derive(Debug, Clone, Copy)]
+struct FrameSchedule {
+    /// Time since the last begin-frame.
+    since_last: Duration,
+    /// Current gap between begin-frames, from the webview's maximum rate up to
+    /// the idle interval.
+    period: Duration,
+    /// Begin-frames sent since the last paint.
+    unpainted: u32,
+    /// Tick on the next app frame regardless of the period.
+    woken: bool,
+}
+
+impl Default for FrameSchedule {
+    /// A new webview ticks on its first frame.
+    fn default() -> Self {
+        Self {
+            since_last: Duration::ZERO,
+            period: Duration::ZERO,
+            unpainted: 0,
+            woken: true,
+        }
+    }
+}
+
+impl FrameSchedule {
+    fn wake(&mut self) {
+        self.woken = true;
+        self.unpainted = 0;
+    }
+
+    fn painted(&mut self) {
+        self.unpainted = 0;
+    }
+
+    /// Advances by one app frame and returns whether to send a begin-frame.
+    fn tick(&mut self, delta: Duration, max_rate: Duration, idle: Duration) -> bool {
+        if self.unpainted == 0 {
+            self.period = max_rate;
+        }
+        self.since_last += delta;
+        // Half a frame of slack keeps the ticks on app frames.
+        if !self.woken && self.since_last + delta / 2 < self.period {
+            return false;
+        }
+        self.woken = false;
+        self.since_last = Duration::ZERO;
+        self.unpainted += 1;
+        if self.unpainted > UNPAINTED_GRACE {
+            self.period = (self.period * 2).min(idle.max(max_rate));
+        }
+        true
+    }
+}
+
+#[derive(Resource, Default)]
+struct BeginFrameSchedules(HashMap<Entity, FrameSchedule>);
+
+impl BeginFrameSchedules {
+    fn wake(&mut self, webview: Entity) {
+        self.0.entry(webview).or_default().wake();
+    }
+}
+
+pub(crate) struct FramePacingPlugin;
+
+impl Plugin for FramePacingPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<RequestFrame>()
+            .register_type::<IdleBeginFrameInterval>()
+            .add_message::<WebviewPainted>();
+
+        app.init_resource::<IdleBeginFrameInterval>()
+            .init_resource::<BeginFrameSchedules>()
+            .add_systems(Main, send_external_begin_frame)
+            .add_systems(Update, (wake_on_change, wake_on_input))
+            .add_observer(request_frame)
+            .add_observer(wake_on_host_emit);
+    }
+}
+
+/// Sends begin-frames to the webviews whose schedules are due, skipping
+/// webviews hidden by throttling.
+#[allow(clippy::too_many_arguments)]
+fn send_external_begin_frame(
+    browsers: NonSend<WebviewBrowsers>,
+    time: Res<Time>,
+    interval: Res<BeginFrameInterval>,
+    idle: Res<IdleBeginFrameInterval>,
+    hidden: Res<HiddenWebviews>,
+    webviews: Query<(Entity, Option<&WebviewFrameRate>), With<WebviewSource>>,
+    mut painted: MessageReader<WebviewPainted>,
+    mut schedules: ResMut<BeginFrameSchedules>,
+    mut metrics: Option<ResMut<WebviewMetrics>>,
+    mut sent_at: Local<HashMap<Entity, Instant>>,
+) {
+    let schedules = &mut schedules.0;
+    schedules.retain(|webview, _| webviews.contains(*webview));
+    sent_at.retain(|webview, _| webviews.contains(*webview));
+    for WebviewPainted(webview) in painted.read() {
+        if let Some(schedule) = schedules.get_mut(webview) {
+            schedule.painted();
+        }
+        if let Some(sent) = sent_at.remove(webview)
+            && let Some(metrics) = metrics.as_deref_mut()
+        {
+            metrics.begin_frame_painted(sent.elapsed());
+        }
+    }
+    for (webview, rate) in webviews.iter() {
+        let schedule = schedules.entry(webview).or_default();
+        if hidden.contains(&webview) {
+            // Tick as soon as it is shown again.
+            schedule.wake();
+            continue;
+        }
+        if schedule.tick(time.delta(), frame_interval(rate, interval.0), idle.0) {
+            browsers.send_external_begin_frame_for(&webview);
+            if metrics.is_some() {
+                sent_at.insert(webview, Instant::now());
+            }
+        }
+    }
+}
+
+/// Resizes, DPI changes, navigation and lifecycle steps all repaint the page.
+fn wake_on_change(
+    webviews: Query<
+        Entity,
+        (
+            With<WebviewSource>,
+            Or<(
+                Changed<WebviewSize>,
+                Changed<WebviewDpr>,
+                Changed<ResolvedWebviewUri>,
+                Changed<WebviewLifecycle>,
+            )>,
+        ),
+    >,
+    mut schedules: ResMut<BeginFrameSchedules>,
+) {
+    for webview in webviews.iter() {
+        schedules.wake(webview);
+    }
+}
+
+/// Keyboard input wakes the focused webview, pointer input every webview
+/// under a pointer.
+#[allow(clippy::too_many_arguments)]
+fn wake_on_input(
+    mut keys: MessageReader<KeyboardInput>,
+    mut cursor: MessageReader<CursorMoved>,
+    mut buttons: MessageReader<MouseButtonInput>,
+    mut wheel: MessageReader<MouseWheel>,
+    mut touches: MessageReader<TouchInput>,
+    focused: Res<FocusedWebview>,
+    hover_map: Option<Res<HoverMap>>,
+    parents: Query<(Option<&ChildOf>, Has<WebviewSource>)>,
+    mut schedules: ResMut<BeginFrameSchedules>,
+) {
+    if (keys.read().count() > 0 || focused.is_changed())
+        && let Some(webview) = focused.0
+    {
+        schedules.wake(webview);
+    }
+    let pointer_input = cursor.read().count()
+        + buttons.read().count()
+        + wheel.read().count()
+        + touches.read().count()
+        > 0;
+    if pointer_input && let Some(hover_map) = hover_map {
+        for hit in hover_map.values().flat_map(|hits| hits.keys()) {
+            if let Some(webview) = find_webview_entity(*hit, &parents) {
+                schedules.wake(webview);
+            }
+        }
+    }
+}
+
+fn wake_on_host_emit(trigger: On<HostEmitEvent>, mut schedules: ResMut<BeginFrameSchedules>) {
+    schedules.wake(trigger.webview);
+}
+
+fn request_frame(
+    trigger: On<RequestFrame>,
+    browsers: NonSend<WebviewBrowsers>,
+    mut schedules: ResMut<BeginFrameSchedules>,
+) {
+    browsers.invalidate(&trigger.webview);
+    browsers.send_external_begin_frame_for(&trigger.webview);
+    schedules.wake(trigger.webview);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAME: Duration = Duration::from_millis(16);
+    const IDLE: Duration = Duration::from_millis(96);
+
+    #[test]
+    fn painting_webview_ticks_every_frame_and_a_static_one_backs_off() {
+        let mut schedule = FrameSchedule::default();
+        for _ in 0..30 {
+            assert!(schedule.tick(FRAME, FRAME, IDLE));
+            schedule.painted();
+        }
+
+        let ticks: Vec<bool> = (0..120)
+            .map(|_| schedule.tick(FRAME, FRAME, IDLE))
+            .collect();
+        // Backed off to the idle interval, i.e. every sixth frame.
+        assert_eq!(ticks[60..].iter().filter(|tick| **tick).count(), 10);
+
+        schedule.wake();
+        assert!(schedule.tick(FRAME, FRAME, IDLE));
+        schedule.painted();
+        assert!(schedule.tick(FRAME, FRAME, IDLE));
+    }
+
+    #[test]
+    fn ticks_stay_on_app_frames() {
+        let mut schedule = FrameSchedule::default();
+        let half_rate = Duration::from_secs_f64(1.0 / 30.0);
+        let mut ticks = Vec::new();
+        for frame in 0..20 {
+            // 60 Hz frames with some jitter.
+            let delta = Duration::from_micros(if frame % 3 == 0 { 16_000 } else { 17_000 });
+            ticks.push(schedule.tick(delta, half_rate, IDLE));
+            schedule.painted();
+        }
+        assert!(ticks.chunks(2).all(|pair| pair == [true, false]));
+    }
+}
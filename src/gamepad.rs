@@ -0,0 +1,486 @@
+//! Gamepad input for webviews.
+//!
+//! [`WebviewGamepadPlugin`] is opt-in: add it next to [`CefPlugin`](crate::CefPlugin)
+//! to drive the [`FocusedWebview`] with a controller. It has two modes:
+//!
+//! - [`WebviewGamepadMode::VirtualCursor`] moves a synthetic mouse pointer with
+//!   the left stick, clicks with a face button and scrolls with the right stick.
+//!   The pointer position is published in [`WebviewVirtualCursor`] so the app
+//!   can draw it.
+//! - [`WebviewGamepadMode::SpatialNavigation`] moves DOM focus between focusable
+//!   elements with the D-pad and clicks the focused one.
+//!
+//! Input only reaches the page while [`WebviewGamepadRouting::Webview`] is set.
+//! A page hands the controller back by calling `window.cef.gamepad.release()`,
+//! which switches the routing to [`WebviewGamepadRouting::Game`] and fires
+//! [`WebviewGamepadReleased`].
+
+use crate::common::{WebviewControl, WebviewSize};
+use crate::focus::{FOCUSABLE_SELECTOR, FocusedWebview};
+use crate::webview::WebviewBrowsers;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Control message name sent by `window.cef.gamepad.release()`.
+const RELEASE_CONTROL: &str = "gamepad.release";
+
+/// Defines `window.__bevyCefSpatialNavigate(dir, sel)` once per document.
+///
+/// Candidates are visible, enabled elements matching `sel` whose center lies in the
+/// pressed direction; the nearest one wins, with off-axis distance weighted
+/// twice as heavily so navigation stays in the row or column. With nothing
+/// focused, the first focusable element is picked.
+const SPATIAL_NAVIGATION_JS: &str = r#"if(!window.__bevyCefSpatialNavigate){window.__bevyCefSpatialNavigate=function(dir,sel){
+var els=Array.prototype.filter.call(document.querySelectorAll(sel),function(e){
+if(e.disabled)return false;var r=e.getBoundingClientRect();return r.width>0&&r.height>0;});
+var pick=function(e){e.focus();e.scrollIntoView({block:'nearest',inline:'nearest'});};
+var cur=document.activeElement;
+if(!cur||els.indexOf(cur)<0){if(els.length)pick(els[0]);return;}
+var a=cur.getBoundingClientRect(),ax=a.left+a.width/2,ay=a.top+a.height/2;
+var best=null,bestScore=Infinity;
+els.forEach(function(e){if(e===cur)return;
+var r=e.getBoundingClientRect(),dx=r.left+r.width/2-ax,dy=r.top+r.height/2-ay,main,cross;
+if(dir==='up'){main=-dy;cross=dx;}else if(dir==='down'){main=dy;cross=dx;}
+else if(dir==='left'){main=-dx;cross=dy;}else{main=dx;cross=dy;}
+if(main<=0)return;var s=main+2*Math.abs(cross);if(s<bestScore){bestScore=s;best=e;}});
+if(best)pick(best);};}
+"#;
+
+const ACTIVATE_JS: &str = "if(document.activeElement)document.activeElement.click();";
+
+/// Opt-in controller support for the focused webview.
+///
+/// Requires [`CefPlugin`](crate::CefPlugin). Configure it through the
+/// [`WebviewGamepadSettings`] resource.
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_cef::prelude::*;
+///
+/// App::new()
+///     .add_plugins((DefaultPlugins, CefPlugin::default(), WebviewGamepadPlugin))
+///     .insert_resource(WebviewGamepadSettings {
+///         mode: WebviewGamepadMode::SpatialNavigation,
+///         ..default()
+///     });
+/// ```
+pub struct WebviewGamepadPlugin;
+
+impl Plugin for WebviewGamepadPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WebviewGamepadSettings>()
+            .init_resource::<WebviewGamepadRouting>()
+            .init_resource::<WebviewVirtualCursor>()
+            .register_type::<WebviewGamepadSettings>()
+            .register_type::<WebviewGamepadRouting>()
+            .register_type::<WebviewVirtualCursor>()
+            .register_type::<WebviewGamepadReleased>()
+            .add_systems(
+                PreUpdate,
+                release_on_request.run_if(on_message::<WebviewControl>),
+            );
+
+        app.add_systems(
+            Update,
+            (
+                drive_virtual_cursor.run_if(routed_in_mode(WebviewGamepadMode::VirtualCursor)),
+                drive_spatial_navigation
+                    .run_if(routed_in_mode(WebviewGamepadMode::SpatialNavigation)),
+            ),
+        );
+    }
+}
+
+/// How gamepad input is translated for the focused webview.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Serialize, Deserialize)]
+pub enum WebviewGamepadMode {
+    /// A stick-driven mouse pointer; see [`WebviewVirtualCursor`].
+    #[default]
+    VirtualCursor,
+    /// D-pad moves DOM focus between focusable elements.
+    SpatialNavigation,
+}
+
+/// Which buttons drive the webview.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub struct WebviewGamepadMapping {
+    /// Primary click in [`WebviewGamepadMode::VirtualCursor`].
+    pub click: GamepadButton,
+    /// Secondary (context-menu) click in [`WebviewGamepadMode::VirtualCursor`].
+    pub secondary_click: GamepadButton,
+    /// Clicks the focused element in [`WebviewGamepadMode::SpatialNavigation`].
+    pub activate: GamepadButton,
+    pub up: GamepadButton,
+    pub down: GamepadButton,
+    pub left: GamepadButton,
+    pub right: GamepadButton,
+}
+
+impl Default for WebviewGamepadMapping {
+    fn default() -> Self {
+        Self {
+            click: GamepadButton::South,
+            secondary_click: GamepadButton::West,
+            activate: GamepadButton::South,
+            up: GamepadButton::DPadUp,
+            down: GamepadButton::DPadDown,
+            left: GamepadButton::DPadLeft,
+            right: GamepadButton::DPadRight,
+        }
+    }
+}
+
+/// Configuration for [`WebviewGamepadPlugin`].
+#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Resource, Default)]
+pub struct WebviewGamepadSettings {
+    pub mode: WebviewGamepadMode,
+    pub mapping: WebviewGamepadMapping,
+    /// Virtual cursor speed at full stick deflection, in DIP per second.
+    pub cursor_speed: f32,
+    /// Scroll speed at full right-stick deflection, in pixels per second.
+    pub scroll_speed: f32,
+    /// Stick magnitude below which input is ignored, in `[0, 1)`.
+    pub dead_zone: f32,
+}
+
+impl Default for WebviewGamepadSettings {
+    fn default() -> Self {
+        Self {
+            mode: WebviewGamepadMode::default(),
+            mapping: WebviewGamepadMapping::default(),
+            cursor_speed: 800.0,
+            scroll_speed: 1200.0,
+            dead_zone: 0.15,
+        }
+    }
+}
+
+/// Who currently owns the gamepad.
+///
+/// Set it back to [`WebviewGamepadRouting::Webview`] to hand the controller to
+/// the page again, e.g. when a menu opens. Game systems can gate themselves on
+/// [`WebviewGamepadRouting::Game`] to avoid reacting to menu input.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub enum WebviewGamepadRouting {
+    #[default]
+    Webview,
+    Game,
+}
+
+/// The virtual cursor driven in [`WebviewGamepadMode::VirtualCursor`].
+///
+/// `position` is in DIP relative to the top-left of `webview`. It recenters
+/// whenever focus moves to another webview. Nothing is drawn by bevy_cef; read
+/// this resource to render a cursor sprite or node.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct WebviewVirtualCursor {
+    pub webview: Option<Entity>,
+    pub position: Vec2,
+}
+
+/// Fired when a page calls `window.cef.gamepad.release()`, after the routing
+/// has switched to [`WebviewGamepadRouting::Game`].
+#[derive(Debug, EntityEvent, Clone, Copy, Reflect, Serialize, Deserialize)]
+pub struct WebviewGamepadReleased {
+    #[event_target]
+    pub webview: Entity,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NavigateDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl NavigateDirection {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Up => "up",
+            Self::Down => "down",
+            Self::Left => "left",
+            Self::Right => "right",
+        }
+    }
+}
+
+/// The webview inputs produced by one frame of virtual-cursor gamepad state.
+#[derive(Debug, Default, PartialEq)]
+struct CursorFrame {
+    moved: bool,
+    /// `(button, mouse_up)` transitions, in order.
+    clicks: Vec<(PointerButton, bool)>,
+    /// Mouse buttons held while moving, so stick drags select text.
+    held: Vec<MouseButton>,
+    scroll: Option<Vec2>,
+}
+
+fn routed_in_mode(
+    mode: WebviewGamepadMode,
+) -> impl Fn(Res<WebviewGamepadSettings>, Res<WebviewGamepadRouting>) -> bool {
+    move |settings, routing| settings.mode == mode && *routing == WebviewGamepadRouting::Webview
+}
+
+fn release_on_request(
+    mut commands: Commands,
+    mut er: MessageReader<WebviewControl>,
+    mut routing: ResMut<WebviewGamepadRouting>,
+) {
+    for msg in er.read().filter(|msg| msg.name == RELEASE_CONTROL) {
+        *routing = WebviewGamepadRouting::Game;
+        commands.trigger(WebviewGamepadReleased {
+            webview: msg.webview,
+        });
+    }
+}
+
+/// Rescales a stick so output starts at zero on the dead-zone edge.
+fn apply_dead_zone(stick: Vec2, dead_zone: f32) -> Vec2 {
+    let len = stick.length();
+    if len <= dead_zone {
+        return Vec2::ZERO;
+    }
+    let scaled = ((len - dead_zone) / (1.0 - dead_zone)).min(1.0);
+    stick / len * scaled
+}
+
+/// Moves the cursor by a stick reading, keeping it inside the webview.
+///
+/// Stick up is screen up, so the y axis is flipped into DIP space.
+fn advance_cursor(position: Vec2, stick: Vec2, speed: f32, dt: f32, size: Vec2) -> Vec2 {
+    let delta = Vec2::new(stick.x, -stick.y) * speed * dt;
+    (position + delta).clamp(Vec2::ZERO, size.max(Vec2::ZERO))
+}
+
+/// Points the cursor at `webview`, recentering it if focus moved.
+fn retarget_cursor(cursor: &mut WebviewVirtualCursor, webview: Entity, size: Vec2) -> bool {
+    if cursor.webview == Some(webview) {
+        return false;
+    }
+    cursor.webview = Some(webview);
+    cursor.position = size / 2.0;
+    true
+}
+
+fn virtual_cursor_frame<'a>(
+    gamepads: impl IntoIterator<Item = &'a Gamepad>,
+    settings: &WebviewGamepadSettings,
+    cursor: &mut WebviewVirtualCursor,
+    size: Vec2,
+    dt: f32,
+) -> CursorFrame {
+    let mapping = &settings.mapping;
+    let mut frame = CursorFrame::default();
+    let mut move_stick = Vec2::ZERO;
+    let mut scroll_stick = Vec2::ZERO;
+    for gamepad in gamepads {
+        move_stick += apply_dead_zone(gamepad.left_stick(), settings.dead_zone);
+        scroll_stick += apply_dead_zone(gamepad.right_stick(), settings.dead_zone);
+        for (button, pointer_button, mouse_button) in [
+            (mapping.click, PointerButton::Primary, MouseButton::Left),
+            (
+                mapping.secondary_click,
+                PointerButton::Secondary,
+                MouseButton::Right,
+            ),
+        ] {
+            if gamepad.just_pressed(button) {
+                frame.clicks.push((pointer_button, false));
+            }
+            if gamepad.just_released(button) {
+                frame.clicks.push((pointer_button, true));
+            }
+            if gamepad.pressed(button) && !frame.held.contains(&mouse_button) {
+                frame.held.push(mouse_button);
+            }
+        }
+    }
+
+    let next = advance_cursor(
+        cursor.position,
+        move_stick.clamp_length_max(1.0),
+        settings.cursor_speed,
+        dt,
+        size,
+    );
+    frame.moved = next != cursor.position;
+    cursor.position = next;
+
+    let scroll_stick = scroll_stick.clamp_length_max(1.0);
+    if scroll_stick != Vec2::ZERO {
+        // Wheel deltas are positive for up/left; stick right should reveal
+        // content to the right.
+        frame.scroll =
+            Some(Vec2::new(-scroll_stick.x, scroll_stick.y) * settings.scroll_speed * dt);
+    }
+    frame
+}
+
+/// Scripts to run for this frame's D-pad and activate presses.
+fn spatial_navigation_scripts<'a>(
+    gamepads: impl IntoIterator<Item = &'a Gamepad>,
+    mapping: &WebviewGamepadMapping,
+) -> Vec<String> {
+    let mut scripts = Vec::new();
+    for gamepad in gamepads {
+        for (button, direction) in [
+            (mapping.up, NavigateDirection::Up),
+            (mapping.down, NavigateDirection::Down),
+            (mapping.left, NavigateDirection::Left),
+            (mapping.right, NavigateDirection::Right),
+        ] {
+            if gamepad.just_pressed(button) {
+                scripts.push(spatial_navigation_script(direction));
+            }
+        }
+        if gamepad.just_pressed(mapping.activate) {
+            scripts.push(ACTIVATE_JS.to_string());
+        }
+    }
+    scripts
+}
+
+fn spatial_navigation_script(direction: NavigateDirection) -> String {
+    format!(
+        "{SPATIAL_NAVIGATION_JS}window.__bevyCefSpatialNavigate('{}','{FOCUSABLE_SELECTOR}');",
+        direction.as_str()
+    )
+}
+
+fn drive_virtual_cursor(
+    gamepads: Query<&Gamepad>,
+    settings: Res<WebviewGamepadSettings>,
+    focused: Res<FocusedWebview>,
+    sizes: Query<&WebviewSize>,
+    mut cursor: ResMut<WebviewVirtualCursor>,
+    time: Res<Time>,
+    browsers: NonSend<WebviewBrowsers>,
+) {
+    let Some(webview) = focused.0 else {
+        return;
+    };
+    let Ok(size) = sizes.get(webview) else {
+        return;
+    };
+    let retargeted = retarget_cursor(&mut cursor, webview, size.0);
+    let frame = virtual_cursor_frame(&gamepads, &settings, &mut cursor, size.0, time.delta_secs());
+    if frame.moved || retargeted {
+        browsers.send_mouse_move(&webview, &frame.held, cursor.position, false);
+    }
+    for (button, mouse_up) in frame.clicks {
+        browsers.send_mouse_click(&webview, cursor.position, button, mouse_up);
+    }
+    if let Some(delta) = frame.scroll {
+        browsers.send_mouse_wheel(&webview, cursor.position, delta);
+    }
+}
+
+fn drive_spatial_navigation(
+    gamepads: Query<&Gamepad>,
+    settings: Res<WebviewGamepadSettings>,
+    focused: Res<FocusedWebview>,
+    browsers: NonSend<WebviewBrowsers>,
+) {
+    let Some(webview) = focused.0 else {
+        return;
+    };
+    for script in spatial_navigation_scripts(&gamepads, &settings.mapping) {
+        browsers.execute_javascript(&webview, &script);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dead_zone_is_rescaled() {
+        assert_eq!(apply_dead_zone(Vec2::new(0.1, 0.0), 0.2), Vec2::ZERO);
+        assert_eq!(
+            apply_dead_zone(Vec2::new(1.0, 0.0), 0.2),
+            Vec2::new(1.0, 0.0)
+        );
+        let half = apply_dead_zone(Vec2::new(0.0, 0.6), 0.2);
+        assert!((half.y - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn cursor_moves_with_stick_and_stays_inside() {
+        let size = Vec2::new(800.0, 600.0);
+        let start = Vec2::new(400.0, 300.0);
+        // Stick up-right moves right and towards the top of the page.
+        assert_eq!(
+            advance_cursor(start, Vec2::new(1.0, 1.0), 100.0, 0.5, size),
+            Vec2::new(450.0, 250.0)
+        );
+        assert_eq!(
+            advance_cursor(start, Vec2::new(-1.0, -1.0), 10_000.0, 1.0, size),
+            Vec2::new(0.0, 600.0)
+        );
+    }
+
+    #[test]
+    fn cursor_recenters_on_new_webview() {
+        let mut cursor = WebviewVirtualCursor::default();
+        let webview = Entity::PLACEHOLDER;
+        assert!(retarget_cursor(
+            &mut cursor,
+            webview,
+            Vec2::new(200.0, 100.0)
+        ));
+        assert_eq!(cursor.position, Vec2::new(100.0, 50.0));
+        cursor.position = Vec2::ZERO;
+        assert!(!retarget_cursor(
+            &mut cursor,
+            webview,
+            Vec2::new(200.0, 100.0)
+        ));
+        assert_eq!(cursor.position, Vec2::ZERO);
+    }
+
+    #[derive(Resource, Default)]
+    struct Released(Vec<Entity>);
+
+    #[test]
+    fn release_hands_gamepad_to_game() {
+        let mut world = World::new();
+        world.init_resource::<Messages<WebviewControl>>();
+        world.init_resource::<WebviewGamepadRouting>();
+        world.init_resource::<Released>();
+        world.add_observer(|on: On<WebviewGamepadReleased>, mut r: ResMut<Released>| {
+            r.0.push(on.webview);
+        });
+        let webview = world.spawn_empty().id();
+        world.write_message(WebviewControl {
+            webview,
+            name: "keyboard.other".into(),
+            payload: "null".into(),
+        });
+        world.write_message(WebviewControl {
+            webview,
+            name: RELEASE_CONTROL.into(),
+            payload: "null".into(),
+        });
+        let mut schedule = Schedule::default();
+        schedule.add_systems(release_on_request);
+        schedule.run(&mut world);
+        assert_eq!(
+            *world.resource::<WebviewGamepadRouting>(),
+            WebviewGamepadRouting::Game
+        );
+        assert_eq!(world.resource::<Released>().0, vec![webview]);
+    }
+
+    #[test]
+    fn spatial_script_targets_direction() {
+        let script = spatial_navigation_script(NavigateDirection::Left);
+        assert!(script.starts_with(SPATIAL_NAVIGATION_JS));
+        assert!(script.ends_with(&format!(
+            "window.__bevyCefSpatialNavigate('left','{FOCUSABLE_SELECTOR}');"
+        )));
+    }
+}
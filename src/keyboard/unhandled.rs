@@ -0,0 +1,239 @@
+//! Key presses the page did not consume, reported back to Bevy.
+//!
+//! Every key-down sent to a webview is remembered in [`PendingKeyDowns`]. When
+//! CEF's keyboard handler reports it unhandled, the matching entry becomes a
+//! [`WebviewKeyUnhandled`] event. Entries the page consumed never come back and
+//! simply expire. A page can also claim a key with
+//! `window.cef.keyboard.markHandled(event)`, which drops its entry before the
+//! unhandled report arrives.
+
+use crate::common::{WebviewControl, drain_control_messages};
+use crate::keyboard::ModifiersState;
+use async_channel::Receiver;
+use bevy::prelude::*;
+use bevy_cef_core::prelude::{KeyUnhandledMessage, KeyUnhandledSenderInner, keycode_to_windows_vk};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Control message name sent by `window.cef.keyboard.markHandled()`.
+const MARK_HANDLED_CONTROL: &str = "keyboard.markHandled";
+
+/// How long a sent key-down waits for an unhandled report before it is
+/// considered consumed by the page.
+const KEY_REPORT_TIMEOUT: Duration = Duration::from_secs(1);
+
+pub(super) struct KeyUnhandledPlugin;
+
+impl Plugin for KeyUnhandledPlugin {
+    fn build(&self, app: &mut App) {
+        let (tx, rx) = async_channel::unbounded();
+        app.insert_resource(KeyUnhandledSender(tx))
+            .insert_resource(KeyUnhandledReceiver(rx))
+            .init_resource::<PendingKeyDowns>()
+            .add_systems(PreUpdate, drain_key_unhandled.after(drain_control_messages));
+    }
+}
+
+/// Fired on the focused webview when a key press reached the page but no
+/// element consumed it: no handler called `preventDefault()` and the page did
+/// not call `window.cef.keyboard.markHandled(event)`.
+///
+/// Use it for game shortcuts that should yield to the page, e.g. closing a menu
+/// on Escape unless a text field or dialog handled it first.
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_cef::prelude::*;
+///
+/// fn close_menu_on_escape(trigger: On<WebviewKeyUnhandled>, mut commands: Commands) {
+///     if trigger.key == KeyCode::Escape {
+///         commands.entity(trigger.webview).despawn();
+///     }
+/// }
+/// ```
+#[derive(Debug, EntityEvent, Clone, Copy)]
+pub struct WebviewKeyUnhandled {
+    #[event_target]
+    pub webview: Entity,
+    pub key: KeyCode,
+    pub modifiers: ModifiersState,
+}
+
+#[derive(Resource, Debug, Deref)]
+pub(crate) struct KeyUnhandledSender(pub(crate) KeyUnhandledSenderInner);
+
+#[derive(Resource, Debug)]
+struct KeyUnhandledReceiver(Receiver<KeyUnhandledMessage>);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PendingKeyDown {
+    webview: Entity,
+    windows_key_code: i32,
+    key: KeyCode,
+    modifiers: ModifiersState,
+    sent_at: Duration,
+}
+
+/// Key-downs sent to CEF that the page has not reported unhandled yet.
+///
+/// CEF only echoes back the Windows virtual-key code, so this keeps the Bevy
+/// [`KeyCode`] and modifiers it was sent with.
+#[derive(Resource, Default, Debug)]
+pub(crate) struct PendingKeyDowns(VecDeque<PendingKeyDown>);
+
+impl PendingKeyDowns {
+    pub(crate) fn record(
+        &mut self,
+        webview: Entity,
+        key: KeyCode,
+        modifiers: ModifiersState,
+        now: Duration,
+    ) {
+        self.0.push_back(PendingKeyDown {
+            webview,
+            windows_key_code: keycode_to_windows_vk(key),
+            key,
+            modifiers,
+            sent_at: now,
+        });
+    }
+
+    /// Removes and returns the oldest key-down matching the report.
+    fn take(&mut self, webview: Entity, windows_key_code: i32) -> Option<PendingKeyDown> {
+        let index = self
+            .0
+            .iter()
+            .position(|k| k.webview == webview && k.windows_key_code == windows_key_code)?;
+        self.0.remove(index)
+    }
+
+    fn prune(&mut self, now: Duration) {
+        self.0
+            .retain(|k| now.saturating_sub(k.sent_at) < KEY_REPORT_TIMEOUT);
+    }
+}
+
+pub(crate) fn drain_key_unhandled(
+    mut commands: Commands,
+    mut controls: MessageReader<WebviewControl>,
+    mut pending: ResMut<PendingKeyDowns>,
+    receiver: Res<KeyUnhandledReceiver>,
+    time: Res<Time<Real>>,
+) {
+    // Claims first: `markHandled` is sent while the page's `keydown` handler
+    // runs, so it can land in the same frame as the unhandled report.
+    for control in controls.read().filter(|c| c.name == MARK_HANDLED_CONTROL) {
+        if let Ok(windows_key_code) = serde_json::from_str::<i32>(&control.payload) {
+            pending.take(control.webview, windows_key_code);
+        }
+    }
+    while let Ok(msg) = receiver.0.try_recv() {
+        if let Some(key_down) = pending.take(msg.webview, msg.windows_key_code) {
+            commands.trigger(WebviewKeyUnhandled {
+                webview: msg.webview,
+                key: key_down.key,
+                modifiers: key_down.modifiers,
+            });
+        }
+    }
+    pending.prune(time.elapsed());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct Fired(Vec<(Entity, KeyCode)>);
+
+    fn setup() -> (World, Schedule, async_channel::Sender<KeyUnhandledMessage>) {
+        let (tx, rx) = async_channel::unbounded();
+        let mut world = World::new();
+        world.insert_resource(KeyUnhandledReceiver(rx));
+        world.init_resource::<PendingKeyDowns>();
+        world.init_resource::<Messages<WebviewControl>>();
+        world.init_resource::<Time<Real>>();
+        world.init_resource::<Fired>();
+        world.add_observer(|on: On<WebviewKeyUnhandled>, mut fired: ResMut<Fired>| {
+            fired.0.push((on.webview, on.key));
+        });
+        let mut schedule = Schedule::default();
+        schedule.add_systems(drain_key_unhandled);
+        (world, schedule, tx)
+    }
+
+    fn report(webview: Entity, key: KeyCode) -> KeyUnhandledMessage {
+        KeyUnhandledMessage {
+            webview,
+            windows_key_code: keycode_to_windows_vk(key),
+        }
+    }
+
+    #[test]
+    fn unhandled_report_fires_with_sent_key() {
+        let (mut world, mut schedule, tx) = setup();
+        let webview = world.spawn_empty().id();
+        let shift = ModifiersState {
+            shift: true,
+            ..default()
+        };
+        world.resource_mut::<PendingKeyDowns>().record(
+            webview,
+            KeyCode::Escape,
+            shift,
+            Duration::ZERO,
+        );
+        tx.send_blocking(report(webview, KeyCode::Escape)).unwrap();
+        schedule.run(&mut world);
+        assert_eq!(
+            world.resource::<Fired>().0,
+            vec![(webview, KeyCode::Escape)]
+        );
+        assert!(world.resource::<PendingKeyDowns>().0.is_empty());
+    }
+
+    #[test]
+    fn report_without_pending_key_is_ignored() {
+        let (mut world, mut schedule, tx) = setup();
+        let webview = world.spawn_empty().id();
+        tx.send_blocking(report(webview, KeyCode::Escape)).unwrap();
+        schedule.run(&mut world);
+        assert!(world.resource::<Fired>().0.is_empty());
+    }
+
+    #[test]
+    fn mark_handled_suppresses_report() {
+        let (mut world, mut schedule, tx) = setup();
+        let webview = world.spawn_empty().id();
+        world.resource_mut::<PendingKeyDowns>().record(
+            webview,
+            KeyCode::Escape,
+            ModifiersState::default(),
+            Duration::ZERO,
+        );
+        world.write_message(WebviewControl {
+            webview,
+            name: MARK_HANDLED_CONTROL.into(),
+            payload: keycode_to_windows_vk(KeyCode::Escape).to_string(),
+        });
+        tx.send_blocking(report(webview, KeyCode::Escape)).unwrap();
+        schedule.run(&mut world);
+        assert!(world.resource::<Fired>().0.is_empty());
+    }
+
+    #[test]
+    fn stale_key_downs_expire() {
+        let mut pending = PendingKeyDowns::default();
+        let webview = Entity::PLACEHOLDER;
+        pending.record(
+            webview,
+            KeyCode::KeyA,
+            ModifiersState::default(),
+            Duration::ZERO,
+        );
+        pending.prune(KEY_REPORT_TIMEOUT / 2);
+        assert_eq!(pending.0.len(), 1);
+        pending.prune(KEY_REPORT_TIMEOUT);
+        assert!(pending.0.is_empty());
+    }
+}
@@ -1,30 +1,61 @@
 #![allow(clippy::type_complexity)]
 
+mod accessibility;
+#[cfg(feature = "audio")]
+mod audio;
+mod cdp;
 mod common;
+mod console;
+mod crash;
 mod cursor_icon;
+mod diagnostics;
+mod discard;
 mod drag;
+mod drag_drop;
 mod focus;
+mod frame_pacing;
+mod gamepad;
 mod keyboard;
+mod lifecycle;
 mod mute;
 mod navigation;
+mod ray_pointer;
 mod resize;
 mod system_param;
+#[cfg(all(feature = "test-utils", target_os = "linux"))]
+pub mod test_utils;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod throttle;
 mod title;
+mod touch;
+mod user_script;
 mod webview;
 mod zoom;
 
+use crate::cdp::CdpPlugin;
 use crate::common::{
     LocalHostPlugin, MessageLoopPlugin, SandboxMode, WebviewCoreComponentsPlugin, WebviewDpiPlugin,
     resolve_no_sandbox,
 };
+use crate::console::ConsolePlugin;
+use crate::crash::CrashPlugin;
 use crate::cursor_icon::SystemCursorIconPlugin;
+use crate::discard::DiscardPlugin;
 use crate::drag::DragPlugin;
+use crate::drag_drop::DragDropPlugin;
 use crate::focus::FocusPlugin;
+use crate::frame_pacing::FramePacingPlugin;
 use crate::keyboard::KeyboardPlugin;
+use crate::lifecycle::LifecyclePlugin;
 use crate::mute::AudioMutePlugin;
 use crate::prelude::{IpcPlugin, NavigationPlugin, WebviewPlugin};
+use crate::ray_pointer::RayPointerPlugin;
 use crate::resize::plugin::ResizePlugin;
+use crate::throttle::ThrottlePlugin;
 use crate::title::TitlePlugin;
+use crate::touch::TouchPlugin;
+use crate::user_script::UserScriptPlugin;
 use crate::zoom::ZoomPlugin;
 use bevy::prelude::*;
 use bevy_cef_core::prelude::{
@@ -33,15 +64,47 @@ use bevy_cef_core::prelude::{
 use bevy_remote::RemotePlugin;
 
 pub mod prelude {
-    pub use crate::focus::FocusedWebview;
-    pub use crate::keyboard::{CefKeyboardFilter, KeyboardDeliverSet, ModifiersState};
+    pub use crate::accessibility::WebviewAccessibilityPlugin;
+    #[cfg(feature = "audio")]
+    pub use crate::audio::{
+        WebviewAudioCapture, WebviewAudioStarted, WebviewAudioStopped, WebviewAudioStream,
+    };
+    pub use crate::cdp::{Cdp, CdpCommand, CdpError, CdpEvent, CdpResult, CdpSession};
+    pub use crate::console::WebviewConsoleMessage;
+    pub use crate::crash::{
+        CrashRecoveryPolicy, WebviewCrashed, WebviewResponsive, WebviewUnresponsive,
+        WebviewWatchdog,
+    };
+    pub use crate::diagnostics::WebviewDiagnosticsPlugin;
+    pub use crate::discard::{WebviewBudget, WebviewDiscarded, WebviewRestored, WebviewSnapshot};
+    pub use crate::drag_drop::WebviewDragStarted;
+    pub use crate::focus::{
+        AutoFocus, FocusedWebview, RequestBlur, RequestFocus, WebviewFocusChanged, WebviewTabIndex,
+    };
+    pub use crate::frame_pacing::{IdleBeginFrameInterval, RequestFrame};
+    pub use crate::gamepad::{
+        WebviewGamepadMapping, WebviewGamepadMode, WebviewGamepadPlugin, WebviewGamepadReleased,
+        WebviewGamepadRouting, WebviewGamepadSettings, WebviewVirtualCursor,
+    };
+    pub use crate::keyboard::{
+        CefKeyboardFilter, KeyboardDeliverSet, ModifiersState, WebviewKeyUnhandled,
+    };
+    pub use crate::lifecycle::{WebviewCreationFailed, WebviewLifecycle};
+    pub use crate::ray_pointer::{
+        WebviewHit, WebviewRayHit, WebviewRayPointer, WebviewRayPress, WebviewRayRelease,
+        WebviewRayScroll,
+    };
     pub use crate::resize::components::{AspectLockMode, WebviewResizable};
+    pub use crate::throttle::{WebviewFrameRate, WebviewThrottleSettings};
+    pub use crate::user_script::{UserScript, UserScriptAsset, UserScriptSource, UserScripts};
     pub use crate::{
         CefPlugin, RunOnMainThread, common::*, navigation::*, title::*, webview::prelude::*,
     };
     pub use bevy_cef_core::prelude::{
-        CefCustomScheme, CefExtensions, CefSchemeBody, CefSchemeHandler, CefSchemeOptions,
-        CefSchemeRequest, CefSchemeResponse, CommandLineConfig, switches,
+        BrowserCreationError, CefCustomScheme, CefExtensions, CefSchemeBody, CefSchemeHandler,
+        CefSchemeOptions, CefSchemeRequest, CefSchemeResponse, CommandLineConfig,
+        RenderPaintElementType, RenderTextureMessage, UserScriptRunAt, WebviewConsoleLevel,
+        WebviewDragData, WebviewTermination, switches,
     };
 }
 
@@ -61,6 +124,13 @@ pub struct CefPlugin {
     /// Controls Chromium's OS-level sandbox. Defaults to the current per-platform
     /// behavior; see [`SandboxMode`].
     pub sandbox: SandboxMode,
+    /// Linux only: runs CEF on its own UI thread, as Windows always does,
+    /// instead of pumping it from Bevy's main thread. Heavy pages then no longer
+    /// stall the app's frames. CEF paces these webviews itself, so
+    /// [`BeginFrameInterval`](crate::prelude::BeginFrameInterval) and
+    /// [`IdleBeginFrameInterval`](crate::prelude::IdleBeginFrameInterval) do not
+    /// apply. Ignored on macOS.
+    pub multi_threaded_message_loop: bool,
 }
 
 impl Plugin for CefPlugin {
@@ -109,6 +179,14 @@ impl Plugin for CefPlugin {
             );
         }
 
+        #[cfg(target_os = "macos")]
+        if self.multi_threaded_message_loop {
+            warn!(
+                "bevy_cef: multi_threaded_message_loop is not supported on macOS; \
+                 CEF keeps running on the main thread."
+            );
+        }
+
         app.add_plugins((
             LocalHostPlugin,
             MessageLoopPlugin {
@@ -116,23 +194,46 @@ impl Plugin for CefPlugin {
                 extensions: self.extensions.clone(),
                 root_cache_path: self.root_cache_path.clone(),
                 no_sandbox,
+                multi_threaded_message_loop: self.multi_threaded_message_loop,
             },
-            WebviewCoreComponentsPlugin,
-            WebviewDpiPlugin,
-            WebviewPlugin,
-            IpcPlugin,
-            KeyboardPlugin,
-            FocusPlugin,
-            SystemCursorIconPlugin,
-            DragPlugin,
-            ResizePlugin,
-            NavigationPlugin,
-            TitlePlugin,
-            ZoomPlugin,
-            AudioMutePlugin,
         ));
-        if !app.is_plugin_added::<RemotePlugin>() {
-            app.add_plugins(RemotePlugin::default());
-        }
+        add_webview_plugins(app);
+    }
+}
+
+/// Adds every plugin that works on webviews, i.e. all but CEF's own setup.
+pub(crate) fn add_webview_plugins(app: &mut App) {
+    app.add_plugins((
+        WebviewCoreComponentsPlugin,
+        WebviewDpiPlugin,
+        WebviewPlugin,
+        IpcPlugin,
+        KeyboardPlugin,
+        FocusPlugin,
+        SystemCursorIconPlugin,
+        DragPlugin,
+        ResizePlugin,
+        NavigationPlugin,
+        TitlePlugin,
+        ZoomPlugin,
+        AudioMutePlugin,
+    ));
+    app.add_plugins((
+        UserScriptPlugin,
+        TouchPlugin,
+        DragDropPlugin,
+        RayPointerPlugin,
+        ThrottlePlugin,
+        FramePacingPlugin,
+        DiscardPlugin,
+        CrashPlugin,
+        LifecyclePlugin,
+        ConsolePlugin,
+        CdpPlugin,
+    ));
+    #[cfg(feature = "audio")]
+    app.add_plugins(audio::WebviewAudioPlugin);
+    if !app.is_plugin_added::<RemotePlugin>() {
+        app.add_plugins(RemotePlugin::default());
     }
 }
@@ -0,0 +1,208 @@
+//! Where each webview's browser is in its life.
+//!
+//! Every webview carries a [`WebviewLifecycle`] that advances on CEF's own
+//! callbacks: the browser being created, loading, crashing and closing. A
+//! browser that cannot be created leaves the webview
+//! [`Failed`](WebviewLifecycle::Failed) and fires [`WebviewCreationFailed`].
+
+use crate::common::WebviewSource;
+use crate::crash::WebviewCrashed;
+use crate::navigation::LoadingStateChanged;
+use async_channel::Receiver;
+use bevy::prelude::*;
+use bevy_cef_core::prelude::{
+    BrowserCreationError, BrowserLifecycleEvent, BrowserLifecycleMessage,
+    BrowserLifecycleSenderInner,
+};
+use serde::{Deserialize, Serialize};
+
+/// The state of a webview's browser.
+///
+/// Added to every [`WebviewSource`]. Set it back to [`Pending`](Self::Pending)
+/// to retry a failed creation.
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_cef::prelude::*;
+///
+/// fn show_when_ready(
+///     mut webviews: Query<(&WebviewLifecycle, &mut Visibility), Changed<WebviewLifecycle>>,
+/// ) {
+///     for (lifecycle, mut visibility) in webviews.iter_mut() {
+///         if *lifecycle == WebviewLifecycle::Ready {
+///             *visibility = Visibility::Inherited;
+///         }
+///     }
+/// }
+/// ```
+#[derive(Component, Debug, Default, Copy, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+pub enum WebviewLifecycle {
+    /// Waiting for its URL to resolve or its host window to open.
+    #[default]
+    Pending,
+    /// Creation was requested and CEF has not confirmed it yet.
+    Creating,
+    /// The browser is up and idle.
+    Ready,
+    /// The browser is loading a page.
+    Loading,
+    /// The render process terminated; the next page load recovers it.
+    Crashed,
+    /// The browser is being closed, e.g. because it was discarded.
+    Closing,
+    /// The browser could not be created. See [`WebviewCreationFailed`].
+    Failed,
+}
+
+/// Fired when a webview's browser could not be created.
+#[derive(Debug, EntityEvent, Copy, Clone, Reflect, Serialize, Deserialize)]
+pub struct WebviewCreationFailed {
+    #[event_target]
+    pub webview: Entity,
+    pub reason: BrowserCreationError,
+}
+
+/// What moves a [`WebviewLifecycle`] on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LifecycleTransition {
+    CreateRequested,
+    Created,
+    Loading(bool),
+    Crashed,
+    CloseRequested,
+    Closed,
+    Failed,
+}
+
+impl WebviewLifecycle {
+    /// Whether `create_webview` should make a browser for the webview.
+    ///
+    /// A closing webview may get its new browser before the old one reports
+    /// that it closed.
+    pub(crate) fn awaits_browser(self) -> bool {
+        matches!(self, Self::Pending | Self::Closing)
+    }
+
+    /// Whether the webview has a live browser: created and not closing.
+    pub(crate) fn has_browser(self) -> bool {
+        matches!(self, Self::Ready | Self::Loading | Self::Crashed)
+    }
+
+    pub(crate) fn apply(self, transition: LifecycleTransition) -> Self {
+        use LifecycleTransition as T;
+        match (self, transition) {
+            (_, T::CreateRequested) => Self::Creating,
+            (_, T::Failed) => Self::Failed,
+            (_, T::CloseRequested) => Self::Closing,
+            (Self::Closing, T::Closed) => Self::Pending,
+            (Self::Creating, T::Created) => Self::Ready,
+            (Self::Creating | Self::Ready | Self::Loading | Self::Crashed, T::Loading(true)) => {
+                Self::Loading
+            }
+            (Self::Loading, T::Loading(false)) => Self::Ready,
+            (Self::Creating | Self::Ready | Self::Loading, T::Crashed) => Self::Crashed,
+            // Late news about a browser that is already gone or replaced.
+            (state, _) => state,
+        }
+    }
+}
+
+pub(crate) struct LifecyclePlugin;
+
+impl Plugin for LifecyclePlugin {
+    fn build(&self, app: &mut App) {
+        let (tx, rx) = async_channel::unbounded();
+        app.insert_resource(BrowserLifecycleSender(tx))
+            .insert_resource(BrowserLifecycleReceiver(rx))
+            .register_required_components::<WebviewSource, WebviewLifecycle>()
+            .register_type::<WebviewLifecycle>()
+            .register_type::<WebviewCreationFailed>()
+            .add_observer(track_loading)
+            .add_observer(track_crash)
+            .add_systems(PreUpdate, drain_lifecycle_events);
+    }
+}
+
+#[derive(Resource, Debug, Deref)]
+pub(crate) struct BrowserLifecycleSender(pub(crate) BrowserLifecycleSenderInner);
+
+#[derive(Resource, Debug)]
+struct BrowserLifecycleReceiver(Receiver<BrowserLifecycleMessage>);
+
+pub(crate) fn creation_failed(
+    commands: &mut Commands,
+    webview: Entity,
+    lifecycle: &mut Mut<WebviewLifecycle>,
+    reason: BrowserCreationError,
+) {
+    warn!("bevy_cef: failed to create the browser of {webview}: {reason}");
+    lifecycle.set_if_neq(lifecycle.apply(LifecycleTransition::Failed));
+    commands.trigger(WebviewCreationFailed { webview, reason });
+}
+
+fn drain_lifecycle_events(
+    mut commands: Commands,
+    receiver: Res<BrowserLifecycleReceiver>,
+    mut webviews: Query<&mut WebviewLifecycle>,
+) {
+    while let Ok(msg) = receiver.0.try_recv() {
+        let Ok(mut lifecycle) = webviews.get_mut(msg.webview) else {
+            continue;
+        };
+        let transition = match msg.event {
+            BrowserLifecycleEvent::Created => LifecycleTransition::Created,
+            BrowserLifecycleEvent::Closed => LifecycleTransition::Closed,
+            BrowserLifecycleEvent::CreationFailed(reason) => {
+                creation_failed(&mut commands, msg.webview, &mut lifecycle, reason);
+                continue;
+            }
+        };
+        lifecycle.set_if_neq(lifecycle.apply(transition));
+    }
+}
+
+fn track_loading(trigger: On<LoadingStateChanged>, mut webviews: Query<&mut WebviewLifecycle>) {
+    if let Ok(mut lifecycle) = webviews.get_mut(trigger.webview) {
+        lifecycle.set_if_neq(lifecycle.apply(LifecycleTransition::Loading(trigger.is_loading)));
+    }
+}
+
+fn track_crash(trigger: On<WebviewCrashed>, mut webviews: Query<&mut WebviewLifecycle>) {
+    if let Ok(mut lifecycle) = webviews.get_mut(trigger.webview) {
+        lifecycle.set_if_neq(lifecycle.apply(LifecycleTransition::Crashed));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use LifecycleTransition as T;
+
+    #[test]
+    fn lifecycle_follows_cef_callbacks() {
+        let mut state = WebviewLifecycle::default();
+        for (transition, expected) in [
+            (T::CreateRequested, WebviewLifecycle::Creating),
+            (T::Created, WebviewLifecycle::Ready),
+            (T::Loading(true), WebviewLifecycle::Loading),
+            (T::Crashed, WebviewLifecycle::Crashed),
+            (T::Loading(true), WebviewLifecycle::Loading),
+            (T::Loading(false), WebviewLifecycle::Ready),
+            (T::CloseRequested, WebviewLifecycle::Closing),
+            (T::Closed, WebviewLifecycle::Pending),
+        ] {
+            state = state.apply(transition);
+            assert_eq!(state, expected);
+        }
+    }
+
+    #[test]
+    fn late_close_of_a_replaced_browser_is_ignored() {
+        let state = WebviewLifecycle::Closing
+            .apply(T::CreateRequested)
+            .apply(T::Closed);
+        assert_eq!(state, WebviewLifecycle::Creating);
+        assert_eq!(state.apply(T::Created), WebviewLifecycle::Ready);
+    }
+}
@@ -0,0 +1,455 @@
+//! World-space ray pointers for XR controllers, laser pointers and scripted
+//! input.
+//!
+//! Any entity with a [`WebviewRayPointer`] casts a ray at the mesh and sprite
+//! webviews in the scene every frame. The nearest opaque hit is stored in its
+//! [`WebviewRayHit`] and forwarded to CEF as mouse movement; the pointer's
+//! buttons and wheel are driven by triggering [`WebviewRayPress`],
+//! [`WebviewRayRelease`] and [`WebviewRayScroll`] on it.
+//!
+//! Pointers are independent, so several can hover different webviews at once.
+//! CEF has a single mouse per browser, though, so [`RayHovers`] lets only the
+//! pointer that most recently entered or pressed a webview drive its hover.
+//! UI webviews are laid out in screen space and are not ray-cast.
+
+use crate::common::WebviewSource;
+use crate::focus::FocusedWebview;
+use crate::system_param::pointer::WebviewPointer;
+use crate::webview::WebviewBrowsers;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Casts a ray at mesh and sprite webviews and forwards what it hits as mouse input.
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_cef::prelude::*;
+///
+/// fn spawn_laser(mut commands: Commands) {
+///     // Casts along the entity's forward axis; parent it to a tracked controller.
+///     let laser = commands
+///         .spawn((WebviewRayPointer::default(), Transform::default()))
+///         .id();
+///     commands.trigger(WebviewRayPress {
+///         pointer: laser,
+///         button: MouseButton::Left,
+///     });
+/// }
+/// ```
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default)]
+#[require(WebviewRayHit, RayPointerButtons)]
+pub struct WebviewRayPointer {
+    /// World-space ray to cast. `None` casts from the entity's
+    /// `GlobalTransform` along its forward axis.
+    pub ray: Option<Ray3d>,
+    /// Hits farther than this are ignored.
+    pub max_distance: f32,
+}
+
+impl Default for WebviewRayPointer {
+    fn default() -> Self {
+        Self {
+            ray: None,
+            max_distance: f32::INFINITY,
+        }
+    }
+}
+
+/// What a [`WebviewRayPointer`] is pointing at; `None` when it hits no
+/// webview. Updated every frame, so it can drive a hit-point reticle.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default)]
+pub struct WebviewRayHit(pub Option<WebviewHit>);
+
+/// A ray hit on a webview.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect, Serialize, Deserialize)]
+pub struct WebviewHit {
+    pub webview: Entity,
+    /// Hit position in DIP relative to the top-left of the webview.
+    pub position: Vec2,
+    /// Hit point in world space.
+    pub point: Vec3,
+    /// Distance from the ray origin.
+    pub distance: f32,
+}
+
+/// Presses `button` on the target pointer, clicking whatever it hits.
+#[derive(Debug, EntityEvent, Copy, Clone, Reflect, Serialize, Deserialize)]
+pub struct WebviewRayPress {
+    #[event_target]
+    pub pointer: Entity,
+    pub button: MouseButton,
+}
+
+/// Releases `button` on the target pointer.
+///
+/// The release goes to the webview the press landed on, even if the pointer
+/// has moved off it since.
+#[derive(Debug, EntityEvent, Copy, Clone, Reflect, Serialize, Deserialize)]
+pub struct WebviewRayRelease {
+    #[event_target]
+    pub pointer: Entity,
+    pub button: MouseButton,
+}
+
+/// Scrolls the webview under the target pointer by `delta` pixels.
+#[derive(Debug, EntityEvent, Copy, Clone, Reflect, Serialize, Deserialize)]
+pub struct WebviewRayScroll {
+    #[event_target]
+    pub pointer: Entity,
+    pub delta: Vec2,
+}
+
+pub(crate) struct RayPointerPlugin;
+
+impl Plugin for RayPointerPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<WebviewRayPointer>()
+            .register_type::<WebviewRayHit>()
+            .register_type::<WebviewRayPress>()
+            .register_type::<WebviewRayRelease>()
+            .register_type::<WebviewRayScroll>()
+            .init_resource::<RayHovers>();
+
+        app.add_observer(on_ray_press)
+            .add_observer(on_ray_release)
+            .add_observer(on_ray_scroll)
+            .add_systems(
+                PostUpdate,
+                update_ray_pointers.after(TransformSystems::Propagate),
+            );
+    }
+}
+
+/// Buttons held on a pointer and the webview that captured them.
+#[derive(Component, Debug, Default)]
+struct RayPointerButtons {
+    held: Vec<MouseButton>,
+    capture: Option<(Entity, Vec2)>,
+}
+
+impl RayPointerButtons {
+    fn press(&mut self, button: MouseButton, hit: &WebviewHit) {
+        if !self.held.contains(&button) {
+            self.held.push(button);
+        }
+        if self.capture.is_none() {
+            self.capture = Some((hit.webview, hit.position));
+        }
+    }
+
+    /// Releases `button`, returning where the release should be delivered.
+    fn release(&mut self, button: MouseButton, hit: Option<&WebviewHit>) -> Option<(Entity, Vec2)> {
+        if !self.held.contains(&button) {
+            return None;
+        }
+        self.held.retain(|b| *b != button);
+        let (webview, last) = self.capture?;
+        if self.held.is_empty() {
+            self.capture = None;
+        }
+        match hit {
+            Some(hit) if hit.webview == webview => Some((webview, hit.position)),
+            _ => Some((webview, last)),
+        }
+    }
+
+    fn track(&mut self, hit: Option<&WebviewHit>) {
+        if let (Some((webview, last)), Some(hit)) = (&mut self.capture, hit)
+            && *webview == hit.webview
+        {
+            *last = hit.position;
+        }
+    }
+}
+
+/// A mouse move to send: the webview, the pointer it follows, the position
+/// and whether the mouse leaves the webview.
+type RayMove = (Entity, Entity, Vec2, bool);
+
+/// The ray pointers over each webview and where they hit it, most recent last.
+///
+/// CEF has one mouse per browser, so only the last pointer, the webview's
+/// topmost, moves it. When the topmost pointer leaves, the mouse jumps to the
+/// next one, and the webview only sees a leave once no pointer is over it.
+#[derive(Resource, Default, Debug)]
+struct RayHovers(HashMap<Entity, Vec<(Entity, Vec2)>>);
+
+impl RayHovers {
+    /// Records that `pointer` now hits `current`, returning the mouse moves to
+    /// send.
+    fn hover(&mut self, pointer: Entity, current: Option<&WebviewHit>) -> Vec<RayMove> {
+        let mut moves = Vec::new();
+        self.0.retain(|&webview, stack| {
+            if current.is_some_and(|hit| hit.webview == webview) {
+                return true;
+            }
+            let Some(index) = stack.iter().position(|(p, _)| *p == pointer) else {
+                return true;
+            };
+            let (_, last) = stack.remove(index);
+            if index == stack.len() {
+                match stack.last() {
+                    Some(&(next, position)) => moves.push((webview, next, position, false)),
+                    None => moves.push((webview, pointer, last, true)),
+                }
+            }
+            !stack.is_empty()
+        });
+        if let Some(hit) = current {
+            let stack = self.0.entry(hit.webview).or_default();
+            match stack.iter().position(|(p, _)| *p == pointer) {
+                Some(index) => {
+                    let moved = stack[index].1 != hit.position;
+                    stack[index].1 = hit.position;
+                    if moved && index + 1 == stack.len() {
+                        moves.push((hit.webview, pointer, hit.position, false));
+                    }
+                }
+                None => {
+                    stack.push((pointer, hit.position));
+                    moves.push((hit.webview, pointer, hit.position, false));
+                }
+            }
+        }
+        moves
+    }
+
+    /// Makes `pointer` the topmost pointer of `webview`, as a press moves the
+    /// mouse to it.
+    fn raise(&mut self, pointer: Entity, webview: Entity) {
+        if let Some(stack) = self.0.get_mut(&webview)
+            && let Some(index) = stack.iter().position(|(p, _)| *p == pointer)
+        {
+            let entry = stack.remove(index);
+            stack.push(entry);
+        }
+    }
+}
+
+fn pointer_button(button: MouseButton) -> Option<PointerButton> {
+    match button {
+        MouseButton::Left => Some(PointerButton::Primary),
+        MouseButton::Right => Some(PointerButton::Secondary),
+        MouseButton::Middle => Some(PointerButton::Middle),
+        _ => None,
+    }
+}
+
+/// The webviews ray pointers can hit.
+type RayTargets<'w, 's> =
+    Query<'w, 's, Entity, (With<WebviewSource>, Or<(With<Mesh3d>, With<Sprite>)>)>;
+
+type RayPointers<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static WebviewRayPointer,
+        &'static GlobalTransform,
+        &'static mut WebviewRayHit,
+        &'static mut RayPointerButtons,
+    ),
+>;
+
+fn cast(
+    pointer: &WebviewRayPointer,
+    transform: &GlobalTransform,
+    webviews: &RayTargets,
+    pointer_param: &WebviewPointer,
+) -> Option<WebviewHit> {
+    let ray = pointer
+        .ray
+        .unwrap_or_else(|| Ray3d::new(transform.translation(), transform.forward()));
+    webviews
+        .iter()
+        .filter_map(|webview| {
+            let (position, distance) = pointer_param.ray_pos(webview, ray)?;
+            (distance <= pointer.max_distance).then(|| WebviewHit {
+                webview,
+                position,
+                point: ray.get_point(distance),
+                distance,
+            })
+        })
+        .min_by(|a, b| a.distance.total_cmp(&b.distance))
+}
+
+/// Casts every pointer and returns the mouse moves to send, each with the
+/// buttons held by the pointer it follows.
+fn cast_pointers(
+    pointers: &mut RayPointers,
+    removed: &mut RemovedComponents<WebviewRayPointer>,
+    hovers: &mut RayHovers,
+    webviews: &RayTargets,
+    pointer_param: &WebviewPointer,
+) -> Vec<(Entity, Vec<MouseButton>, Vec2, bool)> {
+    let mut moves = Vec::new();
+    for pointer in removed.read() {
+        moves.extend(hovers.hover(pointer, None));
+    }
+    for (entity, pointer, transform, mut hit, mut buttons) in pointers.iter_mut() {
+        let current = cast(pointer, transform, webviews, pointer_param);
+        moves.extend(hovers.hover(entity, current.as_ref()));
+        buttons.track(current.as_ref());
+        hit.set_if_neq(WebviewRayHit(current));
+    }
+    moves
+        .into_iter()
+        .map(|(webview, pointer, position, leave)| {
+            let held = pointers
+                .get(pointer)
+                .map(|(.., buttons)| buttons.held.clone())
+                .unwrap_or_default();
+            (webview, held, position, leave)
+        })
+        .collect()
+}
+
+fn update_ray_pointers(
+    mut pointers: RayPointers,
+    mut removed: RemovedComponents<WebviewRayPointer>,
+    mut hovers: ResMut<RayHovers>,
+    webviews: RayTargets,
+    pointer_param: WebviewPointer,
+    browsers: NonSend<WebviewBrowsers>,
+) {
+    let moves = cast_pointers(
+        &mut pointers,
+        &mut removed,
+        &mut hovers,
+        &webviews,
+        &pointer_param,
+    );
+    for (webview, held, position, leave) in moves {
+        browsers.send_mouse_move(&webview, &held, position, leave);
+    }
+}
+
+fn on_ray_press(
+    trigger: On<WebviewRayPress>,
+    mut pointers: Query<(&WebviewRayHit, &mut RayPointerButtons)>,
+    mut hovers: ResMut<RayHovers>,
+    mut focused: ResMut<FocusedWebview>,
+    browsers: NonSend<WebviewBrowsers>,
+) {
+    let Ok((WebviewRayHit(Some(hit)), mut buttons)) = pointers.get_mut(trigger.pointer) else {
+        return;
+    };
+    let Some(button) = pointer_button(trigger.button) else {
+        return;
+    };
+    buttons.press(trigger.button, hit);
+    hovers.raise(trigger.pointer, hit.webview);
+    focused.0 = Some(hit.webview);
+    browsers.send_mouse_click(&hit.webview, hit.position, button, false);
+}
+
+fn on_ray_release(
+    trigger: On<WebviewRayRelease>,
+    mut pointers: Query<(&WebviewRayHit, &mut RayPointerButtons)>,
+    browsers: NonSend<WebviewBrowsers>,
+) {
+    let Ok((hit, mut buttons)) = pointers.get_mut(trigger.pointer) else {
+        return;
+    };
+    let Some(button) = pointer_button(trigger.button) else {
+        return;
+    };
+    if let Some((webview, position)) = buttons.release(trigger.button, hit.0.as_ref()) {
+        browsers.send_mouse_click(&webview, position, button, true);
+    }
+}
+
+fn on_ray_scroll(
+    trigger: On<WebviewRayScroll>,
+    pointers: Query<&WebviewRayHit>,
+    browsers: NonSend<WebviewBrowsers>,
+) {
+    if let Ok(WebviewRayHit(Some(hit))) = pointers.get(trigger.pointer) {
+        browsers.send_mouse_wheel(&hit.webview, hit.position, trigger.delta);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hit(webview: u32, x: f32) -> WebviewHit {
+        WebviewHit {
+            webview: Entity::from_raw_u32(webview).unwrap(),
+            position: Vec2::new(x, 0.0),
+            point: Vec3::ZERO,
+            distance: 1.0,
+        }
+    }
+
+    #[test]
+    fn moving_between_webviews_leaves_the_first() {
+        let pointer = Entity::from_raw_u32(10).unwrap();
+        let a = hit(1, 10.0);
+        let b = hit(2, 20.0);
+        let mut hovers = RayHovers::default();
+        assert_eq!(
+            hovers.hover(pointer, Some(&a)),
+            vec![(a.webview, pointer, a.position, false)]
+        );
+        assert!(hovers.hover(pointer, Some(&a)).is_empty());
+        assert_eq!(
+            hovers.hover(pointer, Some(&b)),
+            vec![
+                (a.webview, pointer, a.position, true),
+                (b.webview, pointer, b.position, false)
+            ]
+        );
+        assert_eq!(
+            hovers.hover(pointer, None),
+            vec![(b.webview, pointer, b.position, true)]
+        );
+        assert!(hovers.0.is_empty());
+    }
+
+    #[test]
+    fn only_the_topmost_pointer_moves_a_shared_webview() {
+        let first = Entity::from_raw_u32(10).unwrap();
+        let second = Entity::from_raw_u32(11).unwrap();
+        let mut hovers = RayHovers::default();
+        hovers.hover(first, Some(&hit(1, 10.0)));
+        let webview = hit(1, 0.0).webview;
+        assert_eq!(
+            hovers.hover(second, Some(&hit(1, 20.0))),
+            vec![(webview, second, Vec2::new(20.0, 0.0), false)]
+        );
+        assert!(hovers.hover(first, Some(&hit(1, 15.0))).is_empty());
+        assert_eq!(
+            hovers.hover(second, None),
+            vec![(webview, first, Vec2::new(15.0, 0.0), false)]
+        );
+
+        hovers.hover(second, Some(&hit(1, 20.0)));
+        hovers.raise(first, webview);
+        assert!(hovers.hover(second, Some(&hit(1, 25.0))).is_empty());
+        assert_eq!(
+            hovers.hover(first, None),
+            vec![(webview, second, Vec2::new(25.0, 0.0), false)]
+        );
+    }
+
+    #[test]
+    fn release_goes_to_the_pressed_webview() {
+        let a = hit(1, 10.0);
+        let moved = hit(1, 15.0);
+        let mut buttons = RayPointerButtons::default();
+        buttons.press(MouseButton::Left, &a);
+        buttons.track(Some(&moved));
+        buttons.track(Some(&hit(2, 99.0)));
+        assert_eq!(
+            buttons.release(MouseButton::Left, Some(&hit(2, 99.0))),
+            Some((a.webview, moved.position))
+        );
+        assert!(buttons.capture.is_none());
+        assert_eq!(buttons.release(MouseButton::Left, Some(&a)), None);
+    }
+}
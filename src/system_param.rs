@@ -1,2 +1,4 @@
 pub mod mesh_aabb;
+pub mod mesh_uv;
 pub mod pointer;
+pub mod projection;
@@ -0,0 +1,146 @@
+use bevy::camera::primitives::Aabb;
+use bevy::math::bounding::{Aabb3d, IntersectsVolume, RayCast3d};
+use bevy::mesh::{PrimitiveTopology, VertexAttributeValues};
+use bevy::prelude::*;
+
+/// Casts `ray` at `mesh` placed by `transform` and interpolates `ATTRIBUTE_UV_0`
+/// at the nearest triangle hit.
+///
+/// Returns the pixel position on a `tex_size` texture and the world-space
+/// distance along the ray. Both triangle faces are hit. `None` for meshes
+/// without positions or UVs, non-triangle-list topologies, misses, and UVs
+/// outside `0..=1`. A ray missing the mesh's local `aabb` is rejected before
+/// any triangle is tested.
+pub(crate) fn ray_mesh_uv(
+    mesh: &Mesh,
+    transform: &GlobalTransform,
+    aabb: Option<&Aabb>,
+    ray: Ray3d,
+    tex_size: Vec2,
+) -> Option<(Vec2, f32)> {
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return None;
+    }
+    let positions = mesh.attribute(Mesh::ATTRIBUTE_POSITION)?.as_float3()?;
+    let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute(Mesh::ATTRIBUTE_UV_0) else {
+        return None;
+    };
+
+    let inverse = transform.affine().inverse();
+    let origin = inverse.transform_point3(ray.origin);
+    let direction = inverse.transform_vector3(*ray.direction);
+    if let Some(aabb) = aabb {
+        let local_ray = RayCast3d::new(origin, Dir3::new(direction).ok()?, f32::MAX);
+        if !local_ray.intersects(&Aabb3d::new(aabb.center, aabb.half_extents)) {
+            return None;
+        }
+    }
+
+    let (t, [a, b, c], bary) = match mesh.indices() {
+        Some(indices) => nearest_triangle(indices.iter(), positions, origin, direction),
+        None => nearest_triangle(0..positions.len(), positions, origin, direction),
+    }?;
+
+    let uv = Vec2::from(*uvs.get(a)?) * bary.x
+        + Vec2::from(*uvs.get(b)?) * bary.y
+        + Vec2::from(*uvs.get(c)?) * bary.z;
+    if !(0.0..=1.0).contains(&uv.x) || !(0.0..=1.0).contains(&uv.y) {
+        return None;
+    }
+    let hit = transform.transform_point(origin + direction * t);
+    Some((uv * tex_size, hit.distance(ray.origin)))
+}
+
+/// The nearest triangle of the triangle list `indices` that the local-space ray
+/// hits, with the ray parameter and barycentric weights of the hit.
+fn nearest_triangle(
+    mut indices: impl Iterator<Item = usize>,
+    positions: &[[f32; 3]],
+    origin: Vec3,
+    direction: Vec3,
+) -> Option<(f32, [usize; 3], Vec3)> {
+    std::iter::from_fn(|| Some([indices.next()?, indices.next()?, indices.next()?]))
+        .filter_map(|[a, b, c]| {
+            let corners = [
+                Vec3::from(*positions.get(a)?),
+                Vec3::from(*positions.get(b)?),
+                Vec3::from(*positions.get(c)?),
+            ];
+            let (t, bary) = intersect_triangle(origin, direction, corners)?;
+            Some((t, [a, b, c], bary))
+        })
+        .min_by(|x, y| x.0.total_cmp(&y.0))
+}
+
+/// Möller–Trumbore intersection, returning the ray parameter and the
+/// barycentric weights of the three corners.
+fn intersect_triangle(origin: Vec3, direction: Vec3, [a, b, c]: [Vec3; 3]) -> Option<(f32, Vec3)> {
+    let edge1 = b - a;
+    let edge2 = c - a;
+    let p = direction.cross(edge2);
+    let det = edge1.dot(p);
+    if det.abs() < f32::EPSILON {
+        return None;
+    }
+    let inv_det = det.recip();
+    let s = origin - a;
+    let u = s.dot(p) * inv_det;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let q = s.cross(edge1);
+    let v = direction.dot(q) * inv_det;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+    let t = edge2.dot(q) * inv_det;
+    (t > 0.0).then_some((t, Vec3::new(1.0 - u - v, u, v)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interpolates_rectangle_uvs() {
+        // 2x1 rectangle facing +Z; UV (0, 0) is its top-left corner.
+        let mesh = Mesh::from(Rectangle::new(2.0, 1.0));
+        let ray = Ray3d::new(Vec3::new(0.5, 0.25, 5.0), Dir3::NEG_Z);
+        let (pos, distance) = ray_mesh_uv(
+            &mesh,
+            &GlobalTransform::IDENTITY,
+            mesh.compute_aabb().as_ref(),
+            ray,
+            Vec2::new(800.0, 400.0),
+        )
+        .unwrap();
+        assert!(pos.abs_diff_eq(Vec2::new(600.0, 100.0), 1e-3));
+        assert!((distance - 5.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn follows_the_mesh_transform() {
+        let mesh = Mesh::from(Rectangle::new(2.0, 1.0));
+        let transform =
+            GlobalTransform::from(Transform::from_xyz(10.0, 0.0, 0.0).with_scale(Vec3::splat(2.0)));
+        let hit = |x: f32| {
+            let ray = Ray3d::new(Vec3::new(x, 0.0, 5.0), Dir3::NEG_Z);
+            ray_mesh_uv(&mesh, &transform, None, ray, Vec2::new(100.0, 100.0))
+        };
+        assert!(hit(10.0).unwrap().0.abs_diff_eq(Vec2::splat(50.0), 1e-3));
+        assert!(hit(8.5).unwrap().0.abs_diff_eq(Vec2::new(12.5, 50.0), 1e-3));
+        assert_eq!(hit(7.0), None);
+    }
+
+    #[test]
+    fn rejects_rays_outside_the_bounds() {
+        let mesh = Mesh::from(Rectangle::new(2.0, 1.0));
+        let ray = Ray3d::new(Vec3::new(0.5, 0.25, 5.0), Dir3::NEG_Z);
+        let elsewhere = Aabb::from_min_max(Vec3::new(5.0, 5.0, -1.0), Vec3::new(6.0, 6.0, 1.0));
+        let hit = |aabb: Option<&Aabb>| {
+            ray_mesh_uv(&mesh, &GlobalTransform::IDENTITY, aabb, ray, Vec2::ONE)
+        };
+        assert!(hit(mesh.compute_aabb().as_ref()).is_some());
+        assert_eq!(hit(Some(&elsewhere)), None);
+    }
+}
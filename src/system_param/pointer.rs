@@ -1,16 +1,24 @@
 #[cfg(target_os = "macos")]
 use crate::common::WebviewIoSurface;
 use crate::common::WebviewSurface;
-use crate::prelude::{WebviewSize, WebviewSource};
+use crate::prelude::{WebviewSize, WebviewSource, WebviewUvMapping};
 use crate::system_param::mesh_aabb::MeshAabb;
+use crate::system_param::mesh_uv::ray_mesh_uv;
+use bevy::camera::RenderTarget;
+use bevy::camera::primitives::Aabb;
 use bevy::ecs::system::SystemParam;
+use bevy::picking::hover::HoverMap;
+use bevy::picking::pointer::PointerId;
 use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
 use std::fmt::Debug;
 
 #[derive(SystemParam)]
 pub struct WebviewPointer<'w, 's, C: Component = Camera3d> {
     aabb: MeshAabb<'w, 's>,
     cameras: Query<'w, 's, (Entity, &'static Camera, &'static GlobalTransform), With<C>>,
+    camera_targets: Query<'w, 's, &'static RenderTarget>,
+    primary_window: Query<'w, 's, Entity, With<PrimaryWindow>>,
     webviews: Query<
         'w,
         's,
@@ -18,6 +26,8 @@ pub struct WebviewPointer<'w, 's, C: Component = Camera3d> {
         (With<WebviewSource>, Without<Camera>),
     >,
     parents: Query<'w, 's, (Option<&'static ChildOf>, Has<WebviewSource>)>,
+    uv_mapped: Query<'w, 's, (&'static Mesh3d, Option<&'static Aabb>), With<WebviewUvMapping>>,
+    meshes: Res<'w, Assets<Mesh>>,
     surfaces: Query<'w, 's, &'static WebviewSurface>,
     images: Res<'w, Assets<Image>>,
     /// [macOS GPU OSR] Per-webview retained IOSurface for on-demand transparent
@@ -38,19 +48,7 @@ impl<C: Component> WebviewPointer<'_, '_, C> {
     }
 
     pub fn pointer_pos(&self, webview: Entity, viewport_pos: Vec2) -> Option<Vec2> {
-        let (min, max) = self.aabb.calculate_local(webview);
-        let aabb_size = Vec2::new(max.x - min.x, max.y - min.y);
-        let (webview_gtf, webview_size) = self.webviews.get(webview).ok()?;
-        let pos = self.cameras.iter().find_map(|(_, camera, camera_gtf)| {
-            pointer_to_webview_uv(
-                viewport_pos,
-                camera,
-                camera_gtf,
-                webview_gtf,
-                aabb_size,
-                webview_size.0,
-            )
-        })?;
+        let (pos, _) = self.pointer_pos_raw(webview, viewport_pos)?;
         if self.is_transparent_at(webview, pos) {
             return None;
         }
@@ -61,21 +59,12 @@ impl<C: Component> WebviewPointer<'_, '_, C> {
     /// Returns the pixel position AND the camera entity that produced the hit.
     /// Used for drag region hit-testing.
     pub fn pointer_pos_raw(&self, webview: Entity, viewport_pos: Vec2) -> Option<(Vec2, Entity)> {
-        let (min, max) = self.aabb.calculate_local(webview);
-        let aabb_size = Vec2::new(max.x - min.x, max.y - min.y);
-        let (webview_gtf, webview_size) = self.webviews.get(webview).ok()?;
         self.cameras
             .iter()
             .find_map(|(cam_entity, camera, camera_gtf)| {
-                pointer_to_webview_uv(
-                    viewport_pos,
-                    camera,
-                    camera_gtf,
-                    webview_gtf,
-                    aabb_size,
-                    webview_size.0,
-                )
-                .map(|pos| (pos, cam_entity))
+                let ray = camera.viewport_to_world(camera_gtf, viewport_pos).ok()?;
+                self.ray_pos_raw(webview, ray)
+                    .map(|(pos, _)| (pos, cam_entity))
             })
     }
 
@@ -93,6 +82,54 @@ impl<C: Component> WebviewPointer<'_, '_, C> {
         Some((webview, pos, cam_entity))
     }
 
+    /// Casts a world-space `ray` at `webview`, returning the pixel position hit
+    /// and the distance along the ray. Transparent pixels are not hit.
+    pub fn ray_pos(&self, webview: Entity, ray: Ray3d) -> Option<(Vec2, f32)> {
+        let (pos, distance) = self.ray_pos_raw(webview, ray)?;
+        if self.is_transparent_at(webview, pos) {
+            return None;
+        }
+        Some((pos, distance))
+    }
+
+    /// Maps `ray` onto `webview` through its mesh UVs when it has
+    /// [`WebviewUvMapping`], or onto the plane of its bounding box otherwise.
+    fn ray_pos_raw(&self, webview: Entity, ray: Ray3d) -> Option<(Vec2, f32)> {
+        let (webview_gtf, webview_size) = self.webviews.get(webview).ok()?;
+        if let Ok((mesh, aabb)) = self.uv_mapped.get(webview) {
+            let mesh = self.meshes.get(&mesh.0)?;
+            return ray_mesh_uv(mesh, webview_gtf, aabb, ray, webview_size.0);
+        }
+        let (min, max) = self.aabb.calculate_local(webview);
+        let aabb_size = Vec2::new(max.x - min.x, max.y - min.y);
+        ray_to_webview_uv(ray, webview_gtf, aabb_size, webview_size.0)
+    }
+
+    /// The viewport position `pos` on `webview` is displayed at in `window`:
+    /// the inverse of [`Self::pointer_pos_raw`], through the plane of its
+    /// bounding box.
+    pub fn viewport_pos(&self, webview: Entity, window: Entity, pos: Vec2) -> Option<Vec2> {
+        let (webview_gtf, webview_size) = self.webviews.get(webview).ok()?;
+        let (min, max) = self.aabb.calculate_local(webview);
+        let aabb_size = Vec2::new(max.x - min.x, max.y - min.y);
+        let world =
+            webview_gtf.transform_point(webview_pos_to_local(pos, aabb_size, webview_size.0));
+        self.cameras
+            .iter()
+            .filter(|(camera_entity, ..)| self.renders_to(*camera_entity, window))
+            .find_map(|(_, camera, camera_gtf)| camera.world_to_viewport(camera_gtf, world).ok())
+    }
+
+    /// Whether `camera` renders to `window`.
+    pub(crate) fn renders_to(&self, camera: Entity, window: Entity) -> bool {
+        let Ok(RenderTarget::Window(target)) = self.camera_targets.get(camera) else {
+            return false;
+        };
+        target
+            .normalize(self.primary_window.single().ok())
+            .is_some_and(|target| target.entity() == window)
+    }
+
     fn is_transparent_at(&self, webview: Entity, pos: Vec2) -> bool {
         let Ok((_, webview_size)) = self.webviews.get(webview) else {
             return false;
@@ -134,15 +171,35 @@ pub(crate) fn find_webview_entity(
     None
 }
 
-fn pointer_to_webview_uv(
-    cursor_pos: Vec2,
-    camera: &Camera,
-    cam_tf: &GlobalTransform,
+/// The nearest webview picked under the mouse, if any.
+pub(crate) fn hovered_webview(
+    hover_map: &HoverMap,
+    parents: &Query<(Option<&ChildOf>, Has<WebviewSource>)>,
+) -> Option<Entity> {
+    hover_map
+        .get(&PointerId::Mouse)?
+        .iter()
+        .filter_map(|(entity, hit)| Some((find_webview_entity(*entity, parents)?, hit.depth)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(webview, _)| webview)
+}
+
+/// The plane-local point of pixel `pos`, as [`ray_to_webview_uv`] lays the
+/// texture out over the plane.
+fn webview_pos_to_local(pos: Vec2, plane_size: Vec2, tex_size: Vec2) -> Vec3 {
+    let u = pos.x / tex_size.x;
+    let v = 1.0 - pos.y / tex_size.y;
+    Vec3::new((u - 0.5) * plane_size.x, (v - 0.5) * plane_size.y, 0.0)
+}
+
+/// Intersects `ray` with a webview plane, returning the pixel position and the
+/// distance along the ray.
+fn ray_to_webview_uv(
+    ray: Ray3d,
     plane_tf: &GlobalTransform,
     plane_size: Vec2,
     tex_size: Vec2,
-) -> Option<Vec2> {
-    let ray = camera.viewport_to_world(cam_tf, cursor_pos).ok()?;
+) -> Option<(Vec2, f32)> {
     let n = plane_tf.forward().as_vec3();
     let t = ray.intersect_plane(
         plane_tf.translation(),
@@ -170,5 +227,5 @@ fn pointer_to_webview_uv(
     }
     let px = u * tex_size.x;
     let py = (1.0 - v) * tex_size.y;
-    Some(Vec2::new(px, py))
+    Some((Vec2::new(px, py), t))
 }
@@ -0,0 +1,136 @@
+use crate::common::{WebviewSize, WebviewSource};
+use crate::system_param::pointer::{WebviewPointer, find_webview_entity, hovered_webview};
+use crate::webview::prelude::WebviewUiMaterial;
+use crate::webview::webview_sprite::obtain_relative_pos;
+use bevy::ecs::system::SystemParam;
+use bevy::picking::hover::HoverMap;
+use bevy::prelude::*;
+use bevy::ui::UiGlobalTransform;
+
+/// Projects a viewport position onto a webview, whichever display path it uses.
+///
+/// Used by input that is not delivered through picking events on the webview
+/// itself, such as captured touches and OS file drags.
+#[derive(SystemParam)]
+pub(crate) struct WebviewProjection<'w, 's> {
+    pointer: WebviewPointer<'w, 's>,
+    parents: Query<'w, 's, (Option<&'static ChildOf>, Has<WebviewSource>)>,
+    cameras: Query<'w, 's, (&'static Camera, &'static GlobalTransform)>,
+    camera_entities: Query<'w, 's, (Entity, &'static Camera, &'static GlobalTransform)>,
+    sprites: Query<
+        'w,
+        's,
+        (
+            &'static Sprite,
+            &'static WebviewSize,
+            &'static GlobalTransform,
+        ),
+    >,
+    nodes: Query<
+        'w,
+        's,
+        (&'static ComputedNode, &'static UiGlobalTransform),
+        With<MaterialNode<WebviewUiMaterial>>,
+    >,
+}
+
+impl WebviewProjection<'_, '_> {
+    /// Returns the webview `entity` belongs to, walking up its ancestors.
+    pub(crate) fn webview_of(&self, entity: Entity) -> Option<Entity> {
+        find_webview_entity(entity, &self.parents)
+    }
+
+    /// The nearest webview picked under the mouse, if any.
+    pub(crate) fn hovered(&self, hover_map: &HoverMap) -> Option<Entity> {
+        hovered_webview(hover_map, &self.parents)
+    }
+
+    /// Returns the DIP position of `viewport_pos` on `webview`, skipping the
+    /// transparency test: the caller has already decided which webview the
+    /// input belongs to.
+    pub(crate) fn project(&self, webview: Entity, viewport_pos: Vec2) -> Option<Vec2> {
+        if let Ok((node, transform)) = self.nodes.get(webview) {
+            return Some(ui_pointer_pos(
+                viewport_pos,
+                transform.translation,
+                node.size(),
+                node.inverse_scale_factor(),
+            ));
+        }
+        if let Ok((sprite, size, transform)) = self.sprites.get(webview) {
+            return obtain_relative_pos(sprite, size, transform, &self.cameras, viewport_pos);
+        }
+        self.pointer
+            .pointer_pos_raw(webview, viewport_pos)
+            .map(|(pos, _)| pos)
+    }
+
+    /// Returns the logical viewport position the DIP position `pos` on
+    /// `webview` is displayed at in `window`: the inverse of [`Self::project`].
+    pub(crate) fn viewport_pos(&self, webview: Entity, window: Entity, pos: Vec2) -> Option<Vec2> {
+        if let Ok((node, transform)) = self.nodes.get(webview) {
+            return Some(ui_viewport_pos(
+                pos,
+                transform.translation,
+                node.size(),
+                node.inverse_scale_factor(),
+            ));
+        }
+        if let Ok((sprite, size, transform)) = self.sprites.get(webview) {
+            let sprite_size = sprite.custom_size?;
+            let center = self
+                .camera_entities
+                .iter()
+                .filter(|(camera_entity, ..)| self.pointer.renders_to(*camera_entity, window))
+                .find_map(|(_, camera, camera_gtf)| {
+                    camera
+                        .world_to_viewport(camera_gtf, transform.translation())
+                        .ok()
+                })?;
+            return Some(center - sprite_size / 2.0 + pos / size.0 * sprite_size);
+        }
+        self.pointer.viewport_pos(webview, window, pos)
+    }
+}
+
+/// Converts a DIP position on a UI node back into a logical viewport position;
+/// the inverse of [`ui_pointer_pos`].
+fn ui_viewport_pos(pos: Vec2, center: Vec2, size: Vec2, inverse_scale_factor: f32) -> Vec2 {
+    (center - size * 0.5) * inverse_scale_factor + pos
+}
+
+/// Converts a logical viewport position into a DIP position on a UI node whose
+/// center and size are in physical pixels.
+fn ui_pointer_pos(viewport_pos: Vec2, center: Vec2, size: Vec2, inverse_scale_factor: f32) -> Vec2 {
+    let physical = viewport_pos / inverse_scale_factor;
+    (physical - center + size * 0.5) * inverse_scale_factor
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ui_pointer_pos_is_top_left_dip() {
+        // 200x100 physical node centered at (300, 150), scale factor 2.
+        let center = Vec2::new(300.0, 150.0);
+        let size = Vec2::new(200.0, 100.0);
+        assert_eq!(
+            ui_pointer_pos(Vec2::new(100.0, 50.0), center, size, 0.5),
+            Vec2::ZERO
+        );
+        assert_eq!(
+            ui_pointer_pos(Vec2::new(150.0, 75.0), center, size, 0.5),
+            Vec2::new(50.0, 25.0)
+        );
+    }
+
+    #[test]
+    fn ui_viewport_pos_inverts_ui_pointer_pos() {
+        let center = Vec2::new(300.0, 150.0);
+        let size = Vec2::new(200.0, 100.0);
+        let viewport = Vec2::new(130.0, 60.0);
+        let dip = ui_pointer_pos(viewport, center, size, 0.5);
+        assert_eq!(ui_viewport_pos(dip, center, size, 0.5), viewport);
+    }
+}
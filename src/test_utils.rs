@@ -0,0 +1,572 @@
+//! Drives real webviews in a windowless Bevy app for integration tests.
+//!
+//! [`WebviewTestApp`] boots [`CefPlugin`] without a window or GPU and pumps
+//! the app itself, so tests wait on what the page actually did — loads,
+//! titles, IPC messages, DOM text, painted frames — each with a timeout.
+//! Clicks and typing are aimed at CSS selectors: the element's rect is
+//! resolved in the page and the input goes through the same browser calls as
+//! real mouse and keyboard input.
+//!
+//! CEF can be initialized once per process and re-launches the test binary
+//! for its subprocesses, so run the tests in a target of their own, without
+//! libtest's harness, and share one app between them:
+//!
+//! ```toml
+//! [[test]]
+//! name = "webview"
+//! harness = false
+//! required-features = ["test-utils"]
+//! ```
+//!
+//! ```rust,no_run
+//! use bevy_cef::prelude::*;
+//! use bevy_cef::test_utils::WebviewTestApp;
+//!
+//! fn main() {
+//!     early_exit_if_subprocess();
+//!     let mut app = WebviewTestApp::new();
+//!
+//!     // Served from `assets/fixtures/form.html`; no network involved.
+//!     let webview = app.spawn(WebviewSource::local("fixtures/form.html"));
+//!     app.wait_for_load(webview).unwrap();
+//!     app.click(webview, "#name").unwrap();
+//!     app.type_text(webview, "#name", "Ferris").unwrap();
+//!     app.click(webview, "button[type=submit]").unwrap();
+//!     app.wait_for_text(webview, "#greeting", "Hello, Ferris").unwrap();
+//! }
+//! ```
+
+use crate::CefPlugin;
+use crate::cdp::{Cdp, CdpError, CdpSession};
+use crate::common::Receive;
+use crate::frame_pacing::RequestFrame;
+use crate::navigation::LoadFinished;
+use crate::title::WebviewTitle;
+use crate::webview::WebviewBrowsers;
+use bevy::app::PluginsState;
+use bevy::ecs::system::RunSystemOnce;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use bevy::render::RenderPlugin;
+use bevy::render::settings::WgpuSettings;
+use bevy::tasks::block_on;
+use bevy::tasks::futures_lite::future;
+use bevy::window::ExitCondition;
+use bevy::winit::WinitPlugin;
+use bevy_cef_core::prelude::{
+    RenderPaintElementType, RenderTextureMessage, create_cef_text_events,
+};
+use serde_json::{Value, json};
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// How long a wait lasts unless [`WebviewTestApp::with_timeout`] says
+/// otherwise.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Why a step of a webview test did not complete.
+#[derive(Debug, Clone, PartialEq)]
+pub enum HarnessError {
+    /// What was awaited did not happen in time.
+    Timeout(String),
+    /// No element matched the selector in time.
+    NoElement(String),
+    /// The evaluated script threw.
+    Script(String),
+    /// The DevTools protocol call failed.
+    Cdp(CdpError),
+}
+
+impl fmt::Display for HarnessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Timeout(what) => write!(f, "timed out waiting for {what}"),
+            Self::NoElement(selector) => write!(f, "no element matches `{selector}`"),
+            Self::Script(error) => write!(f, "script threw: {error}"),
+            Self::Cdp(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for HarnessError {}
+
+impl From<CdpError> for HarnessError {
+    fn from(error: CdpError) -> Self {
+        Self::Cdp(error)
+    }
+}
+
+/// Events of one kind recorded from the moment [`WebviewTestApp::record`]
+/// was called, consumed by [`WebviewTestApp::wait_for`].
+pub struct Recorder<T> {
+    events: Arc<Mutex<VecDeque<(Entity, T)>>>,
+}
+
+impl<T> Clone for Recorder<T> {
+    fn clone(&self) -> Self {
+        Self {
+            events: self.events.clone(),
+        }
+    }
+}
+
+impl<T> Recorder<T> {
+    fn new() -> Self {
+        Self {
+            events: Arc::default(),
+        }
+    }
+
+    fn push(&self, webview: Entity, event: T) {
+        self.events.lock().unwrap().push_back((webview, event));
+    }
+
+    /// Takes the oldest recorded event of `webview`.
+    fn take(&self, webview: Entity) -> Option<T> {
+        let mut events = self.events.lock().unwrap();
+        let index = events.iter().position(|(e, _)| *e == webview)?;
+        events.remove(index).map(|(_, event)| event)
+    }
+}
+
+/// A main-frame image painted by a webview.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebviewFrame {
+    /// Width in physical pixels.
+    pub width: u32,
+    /// Height in physical pixels.
+    pub height: u32,
+    /// BGRA pixels, rows from the top.
+    pub bgra: Vec<u8>,
+}
+
+impl WebviewFrame {
+    /// The sRGB color at `(x, y)` in physical pixels, if inside the frame.
+    pub fn pixel(&self, x: u32, y: u32) -> Option<Srgba> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let i = ((y * self.width + x) * 4) as usize;
+        let [b, g, r, a] = self.bgra[i..i + 4] else {
+            return None;
+        };
+        Some(Srgba::rgba_u8(r, g, b, a))
+    }
+}
+
+#[derive(Resource, Default)]
+struct CapturedFrames(HashMap<Entity, WebviewFrame>);
+
+/// A windowless app running [`CefPlugin`], pumped by the test itself.
+///
+/// Every wait updates the app until its condition holds or the timeout
+/// passes. Dropping the app shuts CEF down.
+pub struct WebviewTestApp {
+    app: App,
+    timeout: Duration,
+    loads: Recorder<i32>,
+}
+
+impl Default for WebviewTestApp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WebviewTestApp {
+    /// Boots CEF with the default [`CefPlugin`].
+    pub fn new() -> Self {
+        Self::with_plugin(CefPlugin::default())
+    }
+
+    /// Boots CEF with `plugin`, e.g. to register custom schemes.
+    pub fn with_plugin(plugin: CefPlugin) -> Self {
+        let plugins = DefaultPlugins
+            .set(WindowPlugin {
+                primary_window: None,
+                exit_condition: ExitCondition::DontExit,
+                close_when_requested: false,
+                ..default()
+            })
+            .set(RenderPlugin {
+                render_creation: WgpuSettings {
+                    backends: None,
+                    ..default()
+                }
+                .into(),
+                ..default()
+            })
+            // `test-utils` turns on `bevy_winit`, so `DefaultPlugins` always
+            // has a `WinitPlugin` here, with or without bevy_cef's `winit`.
+            .disable::<WinitPlugin>();
+
+        let mut app = App::new();
+        app.add_plugins((plugins, plugin))
+            .init_resource::<CapturedFrames>()
+            .add_systems(Last, capture_frames);
+
+        let mut test_app = Self {
+            app,
+            timeout: DEFAULT_TIMEOUT,
+            loads: Recorder::new(),
+        };
+        let loads = test_app.loads.clone();
+        test_app.app.add_observer(move |trigger: On<LoadFinished>| {
+            loads.push(trigger.webview, trigger.http_status_code);
+        });
+        test_app
+    }
+
+    /// Sets how long each wait lasts before failing with
+    /// [`HarnessError::Timeout`].
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// The app under test. Plugins, such as a
+    /// [`JsEmitEventPlugin`](crate::prelude::JsEmitEventPlugin), can be added
+    /// until the first update.
+    pub fn app(&mut self) -> &mut App {
+        &mut self.app
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        self.app.world_mut()
+    }
+
+    /// Spawns a webview with a [`CdpSession`], which the element lookups and
+    /// script evaluation go through.
+    pub fn spawn(&mut self, bundle: impl Bundle) -> Entity {
+        self.app
+            .world_mut()
+            .spawn((bundle, CdpSession::default()))
+            .id()
+    }
+
+    /// Runs one frame of the app.
+    pub fn update(&mut self) {
+        if self.app.plugins_state() != PluginsState::Cleaned {
+            self.app.finish();
+            self.app.cleanup();
+        }
+        self.app.update();
+        // Leaves CEF's threads room to deliver what the frame asked for.
+        std::thread::sleep(Duration::from_millis(1));
+    }
+
+    /// Updates the app until `condition` holds.
+    pub fn wait_until(
+        &mut self,
+        what: &str,
+        mut condition: impl FnMut(&mut World) -> bool,
+    ) -> Result<(), HarnessError> {
+        self.poll(what, |app| condition(app.app.world_mut()).then_some(()))
+    }
+
+    /// Starts recording the events `E`, so none is missed between the action
+    /// that fires it and [`WebviewTestApp::wait_for`].
+    pub fn record<E: EntityEvent + Clone>(&mut self) -> Recorder<E> {
+        let recorder = Recorder::new();
+        let events = recorder.clone();
+        self.app.add_observer(move |trigger: On<E>| {
+            events.push(trigger.event_target(), trigger.event().clone());
+        });
+        recorder
+    }
+
+    /// Starts recording the messages `M` that pages send with `window.cef.emit`.
+    /// Needs the app's [`JsEmitEventPlugin<M>`](crate::prelude::JsEmitEventPlugin).
+    pub fn record_ipc<M: Clone + Send + Sync + 'static>(&mut self) -> Recorder<M> {
+        let recorder = Recorder::new();
+        let events = recorder.clone();
+        self.app.add_observer(move |trigger: On<Receive<M>>| {
+            events.push(trigger.webview, trigger.payload.clone());
+        });
+        recorder
+    }
+
+    /// Waits for the next event of `webview` in `recorder`.
+    pub fn wait_for<T>(
+        &mut self,
+        recorder: &Recorder<T>,
+        webview: Entity,
+    ) -> Result<T, HarnessError> {
+        let what = format!("{} on {webview}", std::any::type_name::<T>());
+        self.poll(&what, |_| recorder.take(webview))
+    }
+
+    /// Waits for the next main-frame load of `webview` to finish, counting
+    /// loads finished since the previous wait, and returns its HTTP status.
+    pub fn wait_for_load(&mut self, webview: Entity) -> Result<i32, HarnessError> {
+        let loads = self.loads.clone();
+        self.poll(&format!("a load of {webview}"), |_| loads.take(webview))
+    }
+
+    /// Waits until the page title of `webview` is `title`.
+    pub fn wait_for_title(&mut self, webview: Entity, title: &str) -> Result<(), HarnessError> {
+        self.wait_until(&format!("title `{title}` on {webview}"), |world| {
+            world
+                .get::<WebviewTitle>(webview)
+                .is_some_and(|current| current.0 == title)
+        })
+    }
+
+    /// Evaluates `expression` in the main frame of `webview` and returns its
+    /// value. Promises are awaited.
+    pub fn eval(&mut self, webview: Entity, expression: &str) -> Result<Value, HarnessError> {
+        let params = json!({
+            "expression": expression,
+            "returnByValue": true,
+            "awaitPromise": true,
+        });
+        let deadline = Instant::now() + self.timeout;
+        let evaluated = loop {
+            let params = params.clone();
+            let mut call = Box::pin(
+                self.app
+                    .world_mut()
+                    .run_system_once(move |mut cdp: Cdp| {
+                        cdp.call_raw(webview, "Runtime.evaluate", params.clone())
+                    })
+                    .expect("CefPlugin registers the Cdp system param"),
+            );
+            let result = loop {
+                if let Some(result) = block_on(future::poll_once(&mut call)) {
+                    break result;
+                }
+                if Instant::now() >= deadline {
+                    return Err(HarnessError::Timeout(format!(
+                        "`{expression}` on {webview}"
+                    )));
+                }
+                self.update();
+            };
+            match result {
+                // The session attaches once the browser is up.
+                Err(CdpError::NoSession) if Instant::now() < deadline => self.update(),
+                result => break result?,
+            }
+        };
+        if let Some(exception) = evaluated.get("exceptionDetails") {
+            let message = exception["exception"]["description"]
+                .as_str()
+                .or_else(|| exception["text"].as_str())
+                .unwrap_or_default();
+            return Err(HarnessError::Script(message.to_string()));
+        }
+        Ok(evaluated["result"]["value"].clone())
+    }
+
+    /// Waits for an element matching `selector`, scrolls it into view and
+    /// returns its rect in DIP from the top-left of the page's viewport.
+    pub fn element_rect(&mut self, webview: Entity, selector: &str) -> Result<Rect, HarnessError> {
+        let script = format!(
+            "(() => {{
+                const element = document.querySelector({selector});
+                if (!element) return null;
+                element.scrollIntoView({{ block: 'center', inline: 'center' }});
+                const r = element.getBoundingClientRect();
+                return [r.left, r.top, r.right, r.bottom];
+            }})()",
+            selector = json!(selector),
+        );
+        let deadline = Instant::now() + self.timeout;
+        loop {
+            if let Some([left, top, right, bottom]) =
+                serde_json::from_value::<Option<[f32; 4]>>(self.eval(webview, &script)?)
+                    .ok()
+                    .flatten()
+            {
+                return Ok(Rect::new(left, top, right, bottom));
+            }
+            if Instant::now() >= deadline {
+                return Err(HarnessError::NoElement(selector.to_string()));
+            }
+            self.update();
+        }
+    }
+
+    /// Clicks the center of the element matching `selector` with the primary
+    /// button.
+    pub fn click(&mut self, webview: Entity, selector: &str) -> Result<(), HarnessError> {
+        let position = self.element_rect(webview, selector)?.center();
+        self.with_browsers(|browsers| {
+            browsers.set_focus(&webview, true);
+            browsers.send_mouse_move(&webview, &[], position, false);
+            browsers.send_mouse_click(&webview, position, PointerButton::Primary, false);
+        });
+        self.update();
+        self.with_browsers(|browsers| {
+            browsers.send_mouse_click(&webview, position, PointerButton::Primary, true);
+        });
+        self.update();
+        Ok(())
+    }
+
+    /// Focuses the element matching `selector` and types `text` into it.
+    pub fn type_text(
+        &mut self,
+        webview: Entity,
+        selector: &str,
+        text: &str,
+    ) -> Result<(), HarnessError> {
+        let script = format!(
+            "(() => {{
+                const element = document.querySelector({selector});
+                if (!element) return false;
+                element.focus();
+                return true;
+            }})()",
+            selector = json!(selector),
+        );
+        self.element_rect(webview, selector)?;
+        if self.eval(webview, &script)? != Value::Bool(true) {
+            return Err(HarnessError::NoElement(selector.to_string()));
+        }
+        self.with_browsers(|browsers| {
+            browsers.set_focus(&webview, true);
+            for event in create_cef_text_events(text) {
+                browsers.send_key(&webview, event);
+            }
+        });
+        self.update();
+        Ok(())
+    }
+
+    /// The `textContent` of the element matching `selector`.
+    pub fn text(&mut self, webview: Entity, selector: &str) -> Result<String, HarnessError> {
+        let script = format!(
+            "document.querySelector({selector})?.textContent ?? null",
+            selector = json!(selector),
+        );
+        match self.eval(webview, &script)? {
+            Value::String(text) => Ok(text),
+            _ => Err(HarnessError::NoElement(selector.to_string())),
+        }
+    }
+
+    /// Waits until the trimmed `textContent` of the element matching
+    /// `selector` is `expected`.
+    pub fn wait_for_text(
+        &mut self,
+        webview: Entity,
+        selector: &str,
+        expected: &str,
+    ) -> Result<(), HarnessError> {
+        let deadline = Instant::now() + self.timeout;
+        let mut last = None;
+        loop {
+            match self.text(webview, selector) {
+                Ok(text) if text.trim() == expected => return Ok(()),
+                Ok(text) => last = Some(text),
+                Err(HarnessError::NoElement(_)) => {}
+                Err(error) => return Err(error),
+            }
+            if Instant::now() >= deadline {
+                return Err(HarnessError::Timeout(format!(
+                    "`{selector}` to read `{expected}` on {webview} (last read {last:?})"
+                )));
+            }
+            self.update();
+        }
+    }
+
+    /// Repaints `webview` and returns the main frame, so pages that stopped
+    /// painting can be captured too.
+    pub fn capture_frame(&mut self, webview: Entity) -> Result<WebviewFrame, HarnessError> {
+        self.app
+            .world_mut()
+            .resource_mut::<CapturedFrames>()
+            .0
+            .remove(&webview);
+        self.app.world_mut().trigger(RequestFrame { webview });
+        self.poll(&format!("a frame of {webview}"), |app| {
+            app.app
+                .world_mut()
+                .resource_mut::<CapturedFrames>()
+                .0
+                .remove(&webview)
+        })
+    }
+
+    fn with_browsers(&mut self, f: impl FnOnce(&WebviewBrowsers)) {
+        f(self.app.world().non_send_resource::<WebviewBrowsers>());
+    }
+
+    fn poll<T>(
+        &mut self,
+        what: &str,
+        mut ready: impl FnMut(&mut Self) -> Option<T>,
+    ) -> Result<T, HarnessError> {
+        let deadline = Instant::now() + self.timeout;
+        loop {
+            if let Some(value) = ready(self) {
+                return Ok(value);
+            }
+            if Instant::now() >= deadline {
+                return Err(HarnessError::Timeout(what.to_string()));
+            }
+            self.update();
+        }
+    }
+}
+
+impl Drop for WebviewTestApp {
+    fn drop(&mut self) {
+        self.app.world_mut().write_message(AppExit::Success);
+        self.update();
+    }
+}
+
+fn capture_frames(
+    mut frames: ResMut<CapturedFrames>,
+    mut textures: MessageReader<RenderTextureMessage>,
+) {
+    for texture in textures.read() {
+        if texture.ty != RenderPaintElementType::View {
+            continue;
+        }
+        frames.0.insert(
+            texture.webview,
+            WebviewFrame {
+                width: texture.width,
+                height: texture.height,
+                bgra: texture.buffer.clone(),
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_pixels_are_read_as_rgba() {
+        let frame = WebviewFrame {
+            width: 2,
+            height: 1,
+            bgra: vec![0, 0, 255, 255, 255, 0, 0, 128],
+        };
+        assert_eq!(frame.pixel(0, 0), Some(Srgba::rgba_u8(255, 0, 0, 255)));
+        assert_eq!(frame.pixel(1, 0), Some(Srgba::rgba_u8(0, 0, 255, 128)));
+        assert_eq!(frame.pixel(2, 0), None);
+    }
+
+    #[test]
+    fn recorder_hands_out_events_per_webview_in_order() {
+        let recorder = Recorder::new();
+        let a = Entity::from_raw_u32(1).unwrap();
+        let b = Entity::from_raw_u32(2).unwrap();
+        recorder.push(a, 1);
+        recorder.push(b, 2);
+        recorder.push(a, 3);
+        assert_eq!(recorder.take(a), Some(1));
+        assert_eq!(recorder.take(a), Some(3));
+        assert_eq!(recorder.take(a), None);
+        assert_eq!(recorder.take(b), Some(2));
+    }
+}
@@ -0,0 +1,956 @@
+//! A browser-free backend for testing apps built on bevy_cef.
+//!
+//! [`MockCefPlugin`] stands in for [`CefPlugin`](crate::CefPlugin): it adds
+//! every webview plugin but never starts CEF, and routes browser operations to
+//! a [`MockBackend`]. The backend records what the plugin asked of it as
+//! [`MockCommand`]s, and feeds page activity (loads, titles, IPC, console
+//! output, frames) into the same channels CEF's handlers use.
+//!
+//! ```rust,no_run
+//! use bevy::prelude::*;
+//! use bevy_cef::prelude::*;
+//! use bevy_cef::testing::{MockBackend, MockCefPlugin, MockCommand};
+//!
+//! let backend = MockBackend::default();
+//! let mut app = App::new();
+//! app.add_plugins((
+//!     DefaultPlugins,
+//!     MockCefPlugin {
+//!         backend: backend.clone(),
+//!     },
+//! ));
+//! let webview = app
+//!     .world_mut()
+//!     .spawn(WebviewSource::new("https://example.com"))
+//!     .id();
+//! app.update();
+//!
+//! backend.finish_load(webview, 200);
+//! app.world_mut().trigger(RequestNavigate {
+//!     webview,
+//!     url: "https://example.com/next".to_string(),
+//! });
+//! app.update();
+//! assert!(backend.commands().contains(&MockCommand::Navigate {
+//!     webview,
+//!     url: "https://example.com/next".to_string(),
+//! }));
+//! ```
+
+use crate::common::LocalHostPlugin;
+use crate::webview::WebviewBrowsers;
+use bevy::input::touch::TouchPhase;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use bevy_cef_core::prelude::*;
+#[allow(deprecated)]
+use raw_window_handle::RawWindowHandle;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Adds bevy_cef's webview plugins on top of a [`MockBackend`] instead of CEF.
+///
+/// Add it in place of [`CefPlugin`](crate::CefPlugin), after Bevy's own
+/// plugins.
+#[derive(Default)]
+pub struct MockCefPlugin {
+    pub backend: MockBackend,
+}
+
+impl Plugin for MockCefPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_non_send(WebviewBrowsers(Box::new(self.backend.clone())))
+            .add_plugins(LocalHostPlugin);
+        crate::add_webview_plugins(app);
+    }
+}
+
+/// A browser operation recorded by [`MockBackend`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum MockCommand {
+    CreateBrowser {
+        webview: Entity,
+        url: String,
+        size: Vec2,
+        dpr: f32,
+    },
+    Close {
+        webview: Entity,
+    },
+    Navigate {
+        webview: Entity,
+        url: String,
+    },
+    Reload {
+        webview: Entity,
+    },
+    ReloadAll,
+    GoBack {
+        webview: Entity,
+    },
+    GoForward {
+        webview: Entity,
+    },
+    Resize {
+        webview: Entity,
+        size: Vec2,
+    },
+    SetDpr {
+        webview: Entity,
+        dpr: f32,
+    },
+    ScreenInfoChanged {
+        webview: Entity,
+    },
+    MouseMove {
+        webview: Entity,
+        buttons: Vec<MouseButton>,
+        position: Vec2,
+        mouse_leave: bool,
+    },
+    MouseClick {
+        webview: Entity,
+        position: Vec2,
+        button: PointerButton,
+        mouse_up: bool,
+    },
+    MouseWheel {
+        webview: Entity,
+        position: Vec2,
+        delta: Vec2,
+    },
+    Touch {
+        webview: Entity,
+        id: i32,
+        position: Vec2,
+        phase: TouchPhase,
+    },
+    Key {
+        webview: Entity,
+        windows_key_code: i32,
+        character: u16,
+        modifiers: u32,
+    },
+    EditCommand {
+        webview: Entity,
+        command: EditCommand,
+    },
+    DragEnter {
+        webview: Entity,
+        files: Vec<PathBuf>,
+        position: Vec2,
+    },
+    DragOver {
+        webview: Entity,
+        position: Vec2,
+    },
+    DragLeave {
+        webview: Entity,
+    },
+    DragDrop {
+        webview: Entity,
+        position: Vec2,
+    },
+    CancelPageDrag {
+        webview: Entity,
+    },
+    Focus {
+        webview: Entity,
+        focused: bool,
+    },
+    Emit {
+        webview: Entity,
+        id: String,
+        payload: serde_json::Value,
+    },
+    ExecuteJavascript {
+        webview: Entity,
+        code: String,
+    },
+    SetUserScripts {
+        webview: Entity,
+        count: usize,
+    },
+    SetBridgePolicy {
+        webview: Entity,
+        policy: BridgePolicyPayload,
+    },
+    ShowDevtool {
+        webview: Entity,
+    },
+    ShowDevtoolIn {
+        webview: Entity,
+        surface: Entity,
+    },
+    InspectElement {
+        webview: Entity,
+        position: Vec2,
+    },
+    OpenCdp {
+        webview: Entity,
+    },
+    CloseCdp {
+        webview: Entity,
+    },
+    /// A CDP command, `{"id": .., "method": .., "params": ..}`.
+    SendCdp {
+        webview: Entity,
+        message: serde_json::Value,
+    },
+    CloseDevtools {
+        webview: Entity,
+    },
+    SetZoomLevel {
+        webview: Entity,
+        zoom_level: f64,
+    },
+    SetAudioMuted {
+        webview: Entity,
+        muted: bool,
+    },
+    SetHidden {
+        webview: Entity,
+        hidden: bool,
+    },
+    SetFrameRate {
+        webview: Entity,
+        fps: i32,
+    },
+    Invalidate {
+        webview: Entity,
+    },
+    ImeComposition {
+        text: String,
+    },
+    ImeCancel,
+    ImeFinish,
+    ImeCommit {
+        text: String,
+    },
+}
+
+/// A [`WebviewBackend`] that records operations instead of running browsers.
+///
+/// Clones share their state, so keep one to drive and inspect the mock while
+/// the app owns the other. Creating a browser reports it as created right
+/// away, and pings are answered unless the page is [hung](Self::set_hung).
+#[derive(Clone, Default)]
+pub struct MockBackend {
+    state: Arc<Mutex<MockState>>,
+}
+
+#[derive(Default)]
+struct MockState {
+    commands: Vec<MockCommand>,
+    webviews: HashMap<Entity, MockWebview>,
+    cdp: HashMap<Entity, CdpSenderInner>,
+    #[cfg(not(target_os = "macos"))]
+    frames: Vec<RenderTextureMessage>,
+}
+
+struct MockWebview {
+    senders: WebviewSenders,
+    hung: bool,
+}
+
+impl MockBackend {
+    /// Every operation recorded so far, oldest first.
+    pub fn commands(&self) -> Vec<MockCommand> {
+        self.state().commands.clone()
+    }
+
+    /// Returns the recorded operations and forgets them.
+    pub fn take_commands(&self) -> Vec<MockCommand> {
+        std::mem::take(&mut self.state().commands)
+    }
+
+    /// Whether the webview has a browser that has not been closed.
+    pub fn has_browser(&self, webview: Entity) -> bool {
+        self.state().webviews.contains_key(&webview)
+    }
+
+    /// The channels the webview's browser reports through, for messages the
+    /// helpers below do not cover.
+    pub fn senders(&self, webview: Entity) -> Option<WebviewSenders> {
+        self.state()
+            .webviews
+            .get(&webview)
+            .map(|mock| mock.senders.clone())
+    }
+
+    /// Reports that the page started loading.
+    pub fn start_load(&self, webview: Entity) {
+        self.send(webview, |senders| {
+            let _ = senders
+                .load_handler
+                .send_blocking(LoadHandlerMessage::LoadingStateChanged {
+                    webview,
+                    is_loading: true,
+                    can_go_back: false,
+                    can_go_forward: false,
+                });
+        });
+    }
+
+    /// Reports that the page finished loading with `http_status_code`.
+    pub fn finish_load(&self, webview: Entity, http_status_code: i32) {
+        self.send(webview, |senders| {
+            let _ = senders
+                .load_handler
+                .send_blocking(LoadHandlerMessage::Finished {
+                    webview,
+                    http_status_code,
+                });
+            let _ = senders
+                .load_handler
+                .send_blocking(LoadHandlerMessage::LoadingStateChanged {
+                    webview,
+                    is_loading: false,
+                    can_go_back: false,
+                    can_go_forward: false,
+                });
+        });
+    }
+
+    /// Reports that the page navigated to `url`.
+    pub fn set_address(&self, webview: Entity, url: impl Into<String>) {
+        let url = url.into();
+        self.send(webview, |senders| {
+            let _ = senders
+                .address_changed
+                .send_blocking(AddressChangedMessage {
+                    webview,
+                    url,
+                    can_go_back: false,
+                    can_go_forward: false,
+                });
+        });
+    }
+
+    /// Reports that the page changed its title.
+    pub fn set_title(&self, webview: Entity, title: impl Into<String>) {
+        let title = title.into();
+        self.send(webview, |senders| {
+            let _ = senders
+                .title_changed
+                .send_blocking(TitleChangedMessage { webview, title });
+        });
+    }
+
+    /// Emits `payload` from a `cef://localhost` page, as
+    /// `window.cef.emit(payload)` does.
+    pub fn emit_from_page(&self, webview: Entity, payload: &impl Serialize) {
+        self.emit_from_origin(webview, "cef://localhost", payload);
+    }
+
+    /// Emits `payload` from a frame at `origin`, e.g. `https://example.com`.
+    pub fn emit_from_origin(&self, webview: Entity, origin: &str, payload: &impl Serialize) {
+        let payload = serde_json::to_string(payload).unwrap_or_default();
+        self.send(webview, |senders| {
+            let _ = senders.ipc_event.send_blocking(IpcEventRaw {
+                webview,
+                origin: origin.to_string(),
+                payload,
+            });
+        });
+    }
+
+    /// Logs `message` to the page's console.
+    pub fn log(&self, webview: Entity, level: WebviewConsoleLevel, message: impl Into<String>) {
+        let message = message.into();
+        self.send(webview, |senders| {
+            let _ = senders.console.send_blocking(ConsoleMessage {
+                webview,
+                level,
+                message,
+                source: String::new(),
+                line: 0,
+            });
+        });
+    }
+
+    /// Answers the CDP command sent with `id`: `Ok` with its result, `Err`
+    /// with a protocol error such as `{"code": -32601, "message": ".."}`.
+    pub fn cdp_reply(
+        &self,
+        webview: Entity,
+        id: i32,
+        result: Result<serde_json::Value, serde_json::Value>,
+    ) {
+        let (success, result) = match result {
+            Ok(result) => (true, result),
+            Err(error) => (false, error),
+        };
+        self.send_cdp_message(
+            webview,
+            CdpMessage::Result {
+                webview,
+                id,
+                success,
+                result,
+            },
+        );
+    }
+
+    /// Fires the CDP domain event `method`, e.g. `Network.requestWillBeSent`.
+    pub fn cdp_event(&self, webview: Entity, method: impl Into<String>, params: serde_json::Value) {
+        self.send_cdp_message(
+            webview,
+            CdpMessage::Event {
+                webview,
+                method: method.into(),
+                params,
+            },
+        );
+    }
+
+    fn send_cdp_message(&self, webview: Entity, message: CdpMessage) {
+        let sender = self.state().cdp.get(&webview).cloned();
+        if let Some(sender) = sender {
+            let _ = sender.send_blocking(message);
+        }
+    }
+
+    /// Terminates the page's render process.
+    pub fn crash(&self, webview: Entity, status: WebviewTermination) {
+        self.send(webview, |senders| {
+            let _ = senders.render_process.send_blocking(RenderProcessMessage {
+                webview,
+                event: RenderProcessEvent::Terminated {
+                    status,
+                    error_code: 0,
+                    error: String::new(),
+                },
+            });
+        });
+    }
+
+    /// Makes the page stop (or resume) answering watchdog pings.
+    pub fn set_hung(&self, webview: Entity, hung: bool) {
+        if let Some(mock) = self.state().webviews.get_mut(&webview) {
+            mock.hung = hung;
+        }
+    }
+
+    /// Paints a frame of `width` × `height` BGRA pixels filled with `bgra`.
+    ///
+    /// Linux and Windows only, where frames reach Bevy through the CPU paint
+    /// path.
+    #[cfg(not(target_os = "macos"))]
+    pub fn paint(&self, webview: Entity, width: u32, height: u32, bgra: [u8; 4]) {
+        self.state().frames.push(RenderTextureMessage {
+            webview,
+            ty: RenderPaintElementType::View,
+            width,
+            height,
+            buffer: bgra.repeat((width * height) as usize),
+        });
+    }
+
+    fn state(&self) -> MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn record(&self, command: MockCommand) {
+        self.state().commands.push(command);
+    }
+
+    fn send(&self, webview: Entity, f: impl FnOnce(&WebviewSenders)) {
+        let senders = self.senders(webview);
+        if let Some(senders) = senders {
+            f(&senders);
+        }
+    }
+}
+
+impl WebviewBackend for MockBackend {
+    fn create_browser(
+        &mut self,
+        webview: Entity,
+        uri: &str,
+        webview_size: Vec2,
+        initial_dpr: f32,
+        _requester: Requester,
+        senders: WebviewSenders,
+        _user_scripts: &[UserScriptPayload],
+        _bridge: &BridgePolicyPayload,
+        _window_handle: Option<RawWindowHandle>,
+    ) -> Result<(), BrowserCreationError> {
+        let _ = senders.lifecycle.send_blocking(BrowserLifecycleMessage {
+            webview,
+            event: BrowserLifecycleEvent::Created,
+        });
+        let mut state = self.state();
+        state.webviews.insert(
+            webview,
+            MockWebview {
+                senders,
+                hung: false,
+            },
+        );
+        state.commands.push(MockCommand::CreateBrowser {
+            webview,
+            url: uri.to_string(),
+            size: webview_size,
+            dpr: initial_dpr,
+        });
+        Ok(())
+    }
+
+    fn close(&mut self, webview: &Entity) {
+        self.state().cdp.remove(webview);
+        let Some(mock) = self.state().webviews.remove(webview) else {
+            return;
+        };
+        let _ = mock
+            .senders
+            .lifecycle
+            .send_blocking(BrowserLifecycleMessage {
+                webview: *webview,
+                event: BrowserLifecycleEvent::Closed,
+            });
+        self.record(MockCommand::Close { webview: *webview });
+    }
+
+    fn send_external_begin_frame_for(&self, _webview: &Entity) {}
+
+    fn navigate(&self, webview: &Entity, url: &str) {
+        self.record(MockCommand::Navigate {
+            webview: *webview,
+            url: url.to_string(),
+        });
+    }
+
+    fn reload_webview(&self, webview: &Entity) {
+        self.record(MockCommand::Reload { webview: *webview });
+    }
+
+    fn reload(&self) {
+        self.record(MockCommand::ReloadAll);
+    }
+
+    fn go_back(&self, webview: &Entity) {
+        self.record(MockCommand::GoBack { webview: *webview });
+    }
+
+    fn go_forward(&self, webview: &Entity) {
+        self.record(MockCommand::GoForward { webview: *webview });
+    }
+
+    fn resize(&self, webview: &Entity, size: Vec2) {
+        self.record(MockCommand::Resize {
+            webview: *webview,
+            size,
+        });
+    }
+
+    fn set_dpr(&self, webview: &Entity, dpr: f32) {
+        self.record(MockCommand::SetDpr {
+            webview: *webview,
+            dpr,
+        });
+    }
+
+    fn notify_screen_info_changed(&self, webview: &Entity) {
+        self.record(MockCommand::ScreenInfoChanged { webview: *webview });
+    }
+
+    fn send_mouse_move(
+        &self,
+        webview: &Entity,
+        buttons: &[MouseButton],
+        position: Vec2,
+        mouse_leave: bool,
+    ) {
+        self.record(MockCommand::MouseMove {
+            webview: *webview,
+            buttons: buttons.to_vec(),
+            position,
+            mouse_leave,
+        });
+    }
+
+    fn send_mouse_click(
+        &self,
+        webview: &Entity,
+        position: Vec2,
+        button: PointerButton,
+        mouse_up: bool,
+    ) {
+        self.record(MockCommand::MouseClick {
+            webview: *webview,
+            position,
+            button,
+            mouse_up,
+        });
+    }
+
+    fn send_mouse_wheel(&self, webview: &Entity, position: Vec2, delta: Vec2) {
+        self.record(MockCommand::MouseWheel {
+            webview: *webview,
+            position,
+            delta,
+        });
+    }
+
+    fn send_touch(&self, webview: &Entity, id: i32, position: Vec2, phase: TouchPhase, _: f32) {
+        self.record(MockCommand::Touch {
+            webview: *webview,
+            id,
+            position,
+            phase,
+        });
+    }
+
+    fn send_key(&self, webview: &Entity, event: cef::KeyEvent) {
+        self.record(MockCommand::Key {
+            webview: *webview,
+            windows_key_code: event.windows_key_code,
+            character: event.character,
+            modifiers: event.modifiers,
+        });
+    }
+
+    fn exec_edit_command(&self, webview: &Entity, cmd: EditCommand) {
+        self.record(MockCommand::EditCommand {
+            webview: *webview,
+            command: cmd,
+        });
+    }
+
+    fn drag_target_enter(&self, webview: &Entity, files: &[PathBuf], position: Vec2) {
+        self.record(MockCommand::DragEnter {
+            webview: *webview,
+            files: files.to_vec(),
+            position,
+        });
+    }
+
+    fn drag_target_over(&self, webview: &Entity, position: Vec2) {
+        self.record(MockCommand::DragOver {
+            webview: *webview,
+            position,
+        });
+    }
+
+    fn drag_target_leave(&self, webview: &Entity) {
+        self.record(MockCommand::DragLeave { webview: *webview });
+    }
+
+    fn drag_target_drop(&self, webview: &Entity, position: Vec2) {
+        self.record(MockCommand::DragDrop {
+            webview: *webview,
+            position,
+        });
+    }
+
+    fn cancel_page_drag(&self, webview: &Entity) {
+        self.record(MockCommand::CancelPageDrag { webview: *webview });
+    }
+
+    fn set_focus(&self, webview: &Entity, focused: bool) {
+        self.record(MockCommand::Focus {
+            webview: *webview,
+            focused,
+        });
+    }
+
+    fn emit_event(&self, webview: &Entity, id: &str, event: &serde_json::Value) {
+        self.record(MockCommand::Emit {
+            webview: *webview,
+            id: id.to_string(),
+            payload: event.clone(),
+        });
+    }
+
+    fn execute_javascript(&self, webview: &Entity, code: &str) {
+        self.record(MockCommand::ExecuteJavascript {
+            webview: *webview,
+            code: code.to_string(),
+        });
+    }
+
+    fn set_user_scripts(&self, webview: &Entity, scripts: &[UserScriptPayload]) {
+        self.record(MockCommand::SetUserScripts {
+            webview: *webview,
+            count: scripts.len(),
+        });
+    }
+
+    fn set_bridge_policy(&self, webview: &Entity, policy: &BridgePolicyPayload) {
+        self.record(MockCommand::SetBridgePolicy {
+            webview: *webview,
+            policy: policy.clone(),
+        });
+    }
+
+    fn show_devtool(&self, webview: &Entity) {
+        self.record(MockCommand::ShowDevtool { webview: *webview });
+    }
+
+    fn show_devtool_in(
+        &mut self,
+        webview: &Entity,
+        surface: Entity,
+        _surface_size: Vec2,
+        _dpr: f32,
+        senders: WebviewSenders,
+    ) -> Result<(), BrowserCreationError> {
+        if *webview == surface || !self.state().webviews.contains_key(webview) {
+            return Err(BrowserCreationError::BrowserNotCreated);
+        }
+        self.close(&surface);
+        let _ = senders.lifecycle.send_blocking(BrowserLifecycleMessage {
+            webview: surface,
+            event: BrowserLifecycleEvent::Created,
+        });
+        let mut state = self.state();
+        state.webviews.insert(
+            surface,
+            MockWebview {
+                senders,
+                hung: false,
+            },
+        );
+        state.commands.push(MockCommand::ShowDevtoolIn {
+            webview: *webview,
+            surface,
+        });
+        Ok(())
+    }
+
+    fn inspect_element_at(&self, webview: &Entity, position: Vec2) {
+        self.record(MockCommand::InspectElement {
+            webview: *webview,
+            position,
+        });
+    }
+
+    fn open_cdp(&mut self, webview: &Entity, sender: CdpSenderInner) -> Option<bool> {
+        let mut state = self.state();
+        if !state.webviews.contains_key(webview) {
+            return Some(false);
+        }
+        state.cdp.insert(*webview, sender);
+        state
+            .commands
+            .push(MockCommand::OpenCdp { webview: *webview });
+        Some(true)
+    }
+
+    fn close_cdp(&mut self, webview: &Entity) {
+        self.state().cdp.remove(webview);
+        self.record(MockCommand::CloseCdp { webview: *webview });
+    }
+
+    fn send_cdp(&self, webview: &Entity, message: &str) -> bool {
+        self.record(MockCommand::SendCdp {
+            webview: *webview,
+            message: serde_json::from_str(message).unwrap_or_default(),
+        });
+        self.state().cdp.contains_key(webview)
+    }
+
+    fn close_devtools(&self, webview: &Entity) {
+        self.record(MockCommand::CloseDevtools { webview: *webview });
+    }
+
+    fn set_zoom_level(&self, webview: &Entity, zoom_level: f64) {
+        self.record(MockCommand::SetZoomLevel {
+            webview: *webview,
+            zoom_level,
+        });
+    }
+
+    fn set_audio_muted(&self, webview: &Entity, muted: bool) {
+        self.record(MockCommand::SetAudioMuted {
+            webview: *webview,
+            muted,
+        });
+    }
+
+    fn set_hidden(&self, webview: &Entity, hidden: bool) {
+        self.record(MockCommand::SetHidden {
+            webview: *webview,
+            hidden,
+        });
+    }
+
+    fn set_frame_rate(&self, webview: &Entity, fps: i32) {
+        self.record(MockCommand::SetFrameRate {
+            webview: *webview,
+            fps,
+        });
+    }
+
+    fn invalidate(&self, webview: &Entity) {
+        self.record(MockCommand::Invalidate { webview: *webview });
+    }
+
+    fn ping(&self, webview: &Entity) {
+        let state = self.state();
+        if let Some(mock) = state.webviews.get(webview)
+            && !mock.hung
+        {
+            let _ = mock
+                .senders
+                .render_process
+                .send_blocking(RenderProcessMessage {
+                    webview: *webview,
+                    event: RenderProcessEvent::Pong,
+                });
+        }
+    }
+
+    fn set_ime_composition(&self, text: &str, _cursor_utf16: Option<u32>) {
+        self.record(MockCommand::ImeComposition {
+            text: text.to_string(),
+        });
+    }
+
+    fn ime_cancel_composition(&self) {
+        self.record(MockCommand::ImeCancel);
+    }
+
+    fn ime_finish_composition(&self, _keep_selection: bool) {
+        self.record(MockCommand::ImeFinish);
+    }
+
+    fn set_ime_commit_text(&self, text: &str) {
+        self.record(MockCommand::ImeCommit {
+            text: text.to_string(),
+        });
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    fn try_receive_textures(&self) -> Vec<RenderTextureMessage> {
+        std::mem::take(&mut self.state().frames)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::WebviewSource;
+    use crate::navigation::{NavigationPlugin, RequestNavigate};
+    use crate::title::{TitlePlugin, WebviewTitle};
+    use bevy::render::RenderPlugin;
+    use bevy::render::settings::WgpuSettings;
+    use bevy::window::ExitCondition;
+    use bevy::winit::WinitPlugin;
+
+    fn app_with(backend: &MockBackend) -> App {
+        let mut app = App::new();
+        app.insert_non_send(WebviewBrowsers(Box::new(backend.clone())))
+            .add_plugins((NavigationPlugin, TitlePlugin));
+        app
+    }
+
+    #[test]
+    fn records_navigation_requests() {
+        let backend = MockBackend::default();
+        let mut app = app_with(&backend);
+        let webview = app.world_mut().spawn_empty().id();
+
+        app.world_mut().trigger(RequestNavigate {
+            webview,
+            url: "cef://localhost/next.html".to_string(),
+        });
+
+        assert_eq!(
+            backend.take_commands(),
+            vec![MockCommand::Navigate {
+                webview,
+                url: "cef://localhost/next.html".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn injected_title_reaches_the_webview() {
+        let backend = MockBackend::default();
+        let mut app = app_with(&backend);
+        let webview = app.world_mut().spawn(WebviewTitle::default()).id();
+        let title_sender = app
+            .world()
+            .resource::<crate::title::TitleChangedSender>()
+            .0
+            .clone();
+        backend.state().webviews.insert(
+            webview,
+            MockWebview {
+                senders: mock_senders(title_sender),
+                hung: false,
+            },
+        );
+
+        backend.set_title(webview, "Inventory");
+        app.update();
+
+        assert_eq!(
+            app.world().get::<WebviewTitle>(webview).unwrap().0,
+            "Inventory"
+        );
+    }
+
+    #[test]
+    fn mock_cef_plugin_boots_and_updates() {
+        let backend = MockBackend::default();
+        let mut app = App::new();
+        app.add_plugins((
+            DefaultPlugins
+                .set(WindowPlugin {
+                    primary_window: None,
+                    exit_condition: ExitCondition::DontExit,
+                    close_when_requested: false,
+                    ..default()
+                })
+                .set(RenderPlugin {
+                    render_creation: WgpuSettings {
+                        backends: None,
+                        ..default()
+                    }
+                    .into(),
+                    ..default()
+                })
+                .disable::<WinitPlugin>(),
+            MockCefPlugin {
+                backend: backend.clone(),
+            },
+        ));
+        app.finish();
+        app.cleanup();
+        let webview = app
+            .world_mut()
+            .spawn(WebviewSource::new("https://example.com"))
+            .id();
+
+        app.update();
+        app.update();
+
+        assert!(backend.has_browser(webview));
+    }
+
+    /// Senders whose channels are all closed except `title_changed`.
+    fn mock_senders(title_changed: TitleChangedSenderInner) -> WebviewSenders {
+        WebviewSenders {
+            ipc_event: async_channel::unbounded().0,
+            brp: async_channel::unbounded().0,
+            cursor_changed: async_channel::unbounded().0,
+            drag_regions: async_channel::unbounded().0,
+            load_handler: async_channel::unbounded().0,
+            address_changed: async_channel::unbounded().0,
+            title_changed,
+            console: async_channel::unbounded().0,
+            control: async_channel::unbounded().0,
+            key_unhandled: async_channel::unbounded().0,
+            take_focus: async_channel::unbounded().0,
+            drag_started: async_channel::unbounded().0,
+            render_process: async_channel::unbounded().0,
+            lifecycle: async_channel::unbounded().0,
+            audio: None,
+            accessibility: None,
+        }
+    }
+}
@@ -0,0 +1,250 @@
+//! Visibility-aware throttling.
+//!
+//! A webview whose [`Visibility`] resolves to hidden, or whose mesh or sprite
+//! is outside every camera's frustum, is marked hidden in CEF after
+//! [`WebviewThrottleSettings::hide_delay`] and gets no begin-frames until it is
+//! visible again. [`WebviewFrameRate`] sets a per-webview frame rate in place
+//! of the global [`BeginFrameInterval`](crate::prelude::BeginFrameInterval).
+
+use crate::common::WebviewSource;
+use crate::webview::WebviewBrowsers;
+use bevy::camera::visibility::VisibilitySystems;
+use bevy::platform::collections::{HashMap, HashSet};
+use bevy::prelude::*;
+use std::time::Duration;
+
+/// Frame rate CEF paints at when it drives compositing itself (Windows, and
+/// Linux with `multi_threaded_message_loop`).
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+const DEFAULT_FRAME_RATE: i32 = 60;
+
+/// Caps the webview at its own rate, in frames per second, instead of the
+/// global [`BeginFrameInterval`](crate::prelude::BeginFrameInterval).
+///
+/// Rates that are not positive and finite, or too small or large to give a
+/// non-zero period, fall back to the global interval.
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_cef::prelude::*;
+///
+/// fn spawn_billboard(mut commands: Commands) {
+///     commands.spawn((
+///         WebviewSource::new("https://example.com/ads"),
+///         WebviewFrameRate(5.0),
+///     ));
+/// }
+/// ```
+#[derive(Component, Debug, Copy, Clone, PartialEq, Reflect)]
+#[reflect(Component, Debug)]
+pub struct WebviewFrameRate(pub f32);
+
+/// How off-screen webviews are throttled.
+#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Resource, Debug, Default)]
+pub struct WebviewThrottleSettings {
+    /// Hide and pause webviews that are not visible. Defaults to `true`.
+    pub pause_hidden: bool,
+    /// How long a webview must stay invisible before it is hidden, so screens
+    /// at the edge of the view do not flicker between states. Showing is
+    /// immediate. Defaults to zero.
+    pub hide_delay: Duration,
+}
+
+impl Default for WebviewThrottleSettings {
+    fn default() -> Self {
+        Self {
+            pause_hidden: true,
+            hide_delay: Duration::ZERO,
+        }
+    }
+}
+
+/// Webviews currently marked hidden in CEF.
+#[derive(Resource, Default, Debug, Deref)]
+pub(crate) struct HiddenWebviews(HashSet<Entity>);
+
+pub(crate) struct ThrottlePlugin;
+
+impl Plugin for ThrottlePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WebviewThrottleSettings>()
+            .init_resource::<HiddenWebviews>()
+            .register_type::<WebviewFrameRate>()
+            .register_type::<WebviewThrottleSettings>();
+
+        app.add_systems(
+            PostUpdate,
+            update_hidden_webviews.after(VisibilitySystems::CheckVisibility),
+        );
+
+        // CEF paints on its own timer with the multi-threaded message loop, so
+        // the rate is pushed to it instead of pacing begin-frames.
+        #[cfg(any(target_os = "windows", target_os = "linux"))]
+        if app
+            .world()
+            .contains_resource::<bevy_cef_core::prelude::BrowsersProxy>()
+        {
+            app.add_systems(
+                Update,
+                sync_frame_rate.after(crate::webview::WebviewSet::CreateBrowser),
+            );
+        }
+    }
+}
+
+/// The shortest begin-frame period of a webview.
+pub(crate) fn frame_interval(rate: Option<&WebviewFrameRate>, global: Duration) -> Duration {
+    rate.filter(|rate| rate.0.is_finite() && rate.0 > 0.0)
+        .and_then(|rate| Duration::try_from_secs_f64(1.0 / rate.0 as f64).ok())
+        .filter(|period| !period.is_zero())
+        .unwrap_or(global)
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct VisibilityTracker {
+    hidden: bool,
+    invisible_for: Duration,
+}
+
+impl VisibilityTracker {
+    /// Returns the new hidden state when it changes.
+    fn update(&mut self, visible: bool, delta: Duration, hide_delay: Duration) -> Option<bool> {
+        if visible {
+            self.invisible_for = Duration::ZERO;
+            return std::mem::take(&mut self.hidden).then_some(false);
+        }
+        self.invisible_for += delta;
+        if !self.hidden && self.invisible_for >= hide_delay {
+            self.hidden = true;
+            return Some(true);
+        }
+        None
+    }
+}
+
+type WebviewVisibility<'a> = (
+    Entity,
+    Option<&'a InheritedVisibility>,
+    Option<&'a ViewVisibility>,
+    Has<Mesh3d>,
+    Has<Sprite>,
+);
+
+/// UI and headless webviews are never frustum-culled, so only their
+/// `Visibility` counts; meshes and sprites must also be in view.
+fn is_visible(
+    inherited: Option<&InheritedVisibility>,
+    view: Option<&ViewVisibility>,
+    culled: bool,
+) -> bool {
+    inherited.is_none_or(|v| v.get()) && (!culled || view.is_none_or(|v| v.get()))
+}
+
+/// Advances every webview's tracker and returns the webviews whose hidden
+/// state flipped.
+fn hidden_changes(
+    webviews: &Query<WebviewVisibility, With<WebviewSource>>,
+    settings: &WebviewThrottleSettings,
+    delta: Duration,
+    trackers: &mut HashMap<Entity, VisibilityTracker>,
+    hidden: &mut HiddenWebviews,
+) -> Vec<(Entity, bool)> {
+    trackers.retain(|entity, _| webviews.contains(*entity));
+    hidden.0.retain(|entity| webviews.contains(*entity));
+    let mut changes = Vec::new();
+    for (entity, inherited, view, is_mesh, is_sprite) in webviews.iter() {
+        let visible = !settings.pause_hidden || is_visible(inherited, view, is_mesh || is_sprite);
+        if let Some(now_hidden) =
+            trackers
+                .entry(entity)
+                .or_default()
+                .update(visible, delta, settings.hide_delay)
+        {
+            if now_hidden {
+                hidden.0.insert(entity);
+            } else {
+                hidden.0.remove(&entity);
+            }
+            changes.push((entity, now_hidden));
+        }
+    }
+    changes
+}
+
+fn update_hidden_webviews(
+    browsers: NonSend<WebviewBrowsers>,
+    time: Res<Time>,
+    settings: Res<WebviewThrottleSettings>,
+    webviews: Query<WebviewVisibility, With<WebviewSource>>,
+    mut hidden: ResMut<HiddenWebviews>,
+    mut trackers: Local<HashMap<Entity, VisibilityTracker>>,
+) {
+    let changes = hidden_changes(
+        &webviews,
+        &settings,
+        time.delta(),
+        &mut trackers,
+        &mut hidden,
+    );
+    for (webview, now_hidden) in changes {
+        browsers.set_hidden(&webview, now_hidden);
+    }
+}
+
+/// Pushes frame rates to CEF where it paints on its own timer.
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn sync_frame_rate(
+    browsers: NonSend<WebviewBrowsers>,
+    rates: Query<(Entity, &WebviewFrameRate), Changed<WebviewFrameRate>>,
+    mut removed: RemovedComponents<WebviewFrameRate>,
+) {
+    for (webview, rate) in rates.iter() {
+        browsers.set_frame_rate(&webview, (rate.0.round() as i32).clamp(1, 60));
+    }
+    for webview in removed.read() {
+        browsers.set_frame_rate(&webview, DEFAULT_FRAME_RATE);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hiding_waits_for_the_delay_and_showing_is_immediate() {
+        let delay = Duration::from_millis(500);
+        let frame = Duration::from_millis(200);
+        let mut tracker = VisibilityTracker::default();
+
+        assert_eq!(tracker.update(false, frame, delay), None);
+        assert_eq!(tracker.update(false, frame, delay), None);
+        assert_eq!(tracker.update(false, frame, delay), Some(true));
+        assert_eq!(tracker.update(false, frame, delay), None);
+        assert_eq!(tracker.update(true, frame, delay), Some(false));
+        assert_eq!(tracker.update(true, frame, delay), None);
+    }
+
+    #[test]
+    fn frame_rate_overrides_the_global_interval() {
+        let global = Duration::from_millis(1000 / 30);
+        assert_eq!(frame_interval(None, global), global);
+        assert_eq!(
+            frame_interval(Some(&WebviewFrameRate(5.0)), global),
+            Duration::from_millis(200)
+        );
+        assert_eq!(frame_interval(Some(&WebviewFrameRate(0.0)), global), global);
+    }
+
+    #[test]
+    fn unusable_frame_rates_fall_back_to_the_global_interval() {
+        let global = Duration::from_millis(1000 / 30);
+        for rate in [1e-30, f32::INFINITY, f32::NAN, f32::MAX, -5.0] {
+            assert_eq!(
+                frame_interval(Some(&WebviewFrameRate(rate)), global),
+                global,
+                "{rate}"
+            );
+        }
+    }
+}
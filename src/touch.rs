@@ -0,0 +1,198 @@
+//! Touch input forwarding.
+//!
+//! A finger is captured by the webview it first lands on: the picking
+//! `Pointer<Press>` for a touch pointer resolves the target webview (mesh,
+//! sprite or UI) and starts the touch. Later `TouchInput` moves and the final
+//! release/cancel are projected into that same webview, even once the finger
+//! leaves it, mirroring implicit pointer capture in browsers. Each finger keeps
+//! one small CEF touch id for its whole lifetime so Chromium's gesture
+//! recognizer can produce pinch-zoom, scroll and fling.
+//!
+//! The mouse paths ignore touch pointers, so pages see real `touchstart` and
+//! `pointerType: "touch"` events instead of emulated mouse clicks.
+
+use crate::system_param::projection::WebviewProjection;
+use crate::webview::WebviewBrowsers;
+use bevy::input::touch::{ForceTouch, TouchInput, TouchPhase};
+use bevy::picking::pointer::PointerId;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+
+pub(crate) struct TouchPlugin;
+
+impl Plugin for TouchPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TouchCaptures>();
+
+        app.add_observer(on_touch_pressed)
+            .add_systems(Update, forward_touches.run_if(on_message::<TouchInput>));
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CapturedTouch {
+    webview: Entity,
+    /// The id CEF sees for this finger.
+    id: i32,
+    last_pos: Vec2,
+}
+
+/// Fingers currently captured by a webview, keyed by Bevy's touch id.
+#[derive(Resource, Default, Debug)]
+pub(crate) struct TouchCaptures {
+    fingers: HashMap<u64, CapturedTouch>,
+}
+
+impl TouchCaptures {
+    /// Captures `finger` for `webview` and returns its CEF touch id, or `None`
+    /// if the finger is already captured.
+    ///
+    /// Bevy's touch ids come straight from the OS and can be arbitrarily
+    /// large, so CEF gets the lowest id not held by another finger.
+    fn capture(&mut self, finger: u64, webview: Entity, pos: Vec2) -> Option<i32> {
+        if self.fingers.contains_key(&finger) {
+            return None;
+        }
+        let id = (0..)
+            .find(|id| self.fingers.values().all(|t| t.id != *id))
+            .unwrap_or_default();
+        self.fingers.insert(
+            finger,
+            CapturedTouch {
+                webview,
+                id,
+                last_pos: pos,
+            },
+        );
+        Some(id)
+    }
+
+    fn get_mut(&mut self, finger: u64) -> Option<&mut CapturedTouch> {
+        self.fingers.get_mut(&finger)
+    }
+
+    fn release(&mut self, finger: u64) -> Option<CapturedTouch> {
+        self.fingers.remove(&finger)
+    }
+}
+
+/// Normalizes a platform force reading into CEF's `[0, 1]` pressure range;
+/// `0.0` when the device does not report force.
+fn touch_pressure(force: Option<ForceTouch>) -> f32 {
+    match force {
+        Some(ForceTouch::Normalized(force)) => force.clamp(0.0, 1.0) as f32,
+        Some(ForceTouch::Calibrated {
+            force,
+            max_possible_force,
+            ..
+        }) if max_possible_force > 0.0 => (force / max_possible_force).clamp(0.0, 1.0) as f32,
+        _ => 0.0,
+    }
+}
+
+/// The touch update to send for a `TouchInput` belonging to a captured finger.
+fn captured_update(
+    captures: &mut TouchCaptures,
+    projection: &WebviewProjection,
+    touch: &TouchInput,
+) -> Option<(CapturedTouch, TouchPhase)> {
+    match touch.phase {
+        // Started is handled by the picking press, which knows the hit entity.
+        TouchPhase::Started => None,
+        TouchPhase::Moved => {
+            let captured = captures.get_mut(touch.id)?;
+            captured.last_pos = projection.project(captured.webview, touch.position)?;
+            Some((*captured, TouchPhase::Moved))
+        }
+        TouchPhase::Ended | TouchPhase::Canceled => {
+            let mut captured = captures.release(touch.id)?;
+            if let Some(pos) = projection.project(captured.webview, touch.position) {
+                captured.last_pos = pos;
+            }
+            Some((captured, touch.phase))
+        }
+    }
+}
+
+fn on_touch_pressed(
+    trigger: On<Pointer<Press>>,
+    mut captures: ResMut<TouchCaptures>,
+    touches: Res<Touches>,
+    projection: WebviewProjection,
+    browsers: NonSend<WebviewBrowsers>,
+) {
+    let PointerId::Touch(finger) = trigger.pointer_id else {
+        return;
+    };
+    let Some(webview) = projection.webview_of(trigger.entity) else {
+        return;
+    };
+    let Some(pos) = projection.project(webview, trigger.pointer_location.position) else {
+        return;
+    };
+    // `Pointer` events bubble, so the same press can arrive once per ancestor.
+    let Some(id) = captures.capture(finger, webview, pos) else {
+        return;
+    };
+    let pressure = touch_pressure(touches.get_pressed(finger).and_then(|t| t.force()));
+    browsers.send_touch(&webview, id, pos, TouchPhase::Started, pressure);
+}
+
+fn forward_touches(
+    mut er: MessageReader<TouchInput>,
+    mut captures: ResMut<TouchCaptures>,
+    projection: WebviewProjection,
+    browsers: NonSend<WebviewBrowsers>,
+) {
+    for touch in er.read() {
+        if let Some((captured, phase)) = captured_update(&mut captures, &projection, touch) {
+            browsers.send_touch(
+                &captured.webview,
+                captured.id,
+                captured.last_pos,
+                phase,
+                touch_pressure(touch.force),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fingers_get_lowest_free_id() {
+        let mut captures = TouchCaptures::default();
+        let webview = Entity::PLACEHOLDER;
+        assert_eq!(captures.capture(9001, webview, Vec2::ZERO), Some(0));
+        assert_eq!(captures.capture(42, webview, Vec2::ZERO), Some(1));
+        assert!(captures.release(9001).is_some());
+        assert_eq!(captures.capture(7, webview, Vec2::ZERO), Some(0));
+    }
+
+    #[test]
+    fn recapturing_a_finger_is_ignored() {
+        let mut captures = TouchCaptures::default();
+        assert_eq!(
+            captures.capture(1, Entity::PLACEHOLDER, Vec2::ZERO),
+            Some(0)
+        );
+        assert_eq!(captures.capture(1, Entity::PLACEHOLDER, Vec2::ONE), None);
+        assert_eq!(captures.get_mut(1).unwrap().last_pos, Vec2::ZERO);
+    }
+
+    #[test]
+    fn pressure_is_normalized() {
+        assert_eq!(touch_pressure(None), 0.0);
+        assert_eq!(touch_pressure(Some(ForceTouch::Normalized(0.25))), 0.25);
+        assert_eq!(
+            touch_pressure(Some(ForceTouch::Calibrated {
+                force: 3.0,
+                max_possible_force: 6.0,
+                altitude_angle: None,
+            })),
+            0.5
+        );
+    }
+}
@@ -0,0 +1,347 @@
+//! URL-scoped user scripts that can be updated while a webview is running.
+//!
+//! [`UserScripts`] and [`PreloadScripts`] are resolved into an internal
+//! [`ResolvedUserScripts`] component. The resolved set is handed to CEF when the
+//! browser is created and re-sent to the render process whenever it changes —
+//! an edit to either component or a hot-reloaded [`UserScriptAsset`] — so the
+//! new scripts run from the next document load without recreating the browser.
+
+use crate::common::{PreloadScripts, ResolvedWebviewUri};
+use crate::webview::WebviewBrowsers;
+use crate::webview::WebviewSet;
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+use bevy::platform::collections::HashSet;
+use bevy::prelude::*;
+use bevy_cef_core::prelude::*;
+
+pub(super) struct UserScriptPlugin;
+
+impl Plugin for UserScriptPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<UserScripts>()
+            .init_asset::<UserScriptAsset>()
+            .init_asset_loader::<UserScriptAssetLoader>()
+            .add_systems(
+                Update,
+                resolve_user_scripts.before(WebviewSet::CreateBrowser),
+            );
+
+        app.add_systems(
+            Update,
+            push_user_scripts
+                .after(WebviewSet::CreateBrowser)
+                .run_if(any_changed_user_scripts),
+        );
+    }
+}
+
+/// Scripts injected into a webview's frames, filtered by URL and frame.
+///
+/// Unlike [`PreloadScripts`], each [`UserScript`] can be limited to URLs
+/// matching a pattern, to the main frame, and can be deferred until the DOM is
+/// ready. Changing this component (or a referenced [`UserScriptAsset`])
+/// takes effect on the next navigation or reload; pages that are already
+/// loaded are left untouched.
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_cef::prelude::*;
+///
+/// fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
+///     commands.spawn((
+///         WebviewSource::new("https://example.com"),
+///         UserScripts(vec![
+///             UserScript::inline("window.GAME = true;"),
+///             UserScript::asset(asset_server.load("scripts/overlay.js"))
+///                 .with_matches(["https://*.example.com/*"])
+///                 .main_frame_only()
+///                 .run_at(UserScriptRunAt::DocumentIdle),
+///         ]),
+///     ));
+/// }
+/// ```
+#[derive(Component, Reflect, Debug, Clone, PartialEq, Default)]
+#[reflect(Component, Debug, Default)]
+pub struct UserScripts(pub Vec<UserScript>);
+
+impl<L> From<L> for UserScripts
+where
+    L: IntoIterator<Item = UserScript>,
+{
+    fn from(scripts: L) -> Self {
+        Self(scripts.into_iter().collect())
+    }
+}
+
+/// A single user script. See [`UserScripts`].
+#[derive(Reflect, Debug, Clone, PartialEq)]
+pub struct UserScript {
+    pub source: UserScriptSource,
+    /// URL match patterns. `<all_urls>` matches everything, and `*` matches any
+    /// run of characters (e.g. `https://*.example.com/*`). An empty list
+    /// matches every URL.
+    pub matches: Vec<String>,
+    /// Skip iframes and only inject into the top-level document.
+    pub main_frame_only: bool,
+    pub run_at: UserScriptRunAt,
+}
+
+impl UserScript {
+    /// Creates a script from JavaScript source, injected into every frame at
+    /// document start.
+    pub fn inline(code: impl Into<String>) -> Self {
+        Self::new(UserScriptSource::Inline(code.into()))
+    }
+
+    /// Creates a script from a loaded `.js` asset.
+    ///
+    /// The script is injected once the asset has finished loading; editing the
+    /// file with asset hot reloading enabled re-sends it to the webview.
+    pub fn asset(handle: Handle<UserScriptAsset>) -> Self {
+        Self::new(UserScriptSource::Asset(handle))
+    }
+
+    fn new(source: UserScriptSource) -> Self {
+        Self {
+            source,
+            matches: Vec::new(),
+            main_frame_only: false,
+            run_at: UserScriptRunAt::default(),
+        }
+    }
+
+    /// Limits the script to URLs matching any of `patterns`.
+    pub fn with_matches<S: Into<String>>(mut self, patterns: impl IntoIterator<Item = S>) -> Self {
+        self.matches = patterns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Only injects the script into the main frame.
+    pub fn main_frame_only(mut self) -> Self {
+        self.main_frame_only = true;
+        self
+    }
+
+    /// Sets when the script runs relative to the page.
+    pub fn run_at(mut self, run_at: UserScriptRunAt) -> Self {
+        self.run_at = run_at;
+        self
+    }
+}
+
+/// Where a [`UserScript`]'s code comes from.
+#[derive(Reflect, Debug, Clone, PartialEq)]
+pub enum UserScriptSource {
+    Inline(String),
+    Asset(Handle<UserScriptAsset>),
+}
+
+/// JavaScript source loaded through the asset server.
+///
+/// `.js` files are also claimed by the `cef://localhost` loader, so request
+/// this type explicitly: `asset_server.load::<UserScriptAsset>("script.js")`.
+#[derive(Asset, TypePath, Debug, Clone)]
+pub struct UserScriptAsset(pub String);
+
+#[derive(Default, TypePath)]
+struct UserScriptAssetLoader;
+
+impl AssetLoader for UserScriptAssetLoader {
+    type Asset = UserScriptAsset;
+    type Settings = ();
+    type Error = std::io::Error;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _: &Self::Settings,
+        _: &mut LoadContext<'_>,
+    ) -> std::result::Result<Self::Asset, Self::Error> {
+        let mut code = String::new();
+        reader.read_to_string(&mut code).await?;
+        Ok(UserScriptAsset(code))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["js"]
+    }
+}
+
+/// The scripts actually shipped to CEF: [`PreloadScripts`] followed by
+/// [`UserScripts`], with asset sources resolved to their code.
+#[derive(Component, Debug, Clone, PartialEq, Default)]
+pub(crate) struct ResolvedUserScripts(pub(crate) Vec<UserScriptPayload>);
+
+fn resolve_user_scripts(
+    mut asset_events: MessageReader<AssetEvent<UserScriptAsset>>,
+    mut removed: RemovedComponents<UserScripts>,
+    assets: Res<Assets<UserScriptAsset>>,
+    mut webviews: Query<(
+        Entity,
+        Ref<PreloadScripts>,
+        Option<Ref<UserScripts>>,
+        &mut ResolvedUserScripts,
+    )>,
+) {
+    let assets_changed = asset_events.read().count() > 0;
+    let removed: HashSet<Entity> = removed.read().collect();
+    for (entity, preload, scripts, mut resolved) in webviews.iter_mut() {
+        let changed = assets_changed
+            || preload.is_changed()
+            || scripts.as_ref().is_some_and(Ref::is_changed)
+            || removed.contains(&entity);
+        if !changed {
+            continue;
+        }
+        resolved.set_if_neq(ResolvedUserScripts(collect_payloads(
+            &preload,
+            scripts.as_deref(),
+            &assets,
+        )));
+    }
+}
+
+fn collect_payloads(
+    preload: &PreloadScripts,
+    scripts: Option<&UserScripts>,
+    assets: &Assets<UserScriptAsset>,
+) -> Vec<UserScriptPayload> {
+    let preload = preload.0.iter().map(|code| UserScriptPayload {
+        code: code.clone(),
+        matches: Vec::new(),
+        main_frame_only: false,
+        run_at: UserScriptRunAt::DocumentStart,
+    });
+    let user = scripts.into_iter().flat_map(|s| &s.0).filter_map(|script| {
+        let code = match &script.source {
+            UserScriptSource::Inline(code) => code.clone(),
+            UserScriptSource::Asset(handle) => assets.get(handle)?.0.clone(),
+        };
+        Some(UserScriptPayload {
+            code,
+            matches: script.matches.clone(),
+            main_frame_only: script.main_frame_only,
+            run_at: script.run_at,
+        })
+    });
+    preload.chain(user).collect()
+}
+
+fn any_changed_user_scripts(
+    webviews: Query<Entity, (Changed<ResolvedUserScripts>, With<ResolvedWebviewUri>)>,
+) -> bool {
+    !webviews.is_empty()
+}
+
+fn push_user_scripts(
+    browsers: NonSend<WebviewBrowsers>,
+    webviews: Query<
+        (Entity, &ResolvedUserScripts),
+        (Changed<ResolvedUserScripts>, With<ResolvedWebviewUri>),
+    >,
+    added: Query<Entity, Added<ResolvedWebviewUri>>,
+) {
+    for (entity, scripts) in webviews.iter() {
+        // Freshly created browsers received the set through their extra info.
+        if added.contains(entity) {
+            continue;
+        }
+        browsers.set_user_scripts(&entity, &scripts.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> (World, Schedule) {
+        let mut world = World::new();
+        world.init_resource::<Assets<UserScriptAsset>>();
+        world.init_resource::<Messages<AssetEvent<UserScriptAsset>>>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(resolve_user_scripts);
+        (world, schedule)
+    }
+
+    fn resolved(world: &World, entity: Entity) -> Vec<String> {
+        world
+            .get::<ResolvedUserScripts>(entity)
+            .unwrap()
+            .0
+            .iter()
+            .map(|s| s.code.clone())
+            .collect()
+    }
+
+    #[test]
+    fn preload_scripts_come_before_user_scripts() {
+        let (mut world, mut schedule) = setup();
+        let entity = world
+            .spawn((
+                PreloadScripts::from(["a"]),
+                UserScripts::from([UserScript::inline("b").main_frame_only()]),
+                ResolvedUserScripts::default(),
+            ))
+            .id();
+        schedule.run(&mut world);
+
+        let scripts = &world.get::<ResolvedUserScripts>(entity).unwrap().0;
+        assert_eq!(scripts.len(), 2);
+        assert_eq!(scripts[0].code, "a");
+        assert!(!scripts[0].main_frame_only);
+        assert_eq!(scripts[1].code, "b");
+        assert!(scripts[1].main_frame_only);
+    }
+
+    #[test]
+    fn asset_scripts_follow_asset_changes() {
+        let (mut world, mut schedule) = setup();
+        let pending = world
+            .resource_mut::<Assets<UserScriptAsset>>()
+            .reserve_handle();
+        let entity = world
+            .spawn((
+                PreloadScripts::default(),
+                UserScripts::from([UserScript::asset(pending)]),
+                ResolvedUserScripts::default(),
+            ))
+            .id();
+        schedule.run(&mut world);
+        assert!(resolved(&world, entity).is_empty());
+
+        let handle = world
+            .resource_mut::<Assets<UserScriptAsset>>()
+            .add(UserScriptAsset("loaded".into()));
+        world.get_mut::<UserScripts>(entity).unwrap().0 = vec![UserScript::asset(handle.clone())];
+        schedule.run(&mut world);
+        assert_eq!(resolved(&world, entity), ["loaded"]);
+
+        world
+            .resource_mut::<Assets<UserScriptAsset>>()
+            .get_mut(&handle)
+            .unwrap()
+            .0 = "edited".into();
+        world.write_message(AssetEvent::<UserScriptAsset>::Modified { id: handle.id() });
+        schedule.run(&mut world);
+        assert_eq!(resolved(&world, entity), ["edited"]);
+    }
+
+    #[test]
+    fn removing_user_scripts_clears_them() {
+        let (mut world, mut schedule) = setup();
+        let entity = world
+            .spawn((
+                PreloadScripts::default(),
+                UserScripts::from([UserScript::inline("b")]),
+                ResolvedUserScripts::default(),
+            ))
+            .id();
+        schedule.run(&mut world);
+        assert_eq!(resolved(&world, entity), ["b"]);
+
+        world.entity_mut(entity).remove::<UserScripts>();
+        schedule.run(&mut world);
+        assert!(resolved(&world, entity).is_empty());
+    }
+}
@@ -1,29 +1,38 @@
 use crate::common::localhost::responser::{InlineHtmlId, InlineHtmlStore};
 use crate::common::{
-    HostWindow, IpcEventRawSender, ResolvedWebviewUri, WebviewDpr, WebviewSize, WebviewSource,
+    ControlMessageSender, HostWindow, IpcEventRawSender, ResolvedBridgePolicy, ResolvedWebviewUri,
+    WebviewDpr, WebviewSize, WebviewSource,
 };
-use crate::cursor_icon::SystemCursorIconSender;
-use crate::prelude::PreloadScripts;
+use crate::cursor_icon::CursorChangedSender;
+use crate::lifecycle::{LifecycleTransition, WebviewLifecycle, creation_failed};
+use crate::user_script::ResolvedUserScripts;
+use crate::webview::devtool::{DevToolPlugin, DevToolSurface};
 use crate::webview::mesh::MeshWebviewPlugin;
+use crate::webview::texture_target::TextureTargetPlugin;
 use crate::webview::ui::UiWebviewPlugin;
 use bevy::ecs::lifecycle::HookContext;
+use bevy::ecs::system::SystemParam;
 use bevy::ecs::world::DeferredWorld;
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
+#[cfg(feature = "winit")]
 use bevy::winit::WINIT_WINDOWS;
 use bevy_cef_core::prelude::*;
-use bevy_remote::BrpSender;
+#[cfg(feature = "winit")]
 #[allow(deprecated)]
 use raw_window_handle::HasRawWindowHandle;
+#[allow(deprecated)]
+use raw_window_handle::RawWindowHandle;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
-#[cfg(target_os = "windows")]
+#[cfg(any(target_os = "windows", target_os = "linux"))]
 use crate::common::CommandChannelReceiver;
-#[cfg(target_os = "windows")]
+#[cfg(any(target_os = "windows", target_os = "linux"))]
 use crate::common::TextureSenderRes;
 
 pub(crate) mod alpha;
+mod devtool;
 // [macos-gpu-osr] Injects the owned CEF webview GPU texture into RenderAssets<GpuImage>.
 #[cfg(target_os = "macos")]
 pub(crate) mod gpu_surface;
@@ -33,15 +42,22 @@ mod ui;
 pub(crate) mod webview_sprite;
 
 pub mod prelude {
+    pub use crate::webview::WebviewBrowsers;
     pub use crate::webview::{
-        BeginFrameInterval, RequestCloseDevtool, RequestShowDevTool, WebviewPlugin, mesh::*,
-        texture_target::*, ui::WebviewUiMaterial,
+        BeginFrameInterval, RequestCloseDevtool, RequestShowDevTool, WebviewPlugin,
+        devtool::{DevToolSurface, RequestInspectElement, RequestShowDevToolIn},
+        mesh::*,
+        texture_target::*,
+        ui::WebviewUiMaterial,
     };
+    pub use bevy_cef_core::prelude::WebviewBackend;
 }
 
 /// A Trigger event to request showing the developer tools in a webview.
 ///
 /// When you want to close the developer tools, use [`RequestCloseDevtool`].
+/// To show them inside another webview instead of a native window, use
+/// [`RequestShowDevToolIn`](crate::prelude::RequestShowDevToolIn).
 ///
 /// ```rust
 /// use bevy::prelude::*;
@@ -85,9 +101,13 @@ pub struct RequestCloseDevtool {
     pub webview: Entity,
 }
 
-/// Controls the interval between CEF external begin frame calls.
+/// The shortest interval between CEF external begin frame calls, i.e. the
+/// highest frame rate of a webview.
 ///
-/// Defaults to ~30fps. Users can override by inserting this resource:
+/// Defaults to ~30fps. Webviews only tick this fast while their page keeps
+/// painting; see [`IdleBeginFrameInterval`](crate::prelude::IdleBeginFrameInterval).
+/// Users can override it by inserting this resource, or per webview with
+/// [`WebviewFrameRate`](crate::prelude::WebviewFrameRate):
 /// ```rust,no_run
 /// use bevy::prelude::*;
 /// use bevy_cef::prelude::*;
@@ -105,6 +125,22 @@ impl Default for BeginFrameInterval {
     }
 }
 
+/// The browsers behind every webview, as the plugin's systems see them.
+///
+/// Holds CEF's [`Browsers`] unless another [`WebviewBackend`] was inserted in
+/// its place before [`WebviewPlugin`] was built, e.g. the mock of the `testing`
+/// feature. With the multi-threaded message loop, always used on Windows, it
+/// holds the `ProxyBackend` that `MessageLoopPlugin` inserted.
+#[derive(Deref, DerefMut)]
+pub struct WebviewBrowsers(pub Box<dyn WebviewBackend>);
+
+#[cfg(not(target_os = "windows"))]
+impl Default for WebviewBrowsers {
+    fn default() -> Self {
+        Self(Box::new(Browsers::default()))
+    }
+}
+
 /// System ordering for the webview lifecycle.
 #[derive(SystemSet, Clone, Debug, Hash, PartialEq, Eq)]
 pub enum WebviewSet {
@@ -124,7 +160,8 @@ pub struct WebviewPlugin;
 
 impl Plugin for WebviewPlugin {
     fn build(&self, app: &mut App) {
-        app.register_type::<RequestShowDevTool>();
+        app.register_type::<RequestShowDevTool>()
+            .add_plugins((DevToolPlugin, TextureTargetPlugin));
 
         app.configure_sets(
             Update,
@@ -138,87 +175,37 @@ impl Plugin for WebviewPlugin {
                 .chain(),
         );
 
-        // macOS/Linux: direct NonSend<Browsers>
         #[cfg(not(target_os = "windows"))]
-        {
-            app.init_non_send::<Browsers>()
-                .init_resource::<BeginFrameInterval>()
-                .add_plugins((MeshWebviewPlugin, UiWebviewPlugin))
-                .add_systems(Main, send_external_begin_frame)
-                .add_systems(
-                    Update,
-                    (
-                        resize.run_if(any_resized).in_set(WebviewSet::CommitResize),
-                        create_webview
-                            .run_if(added_webview)
-                            .in_set(WebviewSet::CreateBrowser),
-                        navigate_on_source_change,
-                    ),
-                )
-                .add_observer(apply_request_show_devtool)
-                .add_observer(apply_request_close_devtool);
-
-            #[cfg(target_os = "macos")]
-            app.add_plugins(crate::webview::gpu_surface::WebviewGpuInjectPlugin);
-        }
+        app.init_non_send::<WebviewBrowsers>();
+        app.init_resource::<BeginFrameInterval>()
+            .add_plugins((MeshWebviewPlugin, UiWebviewPlugin))
+            .add_systems(
+                Update,
+                (
+                    resize.run_if(any_resized).in_set(WebviewSet::CommitResize),
+                    create_webview
+                        .run_if(pending_webview)
+                        .in_set(WebviewSet::CreateBrowser),
+                    navigate_on_source_change,
+                ),
+            )
+            .add_observer(apply_request_show_devtool)
+            .add_observer(apply_request_close_devtool);
 
-        // Windows: BrowsersProxy already inserted by MessageLoopPlugin.
-        // No send_external_begin_frame (CEF drives compositing).
-        // Register conditional drain system that posts CefPostTask(TID_UI).
-        #[cfg(target_os = "windows")]
-        {
-            app.add_plugins((MeshWebviewPlugin, UiWebviewPlugin));
-
-            // Initialise the thread-local BrowsersCefSide on the CEF UI thread
-            // with the texture sender so that created browsers can deliver
-            // rendered frames back to Bevy.
-            let texture_sender = app.world().resource::<TextureSenderRes>().0.clone();
-            {
-                use cef::rc::Rc;
-                use cef::{ImplTask, Task, WrapTask};
-
-                cef::wrap_task! {
-                    struct InitCefBrowsersTask {
-                        sender: async_channel::Sender<RenderTextureMessage>,
-                    }
-                    impl Task {
-                        fn execute(&self) {
-                            bevy_cef_core::prelude::init_cef_browsers(self.sender.clone());
-                        }
-                    }
-                }
-                let mut task = InitCefBrowsersTask::new(texture_sender);
-                cef::post_task(cef::ThreadId::UI, Some(&mut task));
-            }
+        #[cfg(target_os = "macos")]
+        app.add_plugins(crate::webview::gpu_surface::WebviewGpuInjectPlugin);
 
-            app.add_systems(Main, post_drain_task.run_if(win_commands_pending))
-                .add_systems(
-                    Update,
-                    (
-                        resize_win
-                            .run_if(any_resized)
-                            .in_set(WebviewSet::CommitResize),
-                        create_webview_win
-                            .run_if(added_webview)
-                            .in_set(WebviewSet::CreateBrowser),
-                        navigate_on_source_change_win,
-                    ),
-                )
-                .add_observer(apply_request_show_devtool_win)
-                .add_observer(apply_request_close_devtool_win);
+        // With the multi-threaded message loop, WebviewBrowsers is the proxy
+        // MessageLoopPlugin inserted; run its commands on CEF's UI thread.
+        #[cfg(any(target_os = "windows", target_os = "linux"))]
+        if app.world().contains_resource::<TextureSenderRes>() {
+            drive_cef_thread_browsers(app);
         }
 
-        // Platform-conditional despawn hook
         app.world_mut()
             .register_component_hooks::<WebviewSource>()
-            .on_despawn(|world: DeferredWorld, ctx: HookContext| {
-                #[cfg(not(target_os = "windows"))]
-                {
-                    let mut world = world;
-                    world.non_send_mut::<Browsers>().close(&ctx.entity);
-                }
-                #[cfg(target_os = "windows")]
-                world.resource::<BrowsersProxy>().close(&ctx.entity);
+            .on_despawn(|mut world: DeferredWorld, ctx: HookContext| {
+                world.non_send_mut::<WebviewBrowsers>().close(&ctx.entity);
             });
 
         app.world_mut()
@@ -234,86 +221,166 @@ fn any_resized(webviews: Query<Entity, Changed<WebviewSize>>) -> bool {
     !webviews.is_empty()
 }
 
-fn added_webview(webviews: Query<Entity, Added<ResolvedWebviewUri>>) -> bool {
-    !webviews.is_empty()
+fn pending_webview(webviews: Query<&WebviewLifecycle, With<ResolvedWebviewUri>>) -> bool {
+    webviews.iter().any(|lifecycle| lifecycle.awaits_browser())
 }
 
-#[cfg(not(target_os = "windows"))]
-fn send_external_begin_frame(
-    mut hosts: NonSendMut<Browsers>,
-    time: Res<Time>,
-    interval: Res<BeginFrameInterval>,
-    mut timer: Local<Option<Timer>>,
-) {
-    if interval.is_changed() || timer.is_none() {
-        *timer = Some(Timer::new(interval.0, TimerMode::Repeating));
-    }
-    let timer = timer.as_mut().unwrap();
-    timer.tick(time.delta());
-    if timer.just_finished() {
-        hosts.send_external_begin_frame();
+/// Webviews with a resolved URI, of which `create_webview` builds browsers for
+/// those whose [`WebviewLifecycle`] awaits one.
+type PendingWebviews<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static ResolvedWebviewUri,
+        &'static WebviewSize,
+        &'static WebviewDpr,
+        &'static ResolvedUserScripts,
+        &'static ResolvedBridgePolicy,
+        Option<&'static HostWindow>,
+        &'static mut WebviewLifecycle,
+    ),
+>;
+
+/// The Bevy-side ends of every channel a new browser reports through.
+#[derive(SystemParam)]
+struct WebviewSenderResources<'w, #[cfg(feature = "audio")] 's> {
+    ipc_event: Res<'w, IpcEventRawSender>,
+    brp: Res<'w, crate::common::WebviewBrpSender>,
+    cursor_changed: Res<'w, CursorChangedSender>,
+    drag_regions: Res<'w, crate::drag::DraggableRegionSender>,
+    load_handler: Res<'w, crate::navigation::LoadHandlerSender>,
+    address_changed: Res<'w, crate::navigation::AddressChangedSender>,
+    title_changed: Res<'w, crate::title::TitleChangedSender>,
+    console: Res<'w, crate::console::ConsoleMessageSender>,
+    control: Res<'w, ControlMessageSender>,
+    key_unhandled: Res<'w, crate::keyboard::KeyUnhandledSender>,
+    take_focus: Res<'w, crate::focus::TakeFocusSender>,
+    drag_started: Res<'w, crate::drag_drop::DragStartedSender>,
+    render_process: Res<'w, crate::crash::RenderProcessSender>,
+    lifecycle: Res<'w, crate::lifecycle::BrowserLifecycleSender>,
+    accessibility: Option<Res<'w, crate::accessibility::AccessibilitySender>>,
+    #[cfg(feature = "audio")]
+    audio: Res<'w, crate::audio::AudioStreamSender>,
+    #[cfg(feature = "audio")]
+    audio_capture: Query<'w, 's, (), With<crate::audio::WebviewAudioCapture>>,
+}
+
+/// The senders a browser created for `webview` reports through.
+///
+/// A free function because the resources only carry the `'s` lifetime with the
+/// `audio` feature, so no single `impl` header covers both builds.
+#[cfg_attr(not(feature = "audio"), allow(unused_variables))]
+fn webview_senders(resources: &WebviewSenderResources, webview: Entity) -> WebviewSenders {
+    WebviewSenders {
+        ipc_event: resources.ipc_event.0.clone(),
+        brp: resources.brp.0.clone(),
+        cursor_changed: resources.cursor_changed.0.clone(),
+        drag_regions: resources.drag_regions.0.clone(),
+        load_handler: resources.load_handler.0.clone(),
+        address_changed: resources.address_changed.0.clone(),
+        title_changed: resources.title_changed.0.clone(),
+        console: resources.console.0.clone(),
+        control: resources.control.0.clone(),
+        key_unhandled: resources.key_unhandled.0.clone(),
+        take_focus: resources.take_focus.0.clone(),
+        drag_started: resources.drag_started.0.clone(),
+        render_process: resources.render_process.0.clone(),
+        lifecycle: resources.lifecycle.0.clone(),
+        #[cfg(feature = "audio")]
+        audio: resources
+            .audio_capture
+            .contains(webview)
+            .then(|| resources.audio.0.clone()),
+        #[cfg(not(feature = "audio"))]
+        audio: None,
+        accessibility: resources
+            .accessibility
+            .as_ref()
+            .map(|sender| sender.0.clone()),
     }
 }
 
-#[cfg(not(target_os = "windows"))]
-#[allow(clippy::too_many_arguments)]
 fn create_webview(
-    mut browsers: NonSendMut<Browsers>,
+    mut browsers: NonSendMut<WebviewBrowsers>,
+    mut commands: Commands,
     requester: Res<Requester>,
-    ipc_event_sender: Res<IpcEventRawSender>,
-    brp_sender: Res<BrpSender>,
-    cursor_icon_sender: Res<SystemCursorIconSender>,
-    drag_regions_sender: Res<crate::drag::DraggableRegionSender>,
-    load_handler_sender: Res<crate::navigation::LoadHandlerSender>,
-    address_changed_sender: Res<crate::navigation::AddressChangedSender>,
-    title_changed_sender: Res<crate::title::TitleChangedSender>,
-    webviews: Query<
-        (
-            Entity,
-            &ResolvedWebviewUri,
-            &WebviewSize,
-            &WebviewDpr,
-            &PreloadScripts,
-            Option<&HostWindow>,
-        ),
-        Added<ResolvedWebviewUri>,
-    >,
+    senders: WebviewSenderResources,
+    mut webviews: PendingWebviews,
     primary_window: Query<Entity, With<PrimaryWindow>>,
+    windows: Query<(), With<Window>>,
 ) {
+    for (entity, uri, size, dpr, user_scripts, bridge, host_window, mut lifecycle) in
+        webviews.iter_mut()
+    {
+        if !lifecycle.awaits_browser() {
+            continue;
+        }
+        let host_window = match host_window
+            .map(|w| w.0)
+            .or_else(|| primary_window.single().ok())
+        {
+            None => None,
+            Some(window) if !windows.contains(window) => {
+                creation_failed(
+                    &mut commands,
+                    entity,
+                    &mut lifecycle,
+                    BrowserCreationError::MissingWindow,
+                );
+                continue;
+            }
+            Some(window) => {
+                // Retried every frame until winit opens the window.
+                let Some(handle) = native_window_handle(window) else {
+                    continue;
+                };
+                handle
+            }
+        };
+        match browsers.create_browser(
+            entity,
+            &uri.0,
+            size.0,
+            dpr.0,
+            requester.clone(),
+            webview_senders(&senders, entity),
+            &user_scripts.0,
+            &bridge.0,
+            host_window,
+        ) {
+            Ok(()) => {
+                lifecycle.set_if_neq(lifecycle.apply(LifecycleTransition::CreateRequested));
+            }
+            Err(reason) => creation_failed(&mut commands, entity, &mut lifecycle, reason),
+        }
+    }
+}
+
+/// The native handle of `window`, or `None` while winit has not opened it yet.
+#[cfg(feature = "winit")]
+fn native_window_handle(window: Entity) -> Option<Option<RawWindowHandle>> {
     WINIT_WINDOWS.with(|winit_windows| {
         let winit_windows = winit_windows.borrow();
-        for (entity, uri, size, dpr, initialize_scripts, host_window) in webviews.iter() {
-            let host_window = host_window
-                .and_then(|w| winit_windows.get_window(w.0))
-                .or_else(|| winit_windows.get_window(primary_window.single().ok()?))
-                .and_then(|w| {
-                    #[allow(deprecated)]
-                    w.raw_window_handle().ok()
-                });
-            browsers.create_browser(
-                entity,
-                &uri.0,
-                size.0,
-                dpr.0,
-                requester.clone(),
-                ipc_event_sender.0.clone(),
-                brp_sender.clone(),
-                cursor_icon_sender.clone(),
-                drag_regions_sender.0.clone(),
-                load_handler_sender.0.clone(),
-                address_changed_sender.0.clone(),
-                title_changed_sender.0.clone(),
-                &initialize_scripts.0,
-                host_window,
-            );
-        }
-    });
+        let window = winit_windows.get_window(window)?;
+        #[allow(deprecated)]
+        Some(window.raw_window_handle().ok())
+    })
+}
+
+/// Without winit, windows have no native handle: browsers are created without
+/// a parent.
+#[cfg(not(feature = "winit"))]
+fn native_window_handle(_window: Entity) -> Option<Option<RawWindowHandle>> {
+    Some(None)
 }
 
-#[cfg(not(target_os = "windows"))]
 fn navigate_on_source_change(
-    browsers: NonSend<Browsers>,
-    webviews: Query<(Entity, &ResolvedWebviewUri), Changed<ResolvedWebviewUri>>,
+    browsers: NonSend<WebviewBrowsers>,
+    webviews: Query<
+        (Entity, &ResolvedWebviewUri),
+        (Changed<ResolvedWebviewUri>, Without<DevToolSurface>),
+    >,
     added: Query<Entity, Added<ResolvedWebviewUri>>,
 ) {
     for (entity, uri) in webviews.iter() {
@@ -324,9 +391,8 @@ fn navigate_on_source_change(
     }
 }
 
-#[cfg(not(target_os = "windows"))]
 fn resize(
-    browsers: NonSend<Browsers>,
+    browsers: NonSend<WebviewBrowsers>,
     webviews: Query<(Entity, &WebviewSize), Changed<WebviewSize>>,
 ) {
     for (webview, size) in webviews.iter() {
@@ -334,22 +400,49 @@ fn resize(
     }
 }
 
-#[cfg(not(target_os = "windows"))]
-fn apply_request_show_devtool(trigger: On<RequestShowDevTool>, browsers: NonSend<Browsers>) {
+fn apply_request_show_devtool(trigger: On<RequestShowDevTool>, browsers: NonSend<WebviewBrowsers>) {
     browsers.show_devtool(&trigger.webview);
 }
 
-#[cfg(not(target_os = "windows"))]
-fn apply_request_close_devtool(trigger: On<RequestCloseDevtool>, browsers: NonSend<Browsers>) {
+fn apply_request_close_devtool(
+    trigger: On<RequestCloseDevtool>,
+    browsers: NonSend<WebviewBrowsers>,
+) {
     browsers.close_devtools(&trigger.webview);
 }
 
-#[cfg(target_os = "windows")]
-fn win_commands_pending(proxy: Res<BrowsersProxy>) -> bool {
+/// Sets up the browsers of the multi-threaded message loop: initialises the
+/// thread-local `BrowsersCefSide` on the CEF UI thread with the texture sender,
+/// so that created browsers can deliver rendered frames back to Bevy, and
+/// drains queued [`CefCommand`]s there whenever there are any.
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn drive_cef_thread_browsers(app: &mut App) {
+    use cef::rc::Rc;
+    use cef::{ImplTask, Task, WrapTask};
+
+    let texture_sender = app.world().resource::<TextureSenderRes>().0.clone();
+    cef::wrap_task! {
+        struct InitCefBrowsersTask {
+            sender: async_channel::Sender<RenderTextureMessage>,
+        }
+        impl Task {
+            fn execute(&self) {
+                bevy_cef_core::prelude::init_cef_browsers(self.sender.clone());
+            }
+        }
+    }
+    let mut task = InitCefBrowsersTask::new(texture_sender);
+    cef::post_task(cef::ThreadId::UI, Some(&mut task));
+
+    app.add_systems(Main, post_drain_task.run_if(commands_pending));
+}
+
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn commands_pending(proxy: Res<BrowsersProxy>) -> bool {
     !proxy.is_empty()
 }
 
-#[cfg(target_os = "windows")]
+#[cfg(any(target_os = "windows", target_os = "linux"))]
 fn post_drain_task(rx: Res<CommandChannelReceiver>) {
     use cef::rc::Rc;
     use cef::{ImplTask, Task, WrapTask};
@@ -369,92 +462,3 @@ fn post_drain_task(rx: Res<CommandChannelReceiver>) {
     let mut task = DrainTask::new(receiver);
     cef::post_task(cef::ThreadId::UI, Some(&mut task));
 }
-
-#[cfg(target_os = "windows")]
-#[allow(clippy::too_many_arguments)]
-fn create_webview_win(
-    proxy: Res<BrowsersProxy>,
-    requester: Res<Requester>,
-    ipc_event_sender: Res<IpcEventRawSender>,
-    brp_sender: Res<BrpSender>,
-    cursor_icon_sender: Res<SystemCursorIconSender>,
-    drag_regions_sender: Res<crate::drag::DraggableRegionSender>,
-    load_handler_sender: Res<crate::navigation::LoadHandlerSender>,
-    address_changed_sender: Res<crate::navigation::AddressChangedSender>,
-    title_changed_sender: Res<crate::title::TitleChangedSender>,
-    webviews: Query<
-        (
-            Entity,
-            &ResolvedWebviewUri,
-            &WebviewSize,
-            &WebviewDpr,
-            &PreloadScripts,
-            Option<&HostWindow>,
-        ),
-        Added<ResolvedWebviewUri>,
-    >,
-    primary_window: Query<Entity, With<PrimaryWindow>>,
-) {
-    WINIT_WINDOWS.with(|winit_windows| {
-        let winit_windows = winit_windows.borrow();
-        for (entity, uri, size, dpr, initialize_scripts, host_window) in webviews.iter() {
-            let host_window = host_window
-                .and_then(|w| winit_windows.get_window(w.0))
-                .or_else(|| winit_windows.get_window(primary_window.single().ok()?))
-                .and_then(|w| {
-                    #[allow(deprecated)]
-                    w.raw_window_handle().ok()
-                });
-            proxy.create_browser(
-                entity,
-                &uri.0,
-                size.0,
-                dpr.0,
-                requester.clone(),
-                ipc_event_sender.0.clone(),
-                brp_sender.clone(),
-                cursor_icon_sender.clone(),
-                drag_regions_sender.0.clone(),
-                load_handler_sender.0.clone(),
-                address_changed_sender.0.clone(),
-                title_changed_sender.0.clone(),
-                &initialize_scripts.0,
-                host_window,
-            );
-        }
-    });
-}
-
-#[cfg(target_os = "windows")]
-fn navigate_on_source_change_win(
-    proxy: Res<BrowsersProxy>,
-    webviews: Query<(Entity, &ResolvedWebviewUri), Changed<ResolvedWebviewUri>>,
-    added: Query<Entity, Added<ResolvedWebviewUri>>,
-) {
-    for (entity, uri) in webviews.iter() {
-        if added.contains(entity) {
-            continue;
-        }
-        proxy.navigate(&entity, &uri.0);
-    }
-}
-
-#[cfg(target_os = "windows")]
-fn resize_win(
-    proxy: Res<BrowsersProxy>,
-    webviews: Query<(Entity, &WebviewSize), Changed<WebviewSize>>,
-) {
-    for (webview, size) in webviews.iter() {
-        proxy.resize(&webview, size.0);
-    }
-}
-
-#[cfg(target_os = "windows")]
-fn apply_request_show_devtool_win(trigger: On<RequestShowDevTool>, proxy: Res<BrowsersProxy>) {
-    proxy.show_devtool(&trigger.webview);
-}
-
-#[cfg(target_os = "windows")]
-fn apply_request_close_devtool_win(trigger: On<RequestCloseDevtool>, proxy: Res<BrowsersProxy>) {
-    proxy.close_devtools(&trigger.webview);
-}
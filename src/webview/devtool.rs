@@ -0,0 +1,223 @@
+//! DevTools rendered into another webview instead of a native window.
+//!
+//! [`RequestShowDevToolIn`] replaces the browser of a surface webview with the
+//! DevTools of the inspected one. The surface keeps its sprite, UI node, mesh
+//! or texture target, so the DevTools are drawn and driven like any page. When
+//! the DevTools close, or the inspected webview goes away, the surface loads
+//! its own [`WebviewSource`](crate::prelude::WebviewSource) again.
+
+use crate::common::{WebviewDpr, WebviewSize};
+use crate::lifecycle::creation_failed;
+use crate::lifecycle::{LifecycleTransition, WebviewLifecycle};
+use crate::webview::WebviewBrowsers;
+use crate::webview::{RequestCloseDevtool, WebviewSenderResources, webview_senders};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub(crate) struct DevToolPlugin;
+
+impl Plugin for DevToolPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<RequestShowDevToolIn>()
+            .register_type::<RequestInspectElement>()
+            .register_type::<DevToolSurface>();
+
+        app.add_observer(apply_request_show_devtool_in)
+            .add_observer(apply_request_inspect_element)
+            .add_observer(release_surfaces_on_close)
+            .add_systems(Update, release_orphaned_surfaces);
+    }
+}
+
+/// A Trigger event to request showing the developer tools of `webview` inside
+/// the webview `surface`.
+///
+/// The surface's own page is closed and the DevTools take its place, at the
+/// surface's [`WebviewSize`]. They receive the surface's mouse, keyboard and
+/// touch input like any page. [`RequestCloseDevtool`] on `webview` closes them
+/// and restores the surface's page.
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_cef::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Hud;
+///
+/// #[derive(Component)]
+/// struct DevToolPanel;
+///
+/// fn inspect_hud(
+///     mut commands: Commands,
+///     hud: Query<Entity, With<Hud>>,
+///     panel: Query<Entity, With<DevToolPanel>>,
+/// ) {
+///     commands.trigger(RequestShowDevToolIn {
+///         webview: hud.single().unwrap(),
+///         surface: panel.single().unwrap(),
+///     });
+/// }
+/// ```
+#[derive(Reflect, Debug, Copy, Clone, Serialize, Deserialize, EntityEvent)]
+#[reflect(Serialize, Deserialize)]
+pub struct RequestShowDevToolIn {
+    /// The webview to inspect.
+    #[event_target]
+    pub webview: Entity,
+    /// The webview to render the DevTools into.
+    pub surface: Entity,
+}
+
+/// A Trigger event to request inspecting the element at `position` of the
+/// webview.
+///
+/// `position` is in DIP from the top-left of the page, the same space as
+/// [`WebviewRayHit`](crate::prelude::WebviewRayHit). Selects the element in the
+/// DevTools already shown, wherever they are; otherwise opens them in a native
+/// window.
+#[derive(Reflect, Debug, Copy, Clone, Serialize, Deserialize, EntityEvent)]
+#[reflect(Serialize, Deserialize)]
+pub struct RequestInspectElement {
+    #[event_target]
+    pub webview: Entity,
+    pub position: Vec2,
+}
+
+/// Present on a webview while it shows the DevTools of `inspected`.
+#[derive(Component, Debug, Copy, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Component, Debug)]
+pub struct DevToolSurface {
+    pub inspected: Entity,
+}
+
+/// Surfaces whose DevTools are gone: the inspected webview despawned or lost
+/// its browser, or the DevTools failed to open.
+fn orphaned_surfaces(
+    surfaces: &Query<(Entity, &DevToolSurface)>,
+    lifecycles: &Query<&mut WebviewLifecycle>,
+) -> Vec<Entity> {
+    surfaces
+        .iter()
+        .filter(|(surface, devtool)| {
+            lifecycles
+                .get(*surface)
+                .is_ok_and(|l| *l == WebviewLifecycle::Failed)
+                || !lifecycles
+                    .get(devtool.inspected)
+                    .is_ok_and(|l| l.has_browser())
+        })
+        .map(|(surface, _)| surface)
+        .collect()
+}
+
+/// Hands the surface back to its own page: `close` drops the DevTools browser,
+/// after which the surface's browser is created again.
+fn release_surface(
+    commands: &mut Commands,
+    lifecycles: &mut Query<&mut WebviewLifecycle>,
+    surface: Entity,
+    close: impl FnOnce(&Entity),
+) {
+    commands.entity(surface).remove::<DevToolSurface>();
+    let Ok(mut lifecycle) = lifecycles.get_mut(surface) else {
+        return;
+    };
+    if *lifecycle == WebviewLifecycle::Failed {
+        return;
+    }
+    close(&surface);
+    lifecycle.set_if_neq(lifecycle.apply(LifecycleTransition::CloseRequested));
+}
+
+fn apply_request_show_devtool_in(
+    trigger: On<RequestShowDevToolIn>,
+    mut commands: Commands,
+    mut browsers: NonSendMut<WebviewBrowsers>,
+    senders: WebviewSenderResources,
+    mut surfaces: Query<(&WebviewSize, &WebviewDpr, &mut WebviewLifecycle)>,
+) {
+    let RequestShowDevToolIn { webview, surface } = *trigger;
+    let Ok((size, dpr, mut lifecycle)) = surfaces.get_mut(surface) else {
+        warn!("bevy_cef: cannot show the DevTools of {webview} in {surface}: not a webview");
+        return;
+    };
+    match browsers.show_devtool_in(
+        &webview,
+        surface,
+        size.0,
+        dpr.0,
+        webview_senders(&senders, surface),
+    ) {
+        Ok(()) => {
+            lifecycle.set_if_neq(lifecycle.apply(LifecycleTransition::CreateRequested));
+            commands
+                .entity(surface)
+                .insert(DevToolSurface { inspected: webview });
+        }
+        Err(reason) => creation_failed(&mut commands, surface, &mut lifecycle, reason),
+    }
+}
+
+fn apply_request_inspect_element(
+    trigger: On<RequestInspectElement>,
+    browsers: NonSend<WebviewBrowsers>,
+) {
+    browsers.inspect_element_at(&trigger.webview, trigger.position);
+}
+
+fn release_surfaces_on_close(
+    trigger: On<RequestCloseDevtool>,
+    mut commands: Commands,
+    mut browsers: NonSendMut<WebviewBrowsers>,
+    surfaces: Query<(Entity, &DevToolSurface)>,
+    mut lifecycles: Query<&mut WebviewLifecycle>,
+) {
+    for (surface, devtool) in surfaces.iter() {
+        if devtool.inspected == trigger.webview {
+            release_surface(&mut commands, &mut lifecycles, surface, |e| {
+                browsers.close(e)
+            });
+        }
+    }
+}
+
+fn release_orphaned_surfaces(
+    mut commands: Commands,
+    mut browsers: NonSendMut<WebviewBrowsers>,
+    surfaces: Query<(Entity, &DevToolSurface)>,
+    mut lifecycles: Query<&mut WebviewLifecycle>,
+) {
+    for surface in orphaned_surfaces(&surfaces, &lifecycles) {
+        release_surface(&mut commands, &mut lifecycles, surface, |e| {
+            browsers.close(e)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn surface_is_released_when_the_inspected_webview_despawns() {
+        let mut world = World::new();
+        world.insert_non_send(WebviewBrowsers::default());
+        let inspected = world.spawn(WebviewLifecycle::Ready).id();
+        let surface = world
+            .spawn((WebviewLifecycle::Ready, DevToolSurface { inspected }))
+            .id();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(release_orphaned_surfaces);
+
+        schedule.run(&mut world);
+        assert!(world.get::<DevToolSurface>(surface).is_some());
+
+        world.despawn(inspected);
+        schedule.run(&mut world);
+        assert!(world.get::<DevToolSurface>(surface).is_none());
+        assert_eq!(
+            *world.get::<WebviewLifecycle>(surface).unwrap(),
+            WebviewLifecycle::Closing
+        );
+    }
+}
@@ -54,7 +54,9 @@
 //! valid between rebind events.
 
 use crate::common::{WebviewIoSurface, WebviewSize, WebviewSource, WebviewTextureTarget};
+use crate::frame_pacing::WebviewPainted;
 use crate::prelude::{WebviewExtendStandardMaterial, WebviewSurface};
+use crate::webview::WebviewBrowsers;
 use crate::webview::texture_target::{WebviewGpuImageInjectSet, WebviewTextureSlot};
 use crate::webview::ui::WebviewUiMaterial;
 use bevy::asset::{Asset, AssetId, RenderAssetUsages};
@@ -69,7 +71,7 @@ use bevy::render::{
     texture::{DefaultImageSampler, GpuImage},
 };
 use bevy::ui_render::PreparedUiMaterial;
-use bevy_cef_core::prelude::{Browsers, RetainedIoSurface, WebviewGpuSurface};
+use bevy_cef_core::prelude::{RetainedIoSurface, WebviewGpuSurface};
 
 /// Number of consecutive main-world frames a rebind request stays active.
 ///
@@ -415,8 +417,9 @@ fn collect_webview_iosurfaces(
         Option<&mut WebviewIoSurface>,
         Option<&mut CollectedSurfaceId>,
     )>,
-    browsers: NonSend<Browsers>,
+    browsers: NonSend<WebviewBrowsers>,
     pending: ResMut<PendingWebviewIoSurfaces>,
+    mut painted: MessageWriter<WebviewPainted>,
 ) {
     // Decrement BEFORE any trigger inserts below: the same command queue applies
     // FIFO, so a `try_remove` queued here followed by a re-trigger's `try_insert`
@@ -435,13 +438,14 @@ fn collect_webview_iosurfaces(
     pending.clear();
 
     let mut new_frames: HashMap<Entity, RetainedIoSurface> = browsers
-        .take_latest_webview_iosurfaces(|entity| webviews.contains(entity))
+        .take_latest_webview_iosurfaces(&|entity| webviews.contains(entity))
         .into_iter()
         .collect();
 
     for (entity, surface, io_surface, collected_id) in webviews.iter_mut() {
         let id = surface.0.id();
         if let Some(retained) = new_frames.remove(&entity) {
+            painted.write(WebviewPainted(entity));
             // The sticky component keeps an independent retain (`clone()` =
             // CFRetain) for alpha hit-testing; the original moves to the render
             // path. `try_insert`: a despawn may already be queued at this sync point.
@@ -543,6 +547,12 @@ fn webview_blit(
         let Some(surface) = surfaces.0.get(&entry.id) else {
             continue;
         };
+        let _span = info_span!(
+            "webview_paint_upload",
+            width = entry.surface.width,
+            height = entry.surface.height
+        )
+        .entered();
         if !surface.import_and_blit(&render_device, encoder, &entry.surface) {
             bevy::log::error_once!(
                 "[macos-gpu-osr] IOSurface import failed ({}x{}); webview textures will \
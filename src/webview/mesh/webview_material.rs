@@ -1,3 +1,7 @@
+#[cfg(not(target_os = "macos"))]
+use crate::frame_pacing::WebviewPainted;
+#[cfg(not(target_os = "macos"))]
+use crate::webview::WebviewBrowsers;
 use bevy::asset::*;
 use bevy::prelude::*;
 use bevy::render::render_resource::AsBindGroup;
@@ -20,13 +24,8 @@ impl Plugin for WebviewMaterialPlugin {
         // macOS uses the GPU IOSurface accelerated-paint path and never emits
         // `RenderTextureMessage`; the CPU `OnPaint` chain is Linux/Windows-only.
         #[cfg(not(target_os = "macos"))]
-        app.add_message::<RenderTextureMessage>();
-
-        #[cfg(target_os = "linux")]
-        app.add_systems(Update, send_render_textures);
-
-        #[cfg(target_os = "windows")]
-        app.add_systems(Update, send_render_textures_win);
+        app.add_message::<RenderTextureMessage>()
+            .add_systems(Update, send_render_textures);
 
         load_internal_asset!(
             app,
@@ -49,19 +48,14 @@ pub struct WebviewMaterial {
 
 impl Material for WebviewMaterial {}
 
-#[cfg(target_os = "linux")]
-fn send_render_textures(mut ew: MessageWriter<RenderTextureMessage>, browsers: NonSend<Browsers>) {
-    for texture in browsers.try_receive_textures() {
-        ew.write(texture);
-    }
-}
-
-#[cfg(target_os = "windows")]
-fn send_render_textures_win(
+#[cfg(not(target_os = "macos"))]
+fn send_render_textures(
     mut ew: MessageWriter<RenderTextureMessage>,
-    texture_rx: Res<crate::common::TextureReceiverRes>,
+    mut painted: MessageWriter<WebviewPainted>,
+    browsers: NonSend<WebviewBrowsers>,
 ) {
-    while let Ok(texture) = texture_rx.0.try_recv() {
+    for texture in browsers.try_receive_textures() {
+        painted.write(WebviewPainted(texture.webview));
         ew.write(texture);
     }
 }
@@ -75,6 +69,12 @@ fn send_render_textures_win(
 /// is what queues `AssetEvent::Modified` → the `GpuImage` re-upload).
 #[cfg(not(target_os = "macos"))]
 pub(crate) fn update_webview_image(texture: &RenderTextureMessage, mut image: AssetMut<'_, Image>) {
+    let _span = info_span!(
+        "webview_paint_upload",
+        webview = ?texture.webview,
+        bytes = texture.buffer.len()
+    )
+    .entered();
     let expected_len = (texture.width * texture.height * 4) as usize;
     let dimensions_match = image.texture_descriptor.size.width == texture.width
         && image.texture_descriptor.size.height == texture.height;
@@ -40,13 +40,49 @@
 //! material's `prepare_assets` in the `Render` schedule (see the set's docs).
 //!
 //! This module is compiled on every platform so downstream crates never need
-//! `#[cfg]`; on non-macOS the plugin registers nothing (the headless texture
-//! path is macOS-only).
+//! `#[cfg]`. On non-macOS the rebind machinery above is unused: the plugin only
+//! registers `render_texture_targets`, which copies each CPU frame into the
+//! target `Image` itself and so already fires `Modified` for every frame.
 
+#[cfg(not(target_os = "macos"))]
+use crate::common::WebviewTextureTarget;
+#[cfg(not(target_os = "macos"))]
+use crate::prelude::update_webview_image;
 use bevy::asset::AssetId;
 use bevy::prelude::*;
+#[cfg(not(target_os = "macos"))]
+use bevy_cef_core::prelude::RenderTextureMessage;
 use std::marker::PhantomData;
 
+pub(crate) struct TextureTargetPlugin;
+
+impl Plugin for TextureTargetPlugin {
+    fn build(&self, _app: &mut App) {
+        // CPU `OnPaint` consumer: Linux/Windows only. On macOS the target is
+        // fed by the GPU IOSurface path (`gpu_surface`).
+        #[cfg(not(target_os = "macos"))]
+        _app.add_systems(
+            PostUpdate,
+            render_texture_targets.run_if(on_message::<RenderTextureMessage>),
+        );
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn render_texture_targets(
+    mut er: MessageReader<RenderTextureMessage>,
+    mut images: ResMut<Assets<Image>>,
+    webviews: Query<&WebviewTextureTarget>,
+) {
+    for texture in er.read() {
+        if let Ok(target) = webviews.get(texture.webview)
+            && let Some(image) = images.get_mut(target.0.id())
+        {
+            update_webview_image(texture, image);
+        }
+    }
+}
+
 /// Render-world system set containing bevy_cef's webview GPU texture injection
 /// (`RenderSystems::PrepareAssets` phase; populated on macOS only).
 ///
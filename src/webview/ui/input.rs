@@ -5,6 +5,7 @@
 #[cfg(target_os = "macos")]
 use crate::common::WebviewIoSurface;
 use crate::prelude::{WebviewSize, WebviewSource, WebviewSurface};
+use crate::webview::WebviewBrowsers;
 use crate::webview::alpha::is_pixel_transparent;
 #[cfg(target_os = "macos")]
 use crate::webview::alpha::is_pixel_transparent_surface;
@@ -15,10 +16,6 @@ use bevy::input::mouse::MouseScrollUnit;
 use bevy::picking::events::Scroll;
 use bevy::prelude::*;
 use bevy::ui::RelativeCursorPosition;
-#[cfg(not(target_os = "windows"))]
-use bevy_cef_core::prelude::Browsers;
-#[cfg(target_os = "windows")]
-use bevy_cef_core::prelude::BrowsersProxy;
 
 pub struct WebviewUiInputPlugin;
 
@@ -122,7 +119,6 @@ fn ui_pointer_pos_macos(
 /// On macOS, reads `WebviewIoSurface` from `webview_iosurfaces`; on other
 /// platforms (or before the first GPU frame on macOS) falls back to `Image.data`
 /// via `ui_pointer_pos`.
-#[cfg(not(target_os = "windows"))]
 fn resolve_ui_pos(
     entity: bevy::ecs::entity::Entity,
     node: UiNode,
@@ -142,18 +138,17 @@ fn resolve_ui_pos(
 }
 
 #[allow(clippy::too_many_arguments)]
-#[cfg(not(target_os = "windows"))]
 fn on_ui_pointer_move(
     trigger: On<Pointer<Move>>,
     input: Res<ButtonInput<MouseButton>>,
-    browsers: NonSend<Browsers>,
+    browsers: NonSend<WebviewBrowsers>,
     nodes: Query<UiNode, With<MaterialNode<WebviewUiMaterial>>>,
     images: Res<Assets<Image>>,
     #[cfg(target_os = "macos")] webview_iosurfaces: Query<Option<&WebviewIoSurface>>,
     drag_state: Res<crate::drag::DragState>,
     resize_state: Res<crate::resize::ResizeState>,
 ) {
-    if drag_state.is_dragging() || resize_state.is_resizing() {
+    if trigger.pointer_id.is_touch() || drag_state.is_dragging() || resize_state.is_resizing() {
         return;
     }
     let Ok(node) = nodes.get(trigger.entity) else {
@@ -168,20 +163,20 @@ fn on_ui_pointer_move(
     ) else {
         return;
     };
-    browsers.send_mouse_move(&trigger.entity, input.get_pressed(), pos, false);
+    let buttons: Vec<MouseButton> = input.get_pressed().copied().collect();
+    browsers.send_mouse_move(&trigger.entity, &buttons, pos, false);
 }
 
-#[cfg(not(target_os = "windows"))]
 fn on_ui_pointer_pressed(
     trigger: On<Pointer<Press>>,
-    browsers: NonSend<Browsers>,
+    browsers: NonSend<WebviewBrowsers>,
     nodes: Query<UiNode, With<MaterialNode<WebviewUiMaterial>>>,
     images: Res<Assets<Image>>,
     #[cfg(target_os = "macos")] webview_iosurfaces: Query<Option<&WebviewIoSurface>>,
     drag_state: Res<crate::drag::DragState>,
     resize_state: Res<crate::resize::ResizeState>,
 ) {
-    if drag_state.is_dragging() || resize_state.is_resizing() {
+    if trigger.pointer_id.is_touch() || drag_state.is_dragging() || resize_state.is_resizing() {
         return;
     }
     let Ok(node) = nodes.get(trigger.entity) else {
@@ -199,17 +194,16 @@ fn on_ui_pointer_pressed(
     browsers.send_mouse_click(&trigger.entity, pos, trigger.button, false);
 }
 
-#[cfg(not(target_os = "windows"))]
 fn on_ui_pointer_released(
     trigger: On<Pointer<Release>>,
-    browsers: NonSend<Browsers>,
+    browsers: NonSend<WebviewBrowsers>,
     nodes: Query<UiNode, With<MaterialNode<WebviewUiMaterial>>>,
     images: Res<Assets<Image>>,
     #[cfg(target_os = "macos")] webview_iosurfaces: Query<Option<&WebviewIoSurface>>,
     drag_state: Res<crate::drag::DragState>,
     resize_state: Res<crate::resize::ResizeState>,
 ) {
-    if drag_state.is_dragging() || resize_state.is_resizing() {
+    if trigger.pointer_id.is_touch() || drag_state.is_dragging() || resize_state.is_resizing() {
         return;
     }
     let Ok(node) = nodes.get(trigger.entity) else {
@@ -227,10 +221,9 @@ fn on_ui_pointer_released(
     browsers.send_mouse_click(&trigger.entity, pos, trigger.button, true);
 }
 
-#[cfg(not(target_os = "windows"))]
 fn on_ui_pointer_scroll(
     trigger: On<Pointer<Scroll>>,
-    browsers: NonSend<Browsers>,
+    browsers: NonSend<WebviewBrowsers>,
     nodes: Query<UiNode, With<MaterialNode<WebviewUiMaterial>>>,
     images: Res<Assets<Image>>,
     #[cfg(target_os = "macos")] webview_iosurfaces: Query<Option<&WebviewIoSurface>>,
@@ -256,93 +249,6 @@ fn on_ui_pointer_scroll(
     browsers.send_mouse_wheel(&trigger.entity, pos, delta);
 }
 
-#[cfg(target_os = "windows")]
-fn on_ui_pointer_move(
-    trigger: On<Pointer<Move>>,
-    input: Res<ButtonInput<MouseButton>>,
-    proxy: Res<BrowsersProxy>,
-    nodes: Query<UiNode, With<MaterialNode<WebviewUiMaterial>>>,
-    images: Res<Assets<Image>>,
-    drag_state: Res<crate::drag::DragState>,
-    resize_state: Res<crate::resize::ResizeState>,
-) {
-    if drag_state.is_dragging() || resize_state.is_resizing() {
-        return;
-    }
-    let Ok(node) = nodes.get(trigger.entity) else {
-        return;
-    };
-    let Some(pos) = ui_pointer_pos(node, &images) else {
-        return;
-    };
-    let buttons: Vec<MouseButton> = input.get_pressed().copied().collect();
-    proxy.send_mouse_move(&trigger.entity, &buttons, pos, false);
-}
-
-#[cfg(target_os = "windows")]
-fn on_ui_pointer_pressed(
-    trigger: On<Pointer<Press>>,
-    proxy: Res<BrowsersProxy>,
-    nodes: Query<UiNode, With<MaterialNode<WebviewUiMaterial>>>,
-    images: Res<Assets<Image>>,
-    drag_state: Res<crate::drag::DragState>,
-    resize_state: Res<crate::resize::ResizeState>,
-) {
-    if drag_state.is_dragging() || resize_state.is_resizing() {
-        return;
-    }
-    let Ok(node) = nodes.get(trigger.entity) else {
-        return;
-    };
-    let Some(pos) = ui_pointer_pos(node, &images) else {
-        return;
-    };
-    proxy.send_mouse_click(&trigger.entity, pos, trigger.button, false);
-}
-
-#[cfg(target_os = "windows")]
-fn on_ui_pointer_released(
-    trigger: On<Pointer<Release>>,
-    proxy: Res<BrowsersProxy>,
-    nodes: Query<UiNode, With<MaterialNode<WebviewUiMaterial>>>,
-    images: Res<Assets<Image>>,
-    drag_state: Res<crate::drag::DragState>,
-    resize_state: Res<crate::resize::ResizeState>,
-) {
-    if drag_state.is_dragging() || resize_state.is_resizing() {
-        return;
-    }
-    let Ok(node) = nodes.get(trigger.entity) else {
-        return;
-    };
-    let Some(pos) = ui_pointer_pos(node, &images) else {
-        return;
-    };
-    proxy.send_mouse_click(&trigger.entity, pos, trigger.button, true);
-}
-
-#[cfg(target_os = "windows")]
-fn on_ui_pointer_scroll(
-    trigger: On<Pointer<Scroll>>,
-    proxy: Res<BrowsersProxy>,
-    nodes: Query<UiNode, With<MaterialNode<WebviewUiMaterial>>>,
-    images: Res<Assets<Image>>,
-    drag_state: Res<crate::drag::DragState>,
-    resize_state: Res<crate::resize::ResizeState>,
-) {
-    if drag_state.is_dragging() || resize_state.is_resizing() {
-        return;
-    }
-    let Ok(node) = nodes.get(trigger.entity) else {
-        return;
-    };
-    let Some(pos) = ui_pointer_pos(node, &images) else {
-        return;
-    };
-    let delta = scroll_delta(trigger.unit, trigger.x, trigger.y);
-    proxy.send_mouse_wheel(&trigger.entity, pos, delta);
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,55 @@
+//! Drives the `assets/fixtures` pages through a real CEF browser.
+//!
+//! Runs without libtest's harness: CEF is initialized once per process and
+//! re-launches this binary for its subprocesses.
+
+#[cfg(target_os = "linux")]
+fn main() {
+    linux::main();
+}
+
+#[cfg(not(target_os = "linux"))]
+fn main() {}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use bevy::prelude::*;
+    use bevy_cef::prelude::*;
+    use bevy_cef::test_utils::WebviewTestApp;
+    use serde::Deserialize;
+
+    #[derive(Deserialize, Clone, Debug, PartialEq)]
+    struct Greeted {
+        name: String,
+    }
+
+    pub fn main() {
+        early_exit_if_subprocess();
+        let mut app = WebviewTestApp::new();
+        app.app()
+            .add_plugins(JsEmitEventPlugin::<Greeted>::default());
+        let greeted = app.record_ipc::<Greeted>();
+
+        let webview = app.spawn(WebviewSource::local("fixtures/form.html"));
+        assert_eq!(app.wait_for_load(webview).unwrap(), 200);
+        app.wait_for_title(webview, "Form Fixture").unwrap();
+
+        app.type_text(webview, "#name", "Ferris").unwrap();
+        app.click(webview, "button[type=submit]").unwrap();
+        app.wait_for_text(webview, "#greeting", "Hello, Ferris")
+            .unwrap();
+        assert_eq!(
+            app.wait_for(&greeted, webview).unwrap(),
+            Greeted {
+                name: "Ferris".to_string()
+            }
+        );
+
+        let frame = app.capture_frame(webview).unwrap();
+        assert_eq!(frame.pixel(10, 10), Some(Srgba::rgb_u8(255, 0, 0)));
+        assert_eq!(
+            frame.pixel(frame.width - 10, frame.height - 10),
+            Some(Srgba::rgb_u8(0, 0, 255))
+        );
+    }
+}